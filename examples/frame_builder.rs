@@ -0,0 +1,37 @@
+use uberbyte::crc::Crc;
+use uberbyte::framing::builder::{Checksum, FrameBuilder};
+use uberbyte::framing::LengthWidth;
+use uberbyte::Endianness;
+
+/*
+The command_byte_array example composes a command where every size is known upfront.
+This example composes a frame whose length and checksum are only known once the
+payload is complete
+
+byte 00    -> start of frame
+byte 01-02 -> length of the payload, little endian
+byte 03-.. -> payload made out of a command identifier and a MAC address
+last 2     -> CRC-16 over the length and the payload
+*/
+fn main() {
+    let mut payload = FrameBuilder::new();
+    // 2 byte command category and command identifier
+    payload.add_bytes(&[12, 3]);
+    // Target MAC address
+    payload.add_bytes(&[0xBC, 0xD0, 0x74, 0x22, 0x66, 0x37]);
+
+    let mut frame = FrameBuilder::new();
+    frame.add_bytes(&[0x7E]);
+    let start = frame.mark();
+    let length = frame.reserve_length(LengthWidth::U16(Endianness::Little));
+    let payload_start = frame.mark();
+    frame.add_nested(payload);
+    let end = frame.mark();
+    let checksum = frame.reserve_checksum(Checksum::Crc(Crc::CRC_16_XMODEM, Endianness::Big));
+
+    frame.cover(length, payload_start, end).unwrap();
+    frame.cover(checksum, start, end).unwrap();
+
+    let bytes = frame.finish().unwrap().to_u8_vec();
+    println!("Frame: {:02X?}", bytes);
+}
@@ -0,0 +1,30 @@
+use uberbyte::protocols::modbus::{FunctionCode, ModbusFrame};
+
+/*
+The command_byte_array example composes a command by hand.
+For Modbus the frame layout and the CRC are already known, so the frames can be
+built and parsed directly.
+*/
+
+fn main() {
+    // Ask slave 17 for 3 holding registers starting at address 107
+    let request = ModbusFrame::read_holding_registers(17, 107, 3).unwrap();
+
+    let rtu = request.to_rtu().to_u8_vec();
+    println!("RTU request:   {:02X?}", rtu);
+    println!("ASCII request: {:?}", request.to_ascii());
+
+    // The slave answers with the register values
+    let response = ModbusFrame::read_registers_response(
+        17,
+        FunctionCode::ReadHoldingRegisters,
+        &[0xAE41, 0x5652, 0x4340],
+    )
+    .unwrap();
+    let parsed = ModbusFrame::from_rtu(&response.to_rtu()).unwrap();
+
+    match parsed.exception_code().unwrap() {
+        Some(code) => println!("Slave responded with {:?}", code),
+        None => println!("Registers: {:04X?}", parsed.registers().unwrap()),
+    }
+}
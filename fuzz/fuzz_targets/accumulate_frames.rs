@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::framing::accumulator::{
+    Delimited, FrameAccumulator, FrameSpec, LengthPrefixed, SyncWord,
+};
+use uberbyte::framing::LengthWidth;
+use uberbyte::Endianness;
+
+fn drain<S: FrameSpec>(spec: S, data: &[u8]) {
+    let mut accumulator = FrameAccumulator::new(spec);
+
+    for chunk in data.chunks(7) {
+        accumulator.push(chunk);
+        while accumulator.next_frame().is_some() {}
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    drain(
+        LengthPrefixed::new(1, LengthWidth::U16(Endianness::Big))
+            .with_adjustment(-2)
+            .with_max_length(512),
+        data,
+    );
+    drain(Delimited::new(&[0x0D, 0x0A]), data);
+    drain(
+        SyncWord::new(
+            &[0xAA, 0x55],
+            LengthPrefixed::new(2, LengthWidth::U8).with_max_length(64),
+        ),
+        data,
+    );
+});
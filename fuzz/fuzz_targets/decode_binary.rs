@@ -0,0 +1,51 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::compression::rle::RleVariant;
+use uberbyte::ecc::EccScheme;
+use uberbyte::encoding::{cobs, slip};
+use uberbyte::line_coding::{self, Codec8b10b, ManchesterConvention};
+use uberbyte::reed_solomon::ReedSolomon;
+use uberbyte::{BitArray, BitOrder, ByteArray};
+
+fuzz_target!(|data: &[u8]| {
+    let bytes = ByteArray::from(data);
+
+    let _ = cobs::decode(&bytes);
+    let _ = slip::decode(&bytes);
+    let _ = bytes.decompress_rle(RleVariant::PackBits);
+    let _ = bytes.decompress_rle(RleVariant::Escape(0x1B));
+    let _ = bytes.decompress_lzss();
+
+    for scheme in [
+        EccScheme::Hamming74,
+        EccScheme::Hamming84,
+        EccScheme::Secded7264,
+    ] {
+        let _ = scheme.decode(&bytes);
+    }
+
+    let line = BitArray::from_byte_array(&bytes, BitOrder::LsbFirst);
+    let _ = line_coding::decode_manchester(&line, ManchesterConvention::Ieee, BitOrder::LsbFirst);
+    let _ = line_coding::decode_differential_manchester(&line, false, BitOrder::LsbFirst);
+    let _ = line_coding::decode_nrzi(&line, false, BitOrder::LsbFirst);
+    let _ = line_coding::decode_4b5b(&line, BitOrder::LsbFirst);
+    let _ = Codec8b10b::default().decode(&line);
+
+    // the first byte picks the number of parity symbols, the rest is the codeword
+    if let Some((first, rest)) = data.split_first() {
+        let codec = ReedSolomon::new(*first as usize % 32 + 1).unwrap();
+        let codeword = ByteArray::from(rest);
+
+        if let Ok(decoded) = codec.decode(&codeword) {
+            let encoded = codec.encode(&decoded).unwrap();
+            assert!(codec.decode(&encoded).unwrap()[..] == decoded[..]);
+        }
+        let erasures: Vec<usize> = rest
+            .iter()
+            .take(codec.parity_symbols())
+            .map(|position| *position as usize)
+            .collect();
+        let _ = codec.decode_with_erasures(&codeword, &erasures);
+    }
+});
@@ -0,0 +1,29 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::formats::{ihex, srec};
+use uberbyte::protocols::modbus::ModbusFrame;
+use uberbyte::ByteArray;
+
+/// Reads the payload of a parsed frame, which must not panic whatever it holds
+fn inspect(frame: &ModbusFrame) {
+    let _ = frame.registers();
+    let _ = frame.exception_code();
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(frame) = ModbusFrame::from_rtu(&ByteArray::from(data)) {
+        assert_eq!(frame, ModbusFrame::from_rtu(&frame.to_rtu()).unwrap());
+        inspect(&frame);
+    }
+
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = ihex::parse(text);
+    let _ = srec::parse(text);
+    if let Ok(frame) = ModbusFrame::from_ascii(text) {
+        inspect(&frame);
+    }
+});
@@ -0,0 +1,35 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::byte_array::search::BytePattern;
+use uberbyte::encoding::ascii85::Ascii85Variant;
+use uberbyte::encoding::base32::Base32Alphabet;
+use uberbyte::encoding::base64::Base64Config;
+use uberbyte::{ByteArray, UberByte};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(byte) = text.parse::<UberByte>() {
+        assert_eq!(Some(byte), byte.to_string().parse::<UberByte>().ok());
+    }
+
+    for config in [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD] {
+        let _ = ByteArray::from_base64(text, config);
+    }
+    for alphabet in [Base32Alphabet::Rfc4648, Base32Alphabet::Crockford] {
+        let _ = ByteArray::from_base32(text, alphabet);
+    }
+    for variant in [Ascii85Variant::Ascii85, Ascii85Variant::Z85] {
+        let _ = ByteArray::from_ascii85(text, variant);
+    }
+    let _ = ByteArray::from_base16(text);
+
+    if let Ok(pattern) = text.parse::<BytePattern>() {
+        let haystack = ByteArray::from(data);
+        let first = haystack.find_all(&pattern).first().copied();
+        assert_eq!(first, haystack.find(&pattern));
+    }
+});
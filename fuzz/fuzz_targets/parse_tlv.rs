@@ -0,0 +1,44 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::tlv::{LengthFormat, TagFormat, Tlv, TlvConfig};
+use uberbyte::{ByteArray, Endianness};
+
+const CONFIGS: [TlvConfig; 4] = [
+    TlvConfig::BER,
+    TlvConfig {
+        tag: TagFormat::U8,
+        length: LengthFormat::U8,
+    },
+    TlvConfig {
+        tag: TagFormat::U16(Endianness::Big),
+        length: LengthFormat::U16(Endianness::Little),
+    },
+    TlvConfig {
+        tag: TagFormat::U8,
+        length: LengthFormat::U32(Endianness::Big),
+    },
+];
+
+fn walk(entries: Tlv<'_>, depth: usize) {
+    for entry in entries {
+        let Ok(entry) = entry else {
+            return;
+        };
+        if depth < 16 && entry.is_constructed() {
+            walk(entry.children(), depth + 1);
+        }
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, data)) = data.split_first() else {
+        return;
+    };
+    let bytes = ByteArray::from(data);
+
+    walk(
+        Tlv::new(&bytes, CONFIGS[*selector as usize % CONFIGS.len()]),
+        0,
+    );
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uberbyte::testing::check_round_trips;
+use uberbyte::ByteArray;
+
+fuzz_target!(|data: &[u8]| {
+    if let Err(failure) = check_round_trips(&ByteArray::from(data)) {
+        panic!("{}", failure);
+    }
+});
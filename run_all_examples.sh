@@ -38,4 +38,6 @@ sample_header "shift"
 cargo run --package uberbyte --example shift
 
 sample_header "Formatters"
-cargo run --package uberbyte --example formatters
\ No newline at end of file
+cargo run --package uberbyte --example formatters
+sample_header "Modbus frame"
+cargo run --package uberbyte --example modbus_frame
@@ -0,0 +1,335 @@
+//! Command line utility to inspect and convert bytes without writing any code
+//!
+//! Build it with `cargo install uberbyte --features cli`
+
+use std::{env, fs, process::ExitCode};
+
+use uberbyte::{
+    bit_diagram::BitDiagram,
+    crc::Crc,
+    encoding::{base64, cobs, slip},
+    ByteArray, Endianness, UberByte,
+};
+
+const USAGE: &str = "\
+usage: uberbyte <command> [arguments]
+
+commands:
+  show <value>                   prints the value as decimal, hex, octal, binary and bit table
+  set <value> <bit>...           sets the given bits of the value
+  clear <value> <bit>...         clears the given bits of the value
+  flip <value> [<bit>...]        flips the given bits of the value, all bits if none are given
+  hexdump <file>                 prints the content of the file as hex and ASCII
+  crc <preset> <file>            computes the checksum of the file
+  encode <codec> <hex>           encodes the bytes with cobs, slip or base64
+  decode <codec> <input>         decodes cobs or slip hex bytes, or base64 text
+  diff <hex> <hex>               prints the bits that differ between both byte strings
+  diagram <hex> [<field>...]     draws the bit fields of up to 8 big endian bytes
+
+values are written as 0b1010_0101, 0xA5, 0o245, 165 or \"1010 0101\"
+hex byte strings are written as \"7E 01 02\", 7E0102 or 0x7E 0x01 0x02
+fields are written as NAME:7..4 or NAME:3 with the highest and lowest bit
+crc presets: crc-8, crc-16-modbus, crc-16-ccitt-false, crc-16-xmodem, crc-32, crc-32c
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(output) => {
+            print!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Executes the command given by the arguments
+///
+/// # Returns
+///
+/// The text to print, or the error message if the command failed
+fn run(args: &[String]) -> Result<String, String> {
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+
+    match args.as_slice() {
+        ["show", value] => Ok(show(parse_byte(value)?)),
+        ["set", value, bits @ ..] if !bits.is_empty() => {
+            Ok(show(parse_byte(value)?.set(parse_mask(bits)?)))
+        }
+        ["clear", value, bits @ ..] if !bits.is_empty() => {
+            Ok(show(parse_byte(value)?.clear(parse_mask(bits)?)))
+        }
+        ["flip", value] => Ok(show(parse_byte(value)?.flip())),
+        ["flip", value, bits @ ..] => Ok(show(parse_byte(value)? ^ parse_mask(bits)?)),
+        ["hexdump", path] => Ok(hexdump(&read_file(path)?)),
+        ["crc", preset, path] => {
+            let crc = crc_preset(preset)?;
+            let checksum = crc.checksum(&read_file(path)?);
+            Ok(format!(
+                "0x{:0width$X}\n",
+                checksum,
+                width = (crc.width() as usize).div_ceil(4)
+            ))
+        }
+        ["encode", codec, input] => encode(codec, input),
+        ["decode", codec, input] => decode(codec, input),
+        ["diff", old, new] => {
+            let diff = parse_hex(old)?.diff(&parse_hex(new)?);
+            match diff.is_empty() {
+                true => Ok(String::from("equal\n")),
+                false => Ok(diff.to_string()),
+            }
+        }
+        ["diagram", value, fields @ ..] => diagram(&parse_hex(value)?, fields),
+        ["help" | "--help" | "-h"] => Ok(USAGE.to_string()),
+        [] => Err(String::from("missing command")),
+        _ => Err(format!("unknown command or arguments: {}", args.join(" "))),
+    }
+}
+
+fn show(byte: UberByte) -> String {
+    format!(
+        "decimal  {}\nhex      {:#04X}\noctal    {:#o}\nbinary   0b{}\n\n{}\n",
+        byte,
+        byte,
+        byte,
+        byte.to_nibble_binary(),
+        byte.to_bit_table()
+    )
+}
+
+fn hexdump(data: &ByteArray) -> String {
+    let mut text = String::new();
+
+    for (line, chunk) in data[..].chunks(16).enumerate() {
+        let mut hex = String::new();
+        for (index, byte) in chunk.iter().enumerate() {
+            if index == 8 {
+                hex.push(' ');
+            }
+            hex.push_str(&format!("{:02x} ", byte));
+        }
+
+        let ascii: String = chunk
+            .iter()
+            .map(|byte| match byte.into_u8() {
+                value @ 0x20..=0x7E => value as char,
+                _ => '.',
+            })
+            .collect();
+
+        text.push_str(&format!("{:08x}  {:<49} |{}|\n", line * 16, hex, ascii));
+    }
+
+    text
+}
+
+fn diagram(value: &ByteArray, fields: &[&str]) -> Result<String, String> {
+    let mut diagram = BitDiagram::from_bytes(value, Endianness::Big)
+        .map_err(|_| String::from("the value must have between 1 and 8 bytes"))?;
+
+    for field in fields {
+        let (name, offset, width) = parse_field(field)?;
+        diagram = diagram
+            .with_field(name, offset, width)
+            .map_err(|_| format!("field does not fit or overlaps: {}", field))?;
+    }
+
+    Ok(format!("{}\n", diagram))
+}
+
+fn encode(codec: &str, input: &str) -> Result<String, String> {
+    let data = parse_hex(input)?;
+
+    match codec {
+        "cobs" => Ok(to_hex(&cobs::encode(&data))),
+        "slip" => Ok(to_hex(&slip::encode(&data))),
+        "base64" => Ok(format!("{}\n", base64::encode(&data))),
+        _ => Err(format!("unknown codec: {}", codec)),
+    }
+}
+
+fn decode(codec: &str, input: &str) -> Result<String, String> {
+    let decoded = match codec {
+        "cobs" => cobs::decode(&parse_hex(input)?),
+        "slip" => slip::decode(&parse_hex(input)?),
+        "base64" => base64::decode(input.trim()),
+        _ => return Err(format!("unknown codec: {}", codec)),
+    };
+
+    decoded
+        .map(|data| to_hex(&data))
+        .map_err(|error| format!("can not decode {}: {:?}", codec, error))
+}
+
+fn crc_preset(name: &str) -> Result<Crc, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "crc-8" => Ok(Crc::CRC_8),
+        "crc-16-modbus" => Ok(Crc::CRC_16_MODBUS),
+        "crc-16-ccitt-false" => Ok(Crc::CRC_16_CCITT_FALSE),
+        "crc-16-xmodem" => Ok(Crc::CRC_16_XMODEM),
+        "crc-32" => Ok(Crc::CRC_32),
+        "crc-32c" => Ok(Crc::CRC_32C),
+        _ => Err(format!("unknown crc preset: {}", name)),
+    }
+}
+
+fn parse_byte(value: &str) -> Result<UberByte, String> {
+    value
+        .parse::<UberByte>()
+        .map_err(|error| format!("invalid value {}: {:?}", value, error))
+}
+
+fn parse_mask(bits: &[&str]) -> Result<u8, String> {
+    bits.iter()
+        .try_fold(0u8, |mask, bit| match bit.parse::<u8>() {
+            Ok(index @ 0..=7) => Ok(mask | 1 << index),
+            _ => Err(format!("invalid bit index: {}", bit)),
+        })
+}
+
+fn parse_field(field: &str) -> Result<(&str, u32, u32), String> {
+    let invalid = || format!("invalid field: {}", field);
+    let (name, bits) = field.split_once(':').ok_or_else(invalid)?;
+    let (high, low) = bits.split_once("..").unwrap_or((bits, bits));
+    let high: u32 = high.parse().map_err(|_| invalid())?;
+    let low: u32 = low.parse().map_err(|_| invalid())?;
+    if name.is_empty() || low > high {
+        return Err(invalid());
+    }
+
+    Ok((name, low, high - low + 1))
+}
+
+fn parse_hex(input: &str) -> Result<ByteArray, String> {
+    let mut bytes = vec![];
+
+    for token in input.split_whitespace() {
+        let digits = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if !digits.len().is_multiple_of(2) || !digits.bytes().all(|d| d.is_ascii_hexdigit()) {
+            return Err(format!("invalid hex bytes: {}", token));
+        }
+
+        for index in (0..digits.len()).step_by(2) {
+            bytes.push(u8::from_str_radix(&digits[index..index + 2], 16).unwrap_or_default());
+        }
+    }
+
+    Ok(ByteArray::from(bytes))
+}
+
+fn to_hex(data: &ByteArray) -> String {
+    let hex: Vec<String> = data[..]
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect();
+    format!("{}\n", hex.join(" "))
+}
+
+fn read_file(path: &str) -> Result<ByteArray, String> {
+    fs::read(path)
+        .map(ByteArray::from)
+        .map_err(|error| format!("can not read {}: {}", path, error))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run_args(args: &str) -> Result<String, String> {
+        let args: Vec<String> = args.split(' ').map(String::from).collect();
+        run(&args)
+    }
+
+    #[test]
+    fn show() {
+        let output = run_args("show 0xA5").unwrap();
+
+        assert!(output.contains("decimal  165\n"));
+        assert!(output.contains("hex      0xA5\n"));
+        assert!(output.contains("octal    0o245\n"));
+        assert!(output.contains("binary   0b1010_0101\n"));
+        assert!(output.ends_with("7 6 5 4 3 2 1 0\n1 0 1 0 0 1 0 1\n"));
+    }
+
+    #[test]
+    fn set_clear_flip() {
+        assert!(run_args("set 0 0 7").unwrap().contains("hex      0x81\n"));
+        assert!(run_args("clear 0xFF 1")
+            .unwrap()
+            .contains("hex      0xFD\n"));
+        assert!(run_args("flip 0x0F").unwrap().contains("hex      0xF0\n"));
+        assert!(run_args("flip 0x0F 0 4")
+            .unwrap()
+            .contains("hex      0x1E\n"));
+        assert!(run_args("set 0 8").is_err());
+    }
+
+    #[test]
+    fn encode_decode() {
+        assert_eq!(
+            "03 11 22 02 33\n",
+            run_args("encode cobs 11220033").unwrap()
+        );
+        assert_eq!("11 22 00 33\n", run_args("decode cobs 0311220233").unwrap());
+        assert_eq!("01 DB DC C0\n", run_args("encode slip 01C0").unwrap());
+        assert_eq!("Zm9v\n", run_args("encode base64 666F6F").unwrap());
+        assert_eq!("66 6F 6F\n", run_args("decode base64 Zm9v").unwrap());
+        assert!(run_args("decode cobs 0300").is_err());
+        assert!(run_args("encode rot13 00").is_err());
+    }
+
+    #[test]
+    fn diff() {
+        assert_eq!(
+            "[1] 00000001 -> 00000011 ......+.\n",
+            run_args("diff 0001 0003").unwrap()
+        );
+        assert_eq!("equal\n", run_args("diff 0x01 01").unwrap());
+    }
+
+    #[test]
+    fn diagram() {
+        assert_eq!(
+            " 7 6 5 4 3 2 1 0\n\
+             +-------+-+-+-+-+\n\
+             |1 0 1 0|0|1|0|1|\n\
+             +-------+-+-+-+-+\n\
+             HIGH  bits 7..4  0b1010  0xA  10\n\
+             FLAG  bit 3      0b0     0x0  0\n",
+            run_args("diagram A5 HIGH:7..4 FLAG:3").unwrap()
+        );
+        assert!(run_args("diagram 0102 ID:15..8").is_ok());
+        assert!(run_args("diagram A5 HIGH:4..7").is_err());
+        assert!(run_args("diagram A5 HIGH:8").is_err());
+        assert!(run_args("diagram A5 A:7..4 B:4").is_err());
+    }
+
+    #[test]
+    fn hexdump() {
+        let data = ByteArray::from(b"0123456789abcdef\x00".to_vec());
+
+        let output = super::hexdump(&data);
+
+        assert_eq!(
+            "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|\n\
+             00000010  00                                                |.|\n",
+            output
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(run(&[]).is_err());
+        assert!(run_args("show 256").is_err());
+        assert!(run_args("crc crc-64 file").is_err());
+        assert!(run_args("unknown").is_err());
+    }
+}
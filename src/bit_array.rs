@@ -0,0 +1,252 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    slice::SliceIndex,
+};
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// Defines the order in which the bits of a byte are read or written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// The most significant bit (bit 7) comes first
+    #[default]
+    MsbFirst,
+    /// The least significant bit (bit 0) comes first
+    LsbFirst,
+}
+
+/// A simple implementation of a growable array of single bits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitArray {
+    data: Vec<bool>,
+}
+
+/// A simple abstract bit array
+impl BitArray {
+    /// Simple alias for the __default__
+    pub fn new() -> Self {
+        BitArray::default()
+    }
+
+    /// Adds the bit to the end of the bit array
+    pub fn add_mut(&mut self, bit: bool) {
+        self.data.push(bit);
+    }
+
+    /// Add the bit to the bit array
+    ///
+    /// # Returns
+    ///
+    /// A copy of the original array with the new bit attached
+    pub fn add(&self, bit: bool) -> Self {
+        let mut clone = self.clone();
+        clone.add_mut(bit);
+        clone
+    }
+
+    /// Adds all bits of the byte in the given order to the end of the bit array
+    pub fn add_byte_mut(&mut self, byte: UberByte, order: BitOrder) {
+        for index in 0..8 {
+            let bit_index = match order {
+                BitOrder::MsbFirst => 7 - index,
+                BitOrder::LsbFirst => index,
+            };
+            self.add_mut(byte.is_bit_set(bit_index));
+        }
+    }
+
+    /// Retrieves the bit at the specific index
+    ///
+    /// # Returns
+    ///
+    /// The bit if the index exists, None if not
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.data.get(index).copied()
+    }
+
+    /// Returns the number of bits stored in this bit array
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Determines if the bit array contains no bits
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Creates a bit array out of all bits of the given bytes
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::{BitArray, BitOrder, ByteArray};
+    ///
+    /// let bits = BitArray::from_byte_array(&ByteArray::from(vec![0x80]), BitOrder::MsbFirst);
+    ///
+    /// assert_eq!(Some(true), bits.get(0));
+    /// assert_eq!(8, bits.len());
+    /// ```
+    pub fn from_byte_array(bytes: &ByteArray, order: BitOrder) -> Self {
+        let mut bits = BitArray::default();
+        for byte in &bytes[..] {
+            bits.add_byte_mut(*byte, order);
+        }
+        bits
+    }
+
+    /// Packs the bits into bytes
+    ///
+    /// # Returns
+    ///
+    /// A byte array holding 8 bits per byte
+    ///
+    /// An _InvalidLength_ error if the number of bits is not a multiple of 8
+    pub fn to_byte_array(&self, order: BitOrder) -> Result<ByteArray, UberByteError> {
+        if !self.len().is_multiple_of(8) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut bytes = ByteArray::default();
+        for chunk in self.data.chunks(8) {
+            let mut byte = UberByte::MIN;
+            for (index, bit) in chunk.iter().enumerate() {
+                if *bit {
+                    let bit_index = match order {
+                        BitOrder::MsbFirst => 7 - index,
+                        BitOrder::LsbFirst => index,
+                    };
+                    byte.set_mut(1 << bit_index);
+                }
+            }
+            bytes.add_mut(byte);
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl<Idx> Index<Idx> for BitArray
+where
+    Idx: SliceIndex<[bool]>,
+{
+    type Output = Idx::Output;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        &self.data[index]
+    }
+}
+
+impl IndexMut<usize> for BitArray {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.data[index]
+    }
+}
+
+impl From<Vec<bool>> for BitArray {
+    fn from(value: Vec<bool>) -> Self {
+        BitArray { data: value }
+    }
+}
+
+impl From<&[bool]> for BitArray {
+    fn from(value: &[bool]) -> Self {
+        BitArray {
+            data: value.to_vec(),
+        }
+    }
+}
+
+impl FromIterator<bool> for BitArray {
+    fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
+        BitArray {
+            data: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for BitArray {
+    type Item = bool;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl Display for BitArray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for bit in &self.data {
+            f.write_str(if *bit { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn add() {
+        let test_array = BitArray::default();
+
+        let new_test_array = test_array.add(true);
+
+        assert_eq!(0, test_array.len());
+        assert_eq!(1, new_test_array.len());
+        assert_eq!(Some(true), new_test_array.get(0));
+    }
+
+    #[test]
+    fn from_byte_array() {
+        let bytes = ByteArray::from(vec![0b_1000_0001, 0b_0000_0010]);
+
+        let msb = BitArray::from_byte_array(&bytes, BitOrder::MsbFirst);
+        let lsb = BitArray::from_byte_array(&bytes, BitOrder::LsbFirst);
+
+        assert_eq!("1000000100000010", msb.to_string());
+        assert_eq!("1000000101000000", lsb.to_string());
+    }
+
+    #[test]
+    fn to_byte_array_round_trip() {
+        let bytes = ByteArray::from(vec![0x12, 0xA5, 0xFF]);
+
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let bits = BitArray::from_byte_array(&bytes, order);
+            assert_eq!(bytes[..], bits.to_byte_array(order).unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn to_byte_array_invalid_length() {
+        let bits = BitArray::from(vec![true, false, true]);
+
+        assert!(matches!(
+            bits.to_byte_array(BitOrder::MsbFirst),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn from_iterator() {
+        let bits: BitArray = UberByte::from(0b_1010_0000)
+            .bits(BitOrder::MsbFirst)
+            .take(4)
+            .collect();
+
+        assert_eq!("1010", bits.to_string());
+    }
+
+    #[test]
+    fn indexer() {
+        let mut bits = BitArray::from(vec![true, false]);
+
+        bits[1] = true;
+
+        assert!(bits[1]);
+        assert_eq!(2, bits[..].len());
+        assert!(bits.get(2).is_none());
+    }
+}
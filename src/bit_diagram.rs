@@ -0,0 +1,403 @@
+//! RFC style box diagrams of the bit fields inside a value
+//!
+//! ```rust
+//! use uberbyte::bit_diagram::BitDiagram;
+//! use uberbyte::UberByte;
+//!
+//! let diagram = BitDiagram::from(UberByte::from(0xA5))
+//!     .with_field("MODE", 6, 2)
+//!     .unwrap()
+//!     .with_field("DATA", 0, 5)
+//!     .unwrap();
+//!
+//! println!("{}", diagram);
+//! //  7 6 5 4 3 2 1 0
+//! // +---+-+---------+
+//! // |1 0|1|0 0 1 0 1|
+//! // +---+-+---------+
+//! // MODE  bits 7..6  0b10     0x2   2
+//! // DATA  bits 4..0  0b00101  0x05  5
+//! ```
+
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::register_map::Register;
+use crate::{ByteArray, Endianness, UberByte, UberByteError};
+
+const BITS_PER_ROW: u32 = 32;
+
+/// A named range of bits inside a [`BitDiagram`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagramField {
+    /// The name shown in the legend
+    pub name: String,
+    /// The position of the lowest bit of the field
+    pub offset: u32,
+    /// The number of bits in the field
+    pub width: u32,
+}
+
+impl DiagramField {
+    fn contains(&self, bit: u32) -> bool {
+        bit >= self.offset && bit - self.offset < self.width
+    }
+
+    fn bit_range(&self) -> String {
+        match self.width {
+            1 => format!("bit {}", self.offset),
+            _ => format!("bits {}..{}", self.offset + self.width - 1, self.offset),
+        }
+    }
+}
+
+/// A value of up to 64 bits together with the layout of its fields
+///
+/// # Remarks
+///
+/// The diagram is rendered through [`Display`], with up to 32 bits per row
+/// followed by a legend listing every field in binary, hex and decimal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitDiagram {
+    value: u64,
+    width: u32,
+    fields: Vec<DiagramField>,
+}
+
+impl BitDiagram {
+    /// Creates a new diagram of a value with the given number of bits
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the width is 0 or more than 64 bits
+    ///
+    /// A _ValueOverflow_ error if the value does not fit into the width
+    pub fn new(value: u64, width: u32) -> Result<Self, UberByteError> {
+        if width == 0 || width > u64::BITS {
+            return Err(UberByteError::InvalidLength);
+        }
+        if value & !mask(width) != 0 {
+            return Err(UberByteError::ValueOverflow);
+        }
+
+        Ok(BitDiagram {
+            value,
+            width,
+            fields: vec![],
+        })
+    }
+
+    /// Creates a new diagram of the bytes read as a single integer
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if there are no bytes or more than 8
+    pub fn from_bytes(bytes: &ByteArray, endianness: Endianness) -> Result<Self, UberByteError> {
+        if bytes.is_empty() || bytes.len() > 8 {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let fold = |value: u64, byte: &UberByte| (value << 8) | byte.into_u8() as u64;
+        let value = match endianness {
+            Endianness::Big => bytes[..].iter().fold(0, fold),
+            Endianness::Little => bytes[..].iter().rev().fold(0, fold),
+        };
+
+        BitDiagram::new(value, bytes.len() as u32 * 8)
+    }
+
+    /// Adds a named field to the diagram
+    ///
+    /// # Returns
+    ///
+    /// An _IndexOutOfRange_ error if the field is empty, does not fit into the value
+    /// or overlaps another field
+    pub fn with_field(
+        mut self,
+        name: &str,
+        offset: u32,
+        width: u32,
+    ) -> Result<Self, UberByteError> {
+        let field = DiagramField {
+            name: name.to_string(),
+            offset,
+            width,
+        };
+        if width == 0 || offset.saturating_add(width) > self.width {
+            return Err(UberByteError::IndexOutOfRange);
+        }
+        if self
+            .fields
+            .iter()
+            .any(|other| (offset..offset + width).any(|bit| other.contains(bit)))
+        {
+            return Err(UberByteError::IndexOutOfRange);
+        }
+
+        self.fields.push(field);
+        Ok(self)
+    }
+
+    /// The value shown in the diagram
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The number of bits shown in the diagram
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The fields of the diagram in the order they were added
+    pub fn fields(&self) -> &[DiagramField] {
+        &self.fields
+    }
+
+    /// Returns the value of the field with the given name
+    pub fn field_value(&self, name: &str) -> Option<u64> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| self.extract(field))
+    }
+
+    fn extract(&self, field: &DiagramField) -> u64 {
+        (self.value >> field.offset) & mask(field.width)
+    }
+
+    fn owner(&self, bit: u32) -> Option<usize> {
+        self.fields.iter().position(|field| field.contains(bit))
+    }
+
+    /// Determines if a cell border is drawn on the left side of the bit
+    fn starts_cell(&self, bit: u32, row_start: bool) -> bool {
+        row_start || self.owner(bit).is_none() || self.owner(bit) != self.owner(bit + 1)
+    }
+
+    fn legend(&self) -> Vec<String> {
+        let mut fields: Vec<&DiagramField> = self.fields.iter().collect();
+        fields.sort_by_key(|field| Reverse(field.offset));
+
+        let columns: Vec<[String; 5]> = fields
+            .iter()
+            .map(|field| {
+                let value = self.extract(field);
+                [
+                    field.name.clone(),
+                    field.bit_range(),
+                    format!("0b{:0width$b}", value, width = field.width as usize),
+                    format!(
+                        "0x{:0width$X}",
+                        value,
+                        width = field.width.div_ceil(4) as usize
+                    ),
+                    value.to_string(),
+                ]
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..5)
+            .map(|column| {
+                columns
+                    .iter()
+                    .map(|row| row[column].len())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        columns
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+impl Display for BitDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut lines: Vec<String> = vec![];
+        let mut high = self.width;
+
+        while high > 0 {
+            let low = (high - 1) / BITS_PER_ROW * BITS_PER_ROW;
+            let bits: Vec<u32> = (low..high).rev().collect();
+
+            if high > 10 {
+                let tens: String = bits
+                    .iter()
+                    .enumerate()
+                    .map(|(index, bit)| match index == 0 || bit % 10 == 9 {
+                        true => format!(" {}", bit / 10 % 10),
+                        false => String::from("  "),
+                    })
+                    .collect();
+                lines.push(tens.trim_end().to_string());
+            }
+            lines.push(bits.iter().map(|bit| format!(" {}", bit % 10)).collect());
+
+            let mut border = String::new();
+            let mut cells = String::new();
+            for (index, bit) in bits.iter().enumerate() {
+                let starts_cell = self.starts_cell(*bit, index == 0);
+                border.push_str(if starts_cell { "+-" } else { "--" });
+                cells.push(if starts_cell { '|' } else { ' ' });
+                cells.push(if self.value >> bit & 1 == 1 { '1' } else { '0' });
+            }
+            border.push('+');
+            cells.push('|');
+
+            lines.push(border.clone());
+            lines.push(cells);
+            lines.push(border);
+            high = low;
+        }
+
+        lines.extend(self.legend());
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl From<UberByte> for BitDiagram {
+    fn from(byte: UberByte) -> Self {
+        BitDiagram {
+            value: byte.into_u8() as u64,
+            width: u8::BITS,
+            fields: vec![],
+        }
+    }
+}
+
+impl Register {
+    /// Creates a diagram of the value with the fields of the register
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the value does not fit into the register
+    ///
+    /// An _IndexOutOfRange_ error if the fields of the register do not fit or overlap
+    pub fn diagram(&self, value: u32) -> Result<BitDiagram, UberByteError> {
+        self.fields.iter().try_fold(
+            BitDiagram::new(value as u64, self.width.bits())?,
+            |diagram, field| diagram.with_field(&field.name, field.offset, field.width),
+        )
+    }
+}
+
+fn mask(width: u32) -> u64 {
+    match width {
+        0 => 0,
+        width => u64::MAX >> (u64::BITS - width),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::register_map::{Access, Field, RegisterWidth};
+
+    #[test]
+    fn byte_diagram() {
+        let diagram = BitDiagram::from(UberByte::from(0xA5))
+            .with_field("MODE", 6, 2)
+            .unwrap()
+            .with_field("DATA", 0, 5)
+            .unwrap()
+            .with_field("EN", 5, 1)
+            .unwrap();
+
+        assert_eq!(
+            " 7 6 5 4 3 2 1 0\n\
+             +---+-+---------+\n\
+             |1 0|1|0 0 1 0 1|\n\
+             +---+-+---------+\n\
+             MODE  bits 7..6  0b10     0x2   2\n\
+             EN    bit 5      0b1      0x1   1\n\
+             DATA  bits 4..0  0b00101  0x05  5",
+            diagram.to_string()
+        );
+        assert_eq!(Some(5), diagram.field_value("DATA"));
+    }
+
+    #[test]
+    fn unnamed_bits() {
+        let diagram = BitDiagram::from(UberByte::from(0x0F));
+
+        assert_eq!(
+            " 7 6 5 4 3 2 1 0\n\
+             +-+-+-+-+-+-+-+-+\n\
+             |0|0|0|0|1|1|1|1|\n\
+             +-+-+-+-+-+-+-+-+",
+            diagram.to_string()
+        );
+    }
+
+    #[test]
+    fn multi_byte_rows() {
+        let bytes = ByteArray::from(vec![0x01, 0x00, 0x00, 0x00, 0x80]);
+
+        let diagram = BitDiagram::from_bytes(&bytes, Endianness::Little)
+            .unwrap()
+            .with_field("TAG", 32, 8)
+            .unwrap();
+        let text = diagram.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(0x80_0000_0001, diagram.value());
+        assert_eq!(" 3", lines[0]);
+        assert_eq!(" 9 8 7 6 5 4 3 2", lines[1]);
+        assert_eq!("|1 0 0 0 0 0 0 0|", lines[3]);
+        assert_eq!(" 3   2                   1                   0", lines[5]);
+        assert!(lines[8].ends_with("|0|1|"));
+        assert_eq!("TAG  bits 39..32  0b10000000  0x80  128", lines[10]);
+    }
+
+    #[test]
+    fn register_diagram() {
+        let register = Register::new("CTRL", 0x10, RegisterWidth::Bits16)
+            .with_field(Field::new("PRESCALER", 8, 8, Access::ReadWrite))
+            .with_field(Field::new("ENABLE", 0, 1, Access::ReadWrite));
+
+        let diagram = register.diagram(0x0301).unwrap();
+
+        assert_eq!(Some(3), diagram.field_value("PRESCALER"));
+        assert_eq!(Some(1), diagram.field_value("ENABLE"));
+        assert!(matches!(
+            register.diagram(0x1_0000),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            BitDiagram::new(0, 65),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            BitDiagram::new(0x100, 8),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert!(matches!(
+            BitDiagram::from(UberByte::MIN).with_field("A", 4, 5),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+        assert!(matches!(
+            BitDiagram::from(UberByte::MIN)
+                .with_field("A", 0, 4)
+                .unwrap()
+                .with_field("B", 3, 2),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+        assert!(matches!(
+            BitDiagram::from_bytes(&ByteArray::default(), Endianness::Big),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+}
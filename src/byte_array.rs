@@ -6,7 +6,10 @@ use std::{
     str::FromStr,
 };
 
-use crate::{UberByte, UberByteError};
+use crate::{BitOrder, UberByte, UberByteError};
+
+pub mod bitwise;
+pub mod search;
 
 /// A simple implementation of a byte array composed of UberBytes
 #[derive(Debug, Clone)]
@@ -79,6 +82,16 @@ impl ByteArray {
         self.data.len()
     }
 
+    /// Determines if the byte array contains no bytes
+    ///
+    /// # Returns
+    ///
+    /// TRUE if the byte array has a length of 0
+    /// FALSE if at least one byte is stored
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
     /// Simple alias for the __default__
     pub fn new() -> Self {
         ByteArray::default()
@@ -103,6 +116,64 @@ impl ByteArray {
         self.add_mut(UberByte::from(0b_0000_0001));
         return true;
     }
+
+    /// Packs the bits into bytes, filling every byte in the given order
+    ///
+    /// # Remarks
+    ///
+    /// If the number of bits is not a multiple of 8 the last byte is padded with unset bits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::{BitOrder, ByteArray};
+    ///
+    /// let bits = [true, false, false, false, false, false, true, true];
+    ///
+    /// assert_eq!(0b_1000_0011, ByteArray::from_bits(bits, BitOrder::MsbFirst)[0].into_u8());
+    /// assert_eq!(0b_1100_0001, ByteArray::from_bits(bits, BitOrder::LsbFirst)[0].into_u8());
+    /// ```
+    pub fn from_bits<T: IntoIterator<Item = bool>>(bits: T, order: BitOrder) -> Self {
+        let mut bytes = ByteArray::default();
+        let mut byte = UberByte::MIN;
+        let mut count = 0;
+
+        for bit in bits {
+            if bit {
+                let bit_index = match order {
+                    BitOrder::MsbFirst => 7 - count,
+                    BitOrder::LsbFirst => count,
+                };
+                byte.set_mut(1 << bit_index);
+            }
+            count += 1;
+            if count == 8 {
+                bytes.add_mut(byte);
+                byte = UberByte::MIN;
+                count = 0;
+            }
+        }
+        if count > 0 {
+            bytes.add_mut(byte);
+        }
+
+        bytes
+    }
+
+    /// Copies the bytes into a vector of plain u8 values
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::ByteArray;
+    ///
+    /// let bytes = ByteArray::from(vec![0x01, 0xFF]);
+    ///
+    /// assert_eq!(vec![0x01, 0xFF], bytes.to_u8_vec());
+    /// ```
+    pub fn to_u8_vec(&self) -> Vec<u8> {
+        self.data.iter().map(|byte| byte.into_u8()).collect()
+    }
 }
 
 impl Default for ByteArray {
@@ -136,6 +207,14 @@ impl From<&[u8]> for ByteArray {
     }
 }
 
+impl From<&[UberByte]> for ByteArray {
+    fn from(value: &[UberByte]) -> Self {
+        ByteArray {
+            data: value.to_vec(),
+        }
+    }
+}
+
 impl From<Vec<u8>> for ByteArray {
     fn from(value: Vec<u8>) -> Self {
         ByteArray {
@@ -153,6 +232,20 @@ impl IntoIterator for ByteArray {
     }
 }
 
+/// Packs the bits least significant bit first into bytes
+///
+/// # Remarks
+///
+/// Like `UberByte::from([bool; 8])` the bool at index n of every group of 8 defines bit n.
+/// Use [`ByteArray::from_bits`] for the most significant bit first order.
+///
+/// If the number of bits is not a multiple of 8 the last byte is padded with unset bits
+impl FromIterator<bool> for ByteArray {
+    fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
+        ByteArray::from_bits(iter, BitOrder::LsbFirst)
+    }
+}
+
 impl FromStr for ByteArray {
     type Err = UberByteError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -192,6 +285,7 @@ mod test {
     use std::vec;
 
     use super::*;
+    use crate::testing::for_all;
 
     #[test]
     fn from_str() {
@@ -210,6 +304,24 @@ mod test {
         assert_eq!(UberByte::from(1), test_array[0]);
     }
 
+    #[test]
+    fn indexer_property() {
+        for_all(|bytes: &ByteArray| {
+            (0..bytes.len()).all(|index| bytes.get(index) == Some(&bytes[index]))
+                && bytes.get(bytes.len()).is_none()
+        });
+    }
+
+    #[test]
+    fn add_range_property() {
+        for_all(|(first, second): &(ByteArray, ByteArray)| {
+            let joined = first.add_range(second[..].to_vec());
+            joined.len() == first.len() + second.len()
+                && joined[..first.len()] == first[..]
+                && joined[first.len()..] == second[..]
+        });
+    }
+
     #[test]
     #[should_panic]
     fn indexer_out_of_range() {
@@ -281,4 +393,35 @@ mod test {
 
         assert_eq!(2, test_array.len());
     }
+
+    #[test]
+    fn from_bool_iterator() {
+        let bytes: ByteArray = [true, false, false, false, false, false, false, true, true]
+            .into_iter()
+            .collect();
+
+        assert_eq!(2, bytes.len());
+        assert_eq!(UberByte::from(0b_1000_0001), bytes[0]);
+        assert_eq!(UberByte::from(0b_0000_0001), bytes[1]);
+    }
+
+    #[test]
+    fn from_bits() {
+        let bits = [
+            true, true, false, false, false, false, false, false, false, true,
+        ];
+
+        let msb = ByteArray::from_bits(bits, BitOrder::MsbFirst);
+        let lsb = ByteArray::from_bits(bits, BitOrder::LsbFirst);
+        let collected: ByteArray = bits.into_iter().collect();
+
+        assert_eq!(UberByte::from(0b_1100_0000), msb[0]);
+        assert_eq!(UberByte::from(0b_0100_0000), msb[1]);
+        assert_eq!(UberByte::from(0b_0000_0011), lsb[0]);
+        assert_eq!(UberByte::from(0b_0000_0010), lsb[1]);
+        assert!(collected[..] == lsb[..]);
+
+        let first: [bool; 8] = bits[..8].try_into().unwrap();
+        assert_eq!(UberByte::from(first), lsb[0]);
+    }
 }
@@ -0,0 +1,284 @@
+//! Bitwise operations over whole byte arrays
+//!
+//! ```rust
+//! use uberbyte::{ByteArray, Endianness};
+//!
+//! let payload = ByteArray::from(vec![0x12, 0x34]);
+//! let mask = ByteArray::from(vec![0xF0, 0x0F]);
+//!
+//! let masked = (&payload & &mask).unwrap();
+//! let shifted = payload.shl_bits(4, Endianness::Big);
+//!
+//! assert_eq!(0x10, masked[0].into_u8());
+//! assert_eq!(0x04, masked[1].into_u8());
+//! assert_eq!(0x23, shifted[0].into_u8());
+//! assert_eq!(0x40, shifted[1].into_u8());
+//! ```
+
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+use crate::{BitOrder, ByteArray, Endianness, UberByte, UberByteError};
+
+impl ByteArray {
+    /// XORs the byte array with the key, repeating the key as often as needed
+    ///
+    /// # Returns
+    ///
+    /// A new byte array of the same length
+    ///
+    /// An _InvalidLength_ error if the key is empty
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::ByteArray;
+    ///
+    /// let data = ByteArray::from(vec![0x00, 0x01, 0x02]);
+    /// let key = ByteArray::from(vec![0xFF, 0x0F]);
+    ///
+    /// let encrypted = data.xor_repeating(&key).unwrap();
+    ///
+    /// assert_eq!(0xFD, encrypted[2].into_u8());
+    /// ```
+    pub fn xor_repeating(&self, key: &ByteArray) -> Result<ByteArray, UberByteError> {
+        if key.is_empty() {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let bytes: Vec<UberByte> = self[..]
+            .iter()
+            .zip(key[..].iter().cycle())
+            .map(|(byte, key)| *byte ^ *key)
+            .collect();
+        Ok(ByteArray::from(&bytes[..]))
+    }
+
+    /// Shifts all bits of the array towards the most significant bit
+    ///
+    /// # Remarks
+    ///
+    /// The array is treated as one integer stored in the given byte order. Bits shifted
+    /// out of the array are lost and the vacated bits are 0.
+    pub fn shl_bits(&self, amount: usize, endianness: Endianness) -> ByteArray {
+        let bits = self.significance_ordered_bits(endianness);
+        let shifted = bits
+            .iter()
+            .skip(amount)
+            .copied()
+            .chain(std::iter::repeat(false))
+            .take(bits.len())
+            .collect();
+
+        ByteArray::from_significance_ordered_bits(shifted, endianness)
+    }
+
+    /// Shifts all bits of the array towards the least significant bit
+    ///
+    /// # Remarks
+    ///
+    /// The array is treated as one integer stored in the given byte order. Bits shifted
+    /// out of the array are lost and the vacated bits are 0.
+    pub fn shr_bits(&self, amount: usize, endianness: Endianness) -> ByteArray {
+        let bits = self.significance_ordered_bits(endianness);
+        let shifted = std::iter::repeat_n(false, amount.min(bits.len()))
+            .chain(bits.iter().copied())
+            .take(bits.len())
+            .collect();
+
+        ByteArray::from_significance_ordered_bits(shifted, endianness)
+    }
+
+    /// Rotates all bits of the array towards the most significant bit
+    ///
+    /// # Remarks
+    ///
+    /// The array is treated as one integer stored in the given byte order. Bits rotated
+    /// out of the most significant end reappear at the least significant end.
+    ///
+    /// Rotating by `len() * 8 - n` rotates the array by _n_ bits towards the least significant bit
+    pub fn rotate_bits(&self, amount: usize, endianness: Endianness) -> ByteArray {
+        let mut bits = self.significance_ordered_bits(endianness);
+        if !bits.is_empty() {
+            let len = bits.len();
+            bits.rotate_left(amount % len);
+        }
+
+        ByteArray::from_significance_ordered_bits(bits, endianness)
+    }
+
+    /// Returns all bits starting with the most significant one
+    fn significance_ordered_bits(&self, endianness: Endianness) -> Vec<bool> {
+        let bytes: Vec<UberByte> = match endianness {
+            Endianness::Big => self[..].to_vec(),
+            Endianness::Little => self[..].iter().rev().copied().collect(),
+        };
+
+        bytes
+            .iter()
+            .flat_map(|byte| byte.bits(BitOrder::MsbFirst))
+            .collect()
+    }
+
+    fn from_significance_ordered_bits(bits: Vec<bool>, endianness: Endianness) -> ByteArray {
+        let bytes = ByteArray::from_bits(bits, BitOrder::MsbFirst);
+        match endianness {
+            Endianness::Big => bytes,
+            Endianness::Little => {
+                let reversed: Vec<UberByte> = bytes[..].iter().rev().copied().collect();
+                ByteArray::from(&reversed[..])
+            }
+        }
+    }
+
+    fn zip_with(
+        &self,
+        other: &ByteArray,
+        operation: fn(UberByte, UberByte) -> UberByte,
+    ) -> Result<ByteArray, UberByteError> {
+        if self.len() != other.len() {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let bytes: Vec<UberByte> = self[..]
+            .iter()
+            .zip(other[..].iter())
+            .map(|(left, right)| operation(*left, *right))
+            .collect();
+        Ok(ByteArray::from(&bytes[..]))
+    }
+}
+
+macro_rules! bitwise_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        /// Combines the arrays byte by byte
+        ///
+        /// # Returns
+        ///
+        /// An _InvalidLength_ error if the arrays are not of the same length
+        impl $trait for &ByteArray {
+            type Output = Result<ByteArray, UberByteError>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(rhs, |left, right| left $op right)
+            }
+        }
+    };
+}
+
+bitwise_op!(BitAnd, bitand, &);
+bitwise_op!(BitOr, bitor, |);
+bitwise_op!(BitXor, bitxor, ^);
+
+impl Not for &ByteArray {
+    type Output = ByteArray;
+
+    fn not(self) -> Self::Output {
+        let bytes: Vec<UberByte> = self[..].iter().map(|byte| !*byte).collect();
+        ByteArray::from(&bytes[..])
+    }
+}
+
+impl Not for ByteArray {
+    type Output = ByteArray;
+
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bitwise() {
+        let left = ByteArray::from(vec![0b_1100, 0xFF]);
+        let right = ByteArray::from(vec![0b_1010, 0x0F]);
+
+        assert_eq!(vec![0b_1000, 0x0F], (&left & &right).unwrap().to_u8_vec());
+        assert_eq!(vec![0b_1110, 0xFF], (&left | &right).unwrap().to_u8_vec());
+        assert_eq!(vec![0b_0110, 0xF0], (&left ^ &right).unwrap().to_u8_vec());
+        assert_eq!(vec![0xF3, 0x00], (!left).to_u8_vec());
+    }
+
+    #[test]
+    fn bitwise_length_mismatch() {
+        let left = ByteArray::from(vec![0x01]);
+        let right = ByteArray::from(vec![0x01, 0x02]);
+
+        assert!(matches!(&left ^ &right, Err(UberByteError::InvalidLength)));
+    }
+
+    #[test]
+    fn xor_repeating() {
+        let data = ByteArray::from(b"HELLO".to_vec());
+        let key = ByteArray::from(vec![0x20]);
+
+        let lower = data.xor_repeating(&key).unwrap();
+
+        assert_eq!(b"hello".to_vec(), lower.to_u8_vec());
+        assert!(matches!(
+            data.xor_repeating(&ByteArray::default()),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn shift_big_endian() {
+        let data = ByteArray::from(vec![0x81, 0x01]);
+
+        assert_eq!(
+            vec![0x08, 0x08],
+            data.shl_bits(3, Endianness::Big).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x10, 0x20],
+            data.shr_bits(3, Endianness::Big).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x00, 0x00],
+            data.shl_bits(16, Endianness::Big).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x00, 0x00],
+            data.shr_bits(100, Endianness::Big).to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn shift_little_endian() {
+        // the integer 0x0181
+        let data = ByteArray::from(vec![0x81, 0x01]);
+
+        assert_eq!(
+            vec![0x08, 0x0C],
+            data.shl_bits(3, Endianness::Little).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x30, 0x00],
+            data.shr_bits(3, Endianness::Little).to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn rotate() {
+        let data = ByteArray::from(vec![0x81, 0x01]);
+
+        assert_eq!(
+            vec![0x02, 0x03],
+            data.rotate_bits(1, Endianness::Big).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x02, 0x03],
+            data.rotate_bits(1, Endianness::Little).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0x81, 0x01],
+            data.rotate_bits(16, Endianness::Big).to_u8_vec()
+        );
+        assert_eq!(
+            vec![0xC0, 0x80],
+            data.rotate_bits(15, Endianness::Big).to_u8_vec()
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Searching byte arrays for plain, masked and bit aligned patterns
+//!
+//! ```rust
+//! use uberbyte::byte_array::search::BytePattern;
+//! use uberbyte::ByteArray;
+//!
+//! let capture = ByteArray::from(vec![0x00, 0x7E, 0x12, 0x3F, 0x7E, 0x99, 0xAF]);
+//! let pattern: BytePattern = "7E ?? 0x?F".parse().unwrap();
+//!
+//! assert_eq!(Some(1), capture.find(&pattern));
+//! assert_eq!(Some(4), capture.rfind(&pattern));
+//! ```
+
+use std::str::FromStr;
+
+use crate::{BitArray, BitOrder, ByteArray, UberByteError};
+
+/// A sequence of bytes where every byte is compared only on the bits of its mask
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BytePattern {
+    values: Vec<u8>,
+    masks: Vec<u8>,
+}
+
+impl BytePattern {
+    /// Creates a pattern out of value and mask pairs
+    ///
+    /// # Remarks
+    ///
+    /// Only the bits set in the mask are compared, so a mask of 0 matches any byte
+    pub fn from_masked(pairs: &[(u8, u8)]) -> Self {
+        BytePattern {
+            values: pairs.iter().map(|(value, mask)| value & mask).collect(),
+            masks: pairs.iter().map(|(_, mask)| *mask).collect(),
+        }
+    }
+
+    /// Returns the number of bytes in the pattern
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Determines if the pattern contains no bytes
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Determines if the pattern matches the bytes starting at the given offset
+    fn matches_at(&self, data: &ByteArray, offset: usize) -> bool {
+        self.values
+            .iter()
+            .zip(self.masks.iter())
+            .enumerate()
+            .all(|(index, (value, mask))| data[offset + index].into_u8() & mask == *value)
+    }
+}
+
+impl From<&[u8]> for BytePattern {
+    fn from(value: &[u8]) -> Self {
+        BytePattern {
+            values: value.to_vec(),
+            masks: vec![0xFF; value.len()],
+        }
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for BytePattern {
+    fn from(value: &[u8; N]) -> Self {
+        BytePattern::from(&value[..])
+    }
+}
+
+impl From<&ByteArray> for BytePattern {
+    fn from(value: &ByteArray) -> Self {
+        let bytes = value.to_u8_vec();
+        BytePattern::from(&bytes[..])
+    }
+}
+
+impl From<&BytePattern> for BytePattern {
+    fn from(value: &BytePattern) -> Self {
+        value.clone()
+    }
+}
+
+/// Parses a space separated list of hexadecimal bytes
+///
+/// # Remarks
+///
+/// Every byte may have a `0x` prefix and each of its two nibbles may be a `?` wildcard
+///
+/// # Returns
+///
+/// An _InvalidLength_ error if the text contains no bytes
+///
+/// An _InvalidCharacter_ error with the position of the first invalid character
+impl FromStr for BytePattern {
+    type Err = UberByteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pairs = vec![];
+
+        let mut position = 0;
+        for token in s.split(' ') {
+            let start = position;
+            position += token.len() + 1;
+            if token.is_empty() {
+                continue;
+            }
+
+            let (digits, offset) = match token.get(..2) {
+                Some("0x" | "0X") => (&token[2..], start + 2),
+                _ => (token, start),
+            };
+            if digits.len() != 2 {
+                return Err(UberByteError::InvalidCharacter(
+                    offset + digits.len().min(2),
+                ));
+            }
+
+            let mut value = 0;
+            let mut mask = 0;
+            for (index, character) in digits.char_indices() {
+                value <<= 4;
+                mask <<= 4;
+                if character == '?' {
+                    continue;
+                }
+                let digit = character
+                    .to_digit(16)
+                    .ok_or(UberByteError::InvalidCharacter(offset + index))?;
+                value |= digit as u8;
+                mask |= 0x0F;
+            }
+            pairs.push((value, mask));
+        }
+
+        match pairs.is_empty() {
+            true => Err(UberByteError::InvalidLength),
+            false => Ok(BytePattern::from_masked(&pairs)),
+        }
+    }
+}
+
+impl ByteArray {
+    /// Searches for the first occurrence of the pattern
+    ///
+    /// # Returns
+    ///
+    /// The byte offset of the first match, None if the pattern is empty or not found
+    pub fn find(&self, pattern: impl Into<BytePattern>) -> Option<usize> {
+        self.match_offsets(&pattern.into()).next()
+    }
+
+    /// Searches for the last occurrence of the pattern
+    ///
+    /// # Returns
+    ///
+    /// The byte offset of the last match, None if the pattern is empty or not found
+    pub fn rfind(&self, pattern: impl Into<BytePattern>) -> Option<usize> {
+        let pattern = pattern.into();
+        if pattern.is_empty() || pattern.len() > self.len() {
+            return None;
+        }
+
+        (0..=self.len() - pattern.len())
+            .rev()
+            .find(|offset| pattern.matches_at(self, *offset))
+    }
+
+    /// Searches for all occurrences of the pattern, including overlapping ones
+    ///
+    /// # Returns
+    ///
+    /// The byte offsets of all matches in ascending order
+    pub fn find_all(&self, pattern: impl Into<BytePattern>) -> Vec<usize> {
+        self.match_offsets(&pattern.into()).collect()
+    }
+
+    /// Splits the array at every non overlapping occurrence of the pattern
+    ///
+    /// # Returns
+    ///
+    /// The parts between the matches without the matched bytes. Matches at the start
+    /// or the end of the array produce empty parts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::ByteArray;
+    ///
+    /// let stream = ByteArray::from(vec![0x01, 0x7E, 0x7E, 0x02, 0x03]);
+    ///
+    /// let parts = stream.split_on(&[0x7E, 0x7E]);
+    ///
+    /// assert_eq!(2, parts.len());
+    /// assert_eq!(2, parts[1].len());
+    /// ```
+    pub fn split_on(&self, pattern: impl Into<BytePattern>) -> Vec<ByteArray> {
+        let pattern = pattern.into();
+        let mut parts = vec![];
+        let mut start = 0;
+
+        for offset in self.match_offsets(&pattern) {
+            if offset < start {
+                continue;
+            }
+            parts.push(ByteArray::from(&self[start..offset]));
+            start = offset + pattern.len();
+        }
+        parts.push(ByteArray::from(&self[start..]));
+
+        parts
+    }
+
+    /// Searches for the first occurrence of the bit pattern, regardless of byte boundaries
+    ///
+    /// # Remarks
+    ///
+    /// The bits of every byte are read most significant bit first
+    ///
+    /// # Returns
+    ///
+    /// The offset in bits of the first match, None if the pattern is empty or not found
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::{BitArray, ByteArray};
+    ///
+    /// let stream = ByteArray::from(vec![0b_0000_1011, 0b_0100_0000]);
+    /// let sync = BitArray::from(vec![true, false, true, true, false, true]);
+    ///
+    /// assert_eq!(Some(4), stream.find_bits(&sync));
+    /// ```
+    pub fn find_bits(&self, pattern: &BitArray) -> Option<usize> {
+        self.find_all_bits(pattern).into_iter().next()
+    }
+
+    /// Searches for all occurrences of the bit pattern, including overlapping ones
+    ///
+    /// # Returns
+    ///
+    /// The offsets in bits of all matches in ascending order
+    pub fn find_all_bits(&self, pattern: &BitArray) -> Vec<usize> {
+        let bits = BitArray::from_byte_array(self, BitOrder::MsbFirst);
+        if pattern.is_empty() || pattern.len() > bits.len() {
+            return vec![];
+        }
+
+        (0..=bits.len() - pattern.len())
+            .filter(|offset| bits[*offset..*offset + pattern.len()] == pattern[..])
+            .collect()
+    }
+
+    fn match_offsets<'a>(&'a self, pattern: &'a BytePattern) -> impl Iterator<Item = usize> + 'a {
+        let last = match pattern.is_empty() {
+            true => None,
+            false => self.len().checked_sub(pattern.len()),
+        };
+
+        last.into_iter()
+            .flat_map(|last| 0..=last)
+            .filter(move |offset| pattern.matches_at(self, *offset))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_pattern() {
+        let pattern: BytePattern = "7E ?? 0x?F  a?".parse().unwrap();
+
+        assert_eq!(
+            BytePattern::from_masked(&[(0x7E, 0xFF), (0, 0), (0x0F, 0x0F), (0xA0, 0xF0)]),
+            pattern
+        );
+    }
+
+    #[test]
+    fn parse_pattern_errors() {
+        assert!(matches!(
+            "".parse::<BytePattern>(),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            "7E 0xG1".parse::<BytePattern>(),
+            Err(UberByteError::InvalidCharacter(5))
+        ));
+        assert!(matches!(
+            "7E1".parse::<BytePattern>(),
+            Err(UberByteError::InvalidCharacter(2))
+        ));
+    }
+
+    #[test]
+    fn find() {
+        let data = ByteArray::from(vec![0x01, 0x02, 0x01, 0x02, 0x01]);
+
+        assert_eq!(Some(0), data.find(&[0x01, 0x02]));
+        assert_eq!(Some(2), data.rfind(&[0x01, 0x02]));
+        assert_eq!(vec![0, 2, 4], data.find_all(&[0x01]));
+        assert_eq!(vec![0, 2], data.find_all(&[0x01, 0x02, 0x01]));
+        assert_eq!(None, data.find(&[0x03]));
+        assert_eq!(None, data.find(&[0u8; 0]));
+        assert_eq!(None, data.rfind(&[0x01; 6]));
+    }
+
+    #[test]
+    fn find_masked() {
+        let data = ByteArray::from(vec![0x10, 0x2F, 0x3F]);
+        let pattern = BytePattern::from_masked(&[(0x0F, 0x0F)]);
+
+        assert_eq!(vec![1, 2], data.find_all(&pattern));
+    }
+
+    #[test]
+    fn split_on() {
+        let data = ByteArray::from(vec![0x00, 0x00, 0x00, 0x01, 0x00]);
+
+        let parts = data.split_on(&[0x00, 0x00]);
+
+        assert_eq!(
+            vec![0, 3],
+            parts.iter().map(|part| part.len()).collect::<Vec<usize>>()
+        );
+        assert_eq!(0x01, parts[1][1].into_u8());
+
+        let parts = data.split_on(&[0x05]);
+        assert_eq!(1, parts.len());
+        assert_eq!(5, parts[0].len());
+    }
+
+    #[test]
+    fn find_bits() {
+        // the sync word 0b101 starts at bit 6 and bit 8
+        let data = ByteArray::from(vec![0b_0000_0010, 0b_1010_0000]);
+        let sync = BitArray::from(vec![true, false, true]);
+
+        assert_eq!(Some(6), data.find_bits(&sync));
+        assert_eq!(vec![6, 8], data.find_all_bits(&sync));
+        assert_eq!(None, data.find_bits(&BitArray::new()));
+    }
+}
@@ -0,0 +1,325 @@
+//! A lock free single producer single consumer queue of bytes
+//!
+//! [`ByteQueue::with_capacity`] splits the queue into a [`Producer`] and a [`Consumer`]
+//! that can be moved to different threads. Neither side ever blocks: writing into a full
+//! queue writes as much as fits, like a [`ByteRing`](crate::byte_ring::ByteRing) rejecting
+//! new bytes, and reading from an empty queue reads nothing.
+//!
+//! ```rust
+//! use std::thread;
+//! use uberbyte::byte_queue::ByteQueue;
+//! use uberbyte::ByteArray;
+//!
+//! let (mut producer, mut consumer) = ByteQueue::with_capacity(64);
+//!
+//! let writer = thread::spawn(move || producer.write_slice(&[0x7E, 0x01, 0x02]));
+//! assert_eq!(3, writer.join().unwrap());
+//!
+//! let mut received = ByteArray::default();
+//! consumer.read_into(&mut received);
+//! assert_eq!([0x7Eu8, 0x01, 0x02][..], received[..]);
+//! ```
+
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// The state shared by both halves of the queue
+///
+/// # Remarks
+///
+/// The positions run from 0 to twice the capacity, so a full queue can be told apart
+/// from an empty one. Only the producer moves the tail and only the consumer moves the head.
+#[derive(Debug)]
+struct Shared {
+    slots: Box<[AtomicU8]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+impl Shared {
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn distance(&self, from: usize, to: usize) -> usize {
+        (to + 2 * self.capacity() - from) % (2 * self.capacity()).max(1)
+    }
+
+    fn advance(&self, position: usize, count: usize) -> usize {
+        (position + count) % (2 * self.capacity()).max(1)
+    }
+
+    fn slot(&self, position: usize) -> &AtomicU8 {
+        &self.slots[position % self.capacity()]
+    }
+
+    fn len(&self) -> usize {
+        self.distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+        )
+    }
+}
+
+/// Creates the two halves of a single producer single consumer queue
+pub struct ByteQueue;
+
+impl ByteQueue {
+    /// Creates a new empty queue holding up to _capacity_ bytes
+    pub fn with_capacity(capacity: usize) -> (Producer, Consumer) {
+        let shared = Arc::new(Shared {
+            slots: (0..capacity).map(|_| AtomicU8::new(0)).collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        });
+
+        (
+            Producer {
+                shared: Arc::clone(&shared),
+            },
+            Consumer { shared },
+        )
+    }
+}
+
+/// The writing half of a [`ByteQueue`]
+#[derive(Debug)]
+pub struct Producer {
+    shared: Arc<Shared>,
+}
+
+impl Producer {
+    /// The number of bytes the queue can hold
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// The number of bytes that can be written right now
+    pub fn free(&self) -> usize {
+        self.capacity() - self.shared.len()
+    }
+
+    /// Determines if the consumer has been dropped
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+
+    /// Appends the byte to the queue
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the queue is full
+    pub fn push(&mut self, byte: UberByte) -> Result<(), UberByteError> {
+        match self.write_slice(&[byte.into_u8()]) {
+            1 => Ok(()),
+            _ => Err(UberByteError::ValueOverflow),
+        }
+    }
+
+    /// Appends as many of the bytes as fit into the queue
+    ///
+    /// # Returns
+    ///
+    /// The number of appended bytes
+    pub fn write_slice(&mut self, bytes: &[u8]) -> usize {
+        let shared = &self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let head = shared.head.load(Ordering::Acquire);
+        let count = bytes
+            .len()
+            .min(shared.capacity() - shared.distance(head, tail));
+
+        for (index, byte) in bytes[..count].iter().enumerate() {
+            shared
+                .slot(shared.advance(tail, index))
+                .store(*byte, Ordering::Relaxed);
+        }
+        shared
+            .tail
+            .store(shared.advance(tail, count), Ordering::Release);
+
+        count
+    }
+
+    /// Appends as many bytes of the array as fit into the queue
+    ///
+    /// # Returns
+    ///
+    /// The number of appended bytes
+    pub fn write_array(&mut self, bytes: &ByteArray) -> usize {
+        let raw = bytes.to_u8_vec();
+        self.write_slice(&raw)
+    }
+}
+
+impl Write for Producer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.write_slice(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The reading half of a [`ByteQueue`]
+#[derive(Debug)]
+pub struct Consumer {
+    shared: Arc<Shared>,
+}
+
+impl Consumer {
+    /// The number of bytes the queue can hold
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity()
+    }
+
+    /// The number of bytes that can be read right now
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    /// Determines if there are no bytes to read right now
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Determines if the producer has been dropped
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+
+    /// Removes the oldest byte from the queue
+    pub fn pop(&mut self) -> Option<UberByte> {
+        let mut byte = [0];
+        match self.read_slice(&mut byte) {
+            1 => Some(UberByte::from(byte[0])),
+            _ => None,
+        }
+    }
+
+    /// Moves as many bytes as fit into the target out of the queue
+    ///
+    /// # Returns
+    ///
+    /// The number of moved bytes
+    pub fn read_slice(&mut self, target: &mut [u8]) -> usize {
+        let shared = &self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        let tail = shared.tail.load(Ordering::Acquire);
+        let count = target.len().min(shared.distance(head, tail));
+
+        for (index, byte) in target[..count].iter_mut().enumerate() {
+            *byte = shared
+                .slot(shared.advance(head, index))
+                .load(Ordering::Relaxed);
+        }
+        shared
+            .head
+            .store(shared.advance(head, count), Ordering::Release);
+
+        count
+    }
+
+    /// Moves all available bytes to the end of the target
+    ///
+    /// # Returns
+    ///
+    /// The number of moved bytes
+    pub fn read_into(&mut self, target: &mut ByteArray) -> usize {
+        let mut bytes = vec![0; self.len()];
+        let count = self.read_slice(&mut bytes);
+        for byte in &bytes[..count] {
+            target.add_mut(UberByte::from(*byte));
+        }
+        count
+    }
+}
+
+impl Read for Consumer {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_slice(buf))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn wraps_around() {
+        let (mut producer, mut consumer) = ByteQueue::with_capacity(3);
+
+        assert_eq!(3, producer.write_slice(&[1, 2, 3, 4]));
+        assert!(matches!(
+            producer.push(UberByte::from(4)),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert_eq!(Some(UberByte::from(1)), consumer.pop());
+        assert_eq!(1, producer.free());
+        producer.push(UberByte::from(4)).unwrap();
+
+        let mut target = ByteArray::default();
+        assert_eq!(3, consumer.read_into(&mut target));
+        assert_eq!([2u8, 3, 4][..], target[..]);
+        assert!(consumer.is_empty());
+        assert_eq!(None, consumer.pop());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let (mut producer, mut consumer) = ByteQueue::with_capacity(0);
+
+        assert_eq!(0, producer.write_slice(&[1]));
+        assert_eq!(None, consumer.pop());
+    }
+
+    #[test]
+    fn abandoned() {
+        let (producer, consumer) = ByteQueue::with_capacity(1);
+        assert!(!producer.is_abandoned());
+
+        drop(consumer);
+
+        assert!(producer.is_abandoned());
+    }
+
+    #[test]
+    fn stress_across_threads() {
+        const TOTAL: usize = 200_000;
+        let (mut producer, mut consumer) = ByteQueue::with_capacity(61);
+
+        let writer = thread::spawn(move || {
+            let data: Vec<u8> = (0..TOTAL).map(|index| (index % 251) as u8).collect();
+            let mut written = 0;
+            let mut chunk = 1;
+            while written < TOTAL {
+                let end = (written + chunk).min(TOTAL);
+                written += producer.write_slice(&data[written..end]);
+                chunk = chunk % 97 + 1;
+                thread::yield_now();
+            }
+        });
+
+        let mut received = 0;
+        let mut buffer = [0; 40];
+        while received < TOTAL {
+            let count = consumer.read(&mut buffer).unwrap();
+            for byte in &buffer[..count] {
+                assert_eq!((received % 251) as u8, *byte);
+                received += 1;
+            }
+            if count == 0 {
+                thread::yield_now();
+            }
+        }
+
+        writer.join().unwrap();
+        assert!(consumer.is_empty());
+        assert!(consumer.is_abandoned());
+    }
+}
@@ -0,0 +1,456 @@
+//! Ring buffers of bytes for handing received data from a producer to a consumer
+//!
+//! [`ByteRing`] keeps its bytes in a fixed size array and never allocates, while
+//! [`GrowableByteRing`] grows on the heap up to a maximum capacity. Both share the
+//! implementation of [`RingBuffer`] and differ only in their [`RingStorage`].
+//!
+//! ```rust
+//! use uberbyte::byte_ring::{ByteRing, OverflowPolicy};
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let mut ring = ByteRing::<4>::new().with_policy(OverflowPolicy::Overwrite);
+//! for value in 1..=6 {
+//!     ring.push(UberByte::from(value)).unwrap();
+//! }
+//!
+//! let mut received = ByteArray::default();
+//! ring.read_into(&mut received);
+//!
+//! assert_eq!([3u8, 4, 5, 6][..], received[..]);
+//! ```
+
+use std::io::{Read, Write};
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// Defines what happens when a byte is pushed into a full ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// The new byte is rejected
+    #[default]
+    Reject,
+    /// The oldest byte is dropped to make room for the new one
+    Overwrite,
+}
+
+/// The memory a [`RingBuffer`] keeps its bytes in
+pub trait RingStorage {
+    /// All slots of the storage, used or not
+    fn slots(&self) -> &[UberByte];
+
+    /// All slots of the storage, used or not
+    fn slots_mut(&mut self) -> &mut [UberByte];
+
+    /// Adds more slots at the end of the storage
+    ///
+    /// # Returns
+    ///
+    /// false if the storage can not grow any further
+    fn grow(&mut self) -> bool {
+        false
+    }
+}
+
+impl<const N: usize> RingStorage for [UberByte; N] {
+    fn slots(&self) -> &[UberByte] {
+        self
+    }
+
+    fn slots_mut(&mut self) -> &mut [UberByte] {
+        self
+    }
+}
+
+/// Heap storage doubling its size whenever it runs out of slots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Growable {
+    slots: Vec<UberByte>,
+    max_capacity: usize,
+}
+
+impl RingStorage for Growable {
+    fn slots(&self) -> &[UberByte] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [UberByte] {
+        &mut self.slots
+    }
+
+    fn grow(&mut self) -> bool {
+        let capacity = (self.slots.len() * 2).max(16).min(self.max_capacity);
+        if capacity <= self.slots.len() {
+            return false;
+        }
+
+        self.slots.resize(capacity, UberByte::MIN);
+        true
+    }
+}
+
+/// A first in first out buffer of bytes on top of a [`RingStorage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingBuffer<S> {
+    storage: S,
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy,
+}
+
+/// A ring of _N_ bytes that never allocates
+pub type ByteRing<const N: usize> = RingBuffer<[UberByte; N]>;
+
+/// A ring growing on the heap up to a maximum capacity
+pub type GrowableByteRing = RingBuffer<Growable>;
+
+impl<const N: usize> RingBuffer<[UberByte; N]> {
+    /// Creates a new empty ring rejecting bytes when full
+    pub fn new() -> Self {
+        RingBuffer {
+            storage: [UberByte::MIN; N],
+            head: 0,
+            len: 0,
+            policy: OverflowPolicy::Reject,
+        }
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<[UberByte; N]> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RingBuffer<Growable> {
+    /// Creates a new empty ring that grows without limit
+    pub fn new() -> Self {
+        Self::with_capacity(0, usize::MAX)
+    }
+
+    /// Creates a new empty ring with room for the initial capacity that grows up to the maximum
+    pub fn with_capacity(capacity: usize, max_capacity: usize) -> Self {
+        RingBuffer {
+            storage: Growable {
+                slots: vec![UberByte::MIN; capacity.min(max_capacity)],
+                max_capacity,
+            },
+            head: 0,
+            len: 0,
+            policy: OverflowPolicy::Reject,
+        }
+    }
+}
+
+impl Default for RingBuffer<Growable> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: RingStorage> RingBuffer<S> {
+    /// Sets what happens when a byte is pushed into a full ring
+    pub fn with_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The policy applied when the ring is full
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// The number of bytes the ring can hold without growing
+    pub fn capacity(&self) -> usize {
+        self.storage.slots().len()
+    }
+
+    /// The number of buffered bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Determines if no bytes are buffered
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Determines if no more bytes fit without growing
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Drops all buffered bytes
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Appends the byte at the end of the ring
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the ring is full, can not grow and rejects new bytes
+    pub fn push(&mut self, byte: UberByte) -> Result<(), UberByteError> {
+        if self.is_full() && !self.grow() {
+            if self.policy == OverflowPolicy::Reject || self.capacity() == 0 {
+                return Err(UberByteError::ValueOverflow);
+            }
+            self.head = (self.head + 1) % self.capacity();
+            self.len -= 1;
+        }
+
+        let tail = (self.head + self.len) % self.capacity();
+        self.storage.slots_mut()[tail] = byte;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends the bytes at the end of the ring
+    ///
+    /// # Returns
+    ///
+    /// The number of appended bytes, which is less than given only if the ring rejected the rest
+    pub fn push_slice(&mut self, bytes: &[u8]) -> usize {
+        bytes
+            .iter()
+            .take_while(|byte| self.push(UberByte::from(**byte)).is_ok())
+            .count()
+    }
+
+    /// Removes the oldest byte from the ring
+    pub fn pop(&mut self) -> Option<UberByte> {
+        let byte = self.get(0)?;
+        self.consume(1);
+        Some(byte)
+    }
+
+    /// Returns the buffered byte at the given position, counted from the oldest one
+    pub fn get(&self, index: usize) -> Option<UberByte> {
+        match index < self.len {
+            true => Some(self.storage.slots()[(self.head + index) % self.capacity()]),
+            false => None,
+        }
+    }
+
+    /// Returns up to _count_ of the oldest bytes without removing them
+    pub fn peek(&self, count: usize) -> ByteArray {
+        let (first, second) = self.as_slices();
+        let mut bytes = ByteArray::from(&first[..count.min(first.len())]);
+        for byte in second.iter().take(count.saturating_sub(first.len())) {
+            bytes.add_mut(*byte);
+        }
+        bytes
+    }
+
+    /// Moves all buffered bytes to the end of the target
+    ///
+    /// # Returns
+    ///
+    /// The number of moved bytes
+    pub fn read_into(&mut self, target: &mut ByteArray) -> usize {
+        let (first, second) = self.as_slices();
+        for byte in first.iter().chain(second) {
+            target.add_mut(*byte);
+        }
+
+        let count = self.len;
+        self.clear();
+        count
+    }
+
+    /// The buffered bytes from the oldest to the newest, split where the storage wraps around
+    pub fn as_slices(&self) -> (&[UberByte], &[UberByte]) {
+        let slots = self.storage.slots();
+        let end = self.head + self.len;
+        match end <= slots.len() {
+            true => (&slots[self.head..end], &[]),
+            false => (&slots[self.head..], &slots[..end - slots.len()]),
+        }
+    }
+
+    /// The oldest buffered bytes that are stored next to each other
+    ///
+    /// # Remarks
+    ///
+    /// After copying them out, e.g. with DMA, they are removed with [`RingBuffer::consume`]
+    pub fn readable_slice(&self) -> &[UberByte] {
+        self.as_slices().0
+    }
+
+    /// Removes up to _count_ of the oldest bytes
+    pub fn consume(&mut self, count: usize) {
+        let count = count.min(self.len);
+        if count > 0 {
+            self.head = (self.head + count) % self.capacity();
+            self.len -= count;
+        }
+    }
+
+    /// The free slots following the newest byte that are next to each other
+    ///
+    /// # Remarks
+    ///
+    /// After copying bytes into them, e.g. with DMA, they are added with [`RingBuffer::commit`].
+    /// The slice is empty if the ring is full and can not grow
+    pub fn writable_slice(&mut self) -> &mut [UberByte] {
+        if self.is_full() {
+            self.grow();
+        }
+
+        let capacity = self.capacity();
+        if self.len == capacity {
+            return &mut [];
+        }
+        let tail = (self.head + self.len) % capacity;
+        let end = match tail < self.head {
+            true => self.head,
+            false => capacity,
+        };
+        &mut self.storage.slots_mut()[tail..end]
+    }
+
+    /// Adds up to _count_ bytes written into the free slots following the newest byte
+    pub fn commit(&mut self, count: usize) {
+        self.len += count.min(self.capacity() - self.len);
+    }
+
+    /// Grows the storage of a full ring and moves the bytes to the start of the storage
+    fn grow(&mut self) -> bool {
+        let capacity = self.capacity();
+        if !self.storage.grow() {
+            return false;
+        }
+
+        self.storage.slots_mut()[..capacity].rotate_left(self.head);
+        self.head = 0;
+        true
+    }
+}
+
+impl<S: RingStorage> Read for RingBuffer<S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut count = 0;
+        for (target, byte) in buf.iter_mut().zip(std::iter::from_fn(|| self.pop())) {
+            *target = byte.into_u8();
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+impl<S: RingStorage> Write for RingBuffer<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.push_slice(buf))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_pop_wraps_around() {
+        let mut ring = ByteRing::<3>::new();
+
+        assert_eq!(3, ring.push_slice(&[1, 2, 3]));
+        assert!(matches!(
+            ring.push(UberByte::from(4)),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert_eq!(Some(UberByte::from(1)), ring.pop());
+        ring.push(UberByte::from(4)).unwrap();
+
+        let (first, second) = ring.as_slices();
+        assert_eq!(
+            (vec![2, 3], vec![4]),
+            (
+                ByteArray::from(first).to_u8_vec(),
+                ByteArray::from(second).to_u8_vec()
+            )
+        );
+        assert_eq!(vec![2, 3], ring.peek(2).to_u8_vec());
+        assert_eq!(vec![2, 3, 4], ring.peek(10).to_u8_vec());
+        assert_eq!(3, ring.len());
+    }
+
+    #[test]
+    fn overwrite_drops_oldest() {
+        let mut ring = ByteRing::<2>::new().with_policy(OverflowPolicy::Overwrite);
+
+        assert_eq!(3, ring.push_slice(&[1, 2, 3]));
+
+        assert_eq!(Some(UberByte::from(2)), ring.get(0));
+        assert_eq!(Some(UberByte::from(3)), ring.get(1));
+        assert!(ByteRing::<0>::new()
+            .with_policy(OverflowPolicy::Overwrite)
+            .push(UberByte::MAX)
+            .is_err());
+    }
+
+    #[test]
+    fn read_into() {
+        let mut ring = ByteRing::<4>::new();
+        ring.push_slice(&[1, 2, 3]);
+        ring.consume(2);
+        ring.push_slice(&[4, 5, 6]);
+        let mut target = ByteArray::from(vec![0]);
+
+        assert_eq!(4, ring.read_into(&mut target));
+
+        assert_eq!(vec![0, 3, 4, 5, 6], target.to_u8_vec());
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn dma_style_slices() {
+        let mut ring = ByteRing::<4>::new();
+        ring.push_slice(&[1, 2, 3]);
+        ring.consume(2);
+
+        let free = ring.writable_slice();
+        assert_eq!(1, free.len());
+        free[0] = UberByte::from(4);
+        ring.commit(1);
+        assert_eq!(2, ring.writable_slice().len());
+
+        assert_eq!(
+            vec![3, 4],
+            ByteArray::from(ring.readable_slice()).to_u8_vec()
+        );
+        ring.consume(2);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn growable() {
+        let mut ring = GrowableByteRing::with_capacity(2, 20);
+        ring.push_slice(&[1, 2]);
+        ring.consume(1);
+
+        assert_eq!(19, ring.push_slice(&[0xAA; 30]));
+
+        assert_eq!(20, ring.capacity());
+        assert_eq!(Some(UberByte::from(2)), ring.get(0));
+        assert_eq!(
+            vec![0xAA; 19],
+            ByteArray::from(&ring.peek(20)[1..]).to_u8_vec()
+        );
+        assert_eq!(16, GrowableByteRing::new().writable_slice().len());
+    }
+
+    #[test]
+    fn io() {
+        let mut ring = ByteRing::<8>::new();
+
+        assert_eq!(8, ring.write(b"0123456789").unwrap());
+        let mut text = String::new();
+        ring.read_to_string(&mut text).unwrap();
+
+        assert_eq!("01234567", text);
+        assert!(ring.write_all(&[0; 9]).is_err());
+    }
+}
@@ -0,0 +1,34 @@
+//! Lightweight compression for logs and captures without pulling in a full deflate implementation
+
+pub mod lzss;
+pub mod rle;
+
+#[cfg(test)]
+mod test {
+    /// Generates pseudo random data with runs of repeated bytes and repeated phrases
+    pub(crate) fn sample_data(seed: u64, length: usize) -> Vec<u8> {
+        let mut state = seed.max(1);
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut data = vec![];
+        while data.len() < length {
+            let value = next();
+            let count = (value >> 8) as usize % 40 + 1;
+            match value % 4 {
+                0 => data.extend(std::iter::repeat_n((value >> 16) as u8, count)),
+                1 if data.len() > count => {
+                    let start = (value >> 24) as usize % (data.len() - count);
+                    data.extend_from_within(start..start + count);
+                }
+                _ => data.extend((0..count).map(|_| next() as u8)),
+            }
+        }
+        data.truncate(length);
+        data
+    }
+}
@@ -0,0 +1,249 @@
+//! LZSS dictionary compression with a sliding window
+//!
+//! Every group of up to 8 tokens is preceded by a flag byte, read from the least significant
+//! bit. A set bit marks a literal byte, a cleared bit a 2 byte reference to an earlier match
+//! holding 12 bits of distance and 4 bits of length.
+//!
+//! ```rust
+//! use uberbyte::compression::lzss::LzssConfig;
+//! use uberbyte::ByteArray;
+//!
+//! let log = ByteArray::from("OK OK OK OK OK OK".as_bytes());
+//!
+//! let compressed = log.compress_lzss(LzssConfig::default());
+//!
+//! assert!(compressed.len() < log.len());
+//! assert_eq!(log[..], compressed.decompress_lzss().unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByteError};
+
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = MIN_MATCH + 0x0F;
+const MAX_WINDOW: usize = 4096;
+
+/// The settings of the LZSS encoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LzssConfig {
+    /// How many of the preceding bytes are searched for matches
+    ///
+    /// # Remarks
+    ///
+    /// Values outside of 1 to 4096 are clamped. A smaller window compresses faster
+    /// at the cost of finding fewer matches.
+    pub window_size: usize,
+}
+
+impl LzssConfig {
+    /// Searches the largest window the format can address
+    pub const DEFAULT: LzssConfig = LzssConfig {
+        window_size: MAX_WINDOW,
+    };
+
+    /// Creates a new configuration with the given window size
+    pub fn new(window_size: usize) -> Self {
+        LzssConfig { window_size }
+    }
+}
+
+impl Default for LzssConfig {
+    fn default() -> Self {
+        LzssConfig::DEFAULT
+    }
+}
+
+impl ByteArray {
+    /// Compresses repeated sequences into references to their previous occurrence
+    pub fn compress_lzss(&self, config: LzssConfig) -> ByteArray {
+        let data = self.to_u8_vec();
+        let window = config.window_size.clamp(1, MAX_WINDOW);
+
+        let mut compressed = vec![];
+        let mut flags_index = 0;
+        let mut token = 0;
+        let mut index = 0;
+
+        while index < data.len() {
+            if token % 8 == 0 {
+                flags_index = compressed.len();
+                compressed.push(0);
+            }
+
+            let (distance, length) = longest_match(&data, index, window);
+            if length >= MIN_MATCH {
+                let reference = distance - 1;
+                compressed.push(reference as u8);
+                compressed.push(((reference >> 8) << 4) as u8 | (length - MIN_MATCH) as u8);
+                index += length;
+            } else {
+                compressed[flags_index] |= 1 << (token % 8);
+                compressed.push(data[index]);
+                index += 1;
+            }
+            token += 1;
+        }
+
+        ByteArray::from(compressed)
+    }
+
+    /// Restores the bytes compressed with [`ByteArray::compress_lzss`]
+    ///
+    /// # Remarks
+    ///
+    /// The window size is not needed, every reference carries its own distance
+    ///
+    /// # Returns
+    ///
+    /// A _CodeViolation_ error with the index of a reference that is cut off or
+    /// points before the start of the data, or of a flag byte not followed by a token
+    pub fn decompress_lzss(&self) -> Result<ByteArray, UberByteError> {
+        let data = self.to_u8_vec();
+        let mut decompressed: Vec<u8> = vec![];
+        let mut index = 0;
+
+        while index < data.len() {
+            let flags = data[index];
+            if index + 1 == data.len() {
+                return Err(UberByteError::CodeViolation(index));
+            }
+            index += 1;
+
+            for bit in 0..8 {
+                if index >= data.len() {
+                    break;
+                }
+                if flags >> bit & 1 == 1 {
+                    decompressed.push(data[index]);
+                    index += 1;
+                    continue;
+                }
+
+                let reference = data
+                    .get(index..index + 2)
+                    .ok_or(UberByteError::CodeViolation(index))?;
+                let distance = (reference[0] as usize | (reference[1] as usize >> 4) << 8) + 1;
+                let length = (reference[1] & 0x0F) as usize + MIN_MATCH;
+                if distance > decompressed.len() {
+                    return Err(UberByteError::CodeViolation(index));
+                }
+
+                let start = decompressed.len() - distance;
+                for offset in 0..length {
+                    decompressed.push(decompressed[start + offset]);
+                }
+                index += 2;
+            }
+        }
+
+        Ok(ByteArray::from(decompressed))
+    }
+}
+
+/// Finds the longest earlier match of the bytes at the index within the window
+///
+/// # Remarks
+///
+/// Matches may run into the bytes being matched, which encodes runs of a repeated sequence.
+/// On equal lengths the closest match wins.
+///
+/// # Returns
+///
+/// The distance and length of the match
+fn longest_match(data: &[u8], index: usize, window: usize) -> (usize, usize) {
+    let limit = MAX_MATCH.min(data.len() - index);
+    let mut best = (0, 0);
+
+    for distance in 1..=window.min(index) {
+        let start = index - distance;
+        let length = (0..limit)
+            .take_while(|offset| data[start + offset] == data[index + offset])
+            .count();
+        if length > best.1 {
+            best = (distance, length);
+            if length == limit {
+                break;
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compression::test::sample_data;
+
+    #[test]
+    fn references() {
+        let data = ByteArray::from("abcabcabcd".as_bytes());
+
+        let compressed = data.compress_lzss(LzssConfig::default());
+
+        assert_eq!(
+            vec![0b0001_0111, b'a', b'b', b'c', 0x02, 0x03, b'd'],
+            compressed.to_u8_vec()
+        );
+        assert_eq!(data[..], compressed.decompress_lzss().unwrap()[..]);
+    }
+
+    #[test]
+    fn long_distance() {
+        let mut data = b"0123456789".to_vec();
+        data.extend((0..300).map(|index| (index * 7 % 256) as u8 ^ 0x5A));
+        data.extend(b"0123456789");
+        let data = ByteArray::from(data);
+
+        let wide = data.compress_lzss(LzssConfig::default());
+        let narrow = data.compress_lzss(LzssConfig::new(16));
+
+        assert!(wide.len() < narrow.len());
+        assert_eq!(data[..], wide.decompress_lzss().unwrap()[..]);
+        assert_eq!(data[..], narrow.decompress_lzss().unwrap()[..]);
+    }
+
+    #[test]
+    fn round_trip_property() {
+        let configs = [
+            LzssConfig::default(),
+            LzssConfig::new(1),
+            LzssConfig::new(64),
+            LzssConfig::new(usize::MAX),
+        ];
+
+        for seed in 1..50 {
+            let data = ByteArray::from(sample_data(seed, seed as usize * 23));
+            for config in configs {
+                let compressed = data.compress_lzss(config);
+                assert_eq!(
+                    data[..],
+                    compressed.decompress_lzss().unwrap()[..],
+                    "seed {} with {:?}",
+                    seed,
+                    config
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_data() {
+        assert!(ByteArray::default().decompress_lzss().unwrap().is_empty());
+        assert!(matches!(
+            ByteArray::from(vec![0x00, 0x00, 0x00]).decompress_lzss(),
+            Err(UberByteError::CodeViolation(1))
+        ));
+        assert!(matches!(
+            ByteArray::from(vec![0x01, b'a', 0x00]).decompress_lzss(),
+            Err(UberByteError::CodeViolation(2))
+        ));
+        assert!(matches!(
+            ByteArray::from(vec![0x00]).decompress_lzss(),
+            Err(UberByteError::CodeViolation(0))
+        ));
+        assert!(matches!(
+            ByteArray::from(b"\xFFabcdefgh\xFF".to_vec()).decompress_lzss(),
+            Err(UberByteError::CodeViolation(9))
+        ));
+    }
+}
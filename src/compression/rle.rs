@@ -0,0 +1,271 @@
+//! Run-length encoding of repeated bytes
+//!
+//! ```rust
+//! use uberbyte::compression::rle::RleVariant;
+//! use uberbyte::ByteArray;
+//!
+//! let status = ByteArray::from(vec![0x00; 200]);
+//!
+//! let packed = status.compress_rle(RleVariant::PackBits);
+//!
+//! assert_eq!([0x81u8, 0x00, 0xB9, 0x00][..], packed[..]);
+//! assert_eq!(status[..], packed.decompress_rle(RleVariant::PackBits).unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+const PACK_BITS_MAX_RUN: usize = 128;
+const PACK_BITS_NO_OP: i8 = -128;
+const ESCAPE_MAX_RUN: usize = 255;
+const ESCAPE_MIN_RUN: usize = 4;
+
+/// The supported run-length encodings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RleVariant {
+    /// The PackBits scheme used by TIFF and MacPaint
+    ///
+    /// A header byte _n_ is followed by _n + 1_ literal bytes if positive, or by a
+    /// single byte repeated _1 - n_ times if negative
+    #[default]
+    PackBits,
+    /// Runs are written as the escape byte, the run length and the repeated byte
+    ///
+    /// Runs of at least 4 bytes and every occurrence of the escape byte are escaped,
+    /// all other bytes are copied as they are
+    Escape(u8),
+}
+
+impl ByteArray {
+    /// Compresses runs of repeated bytes
+    pub fn compress_rle(&self, variant: RleVariant) -> ByteArray {
+        let data = self.to_u8_vec();
+
+        let compressed = match variant {
+            RleVariant::PackBits => pack_bits(&data),
+            RleVariant::Escape(escape) => escape_runs(&data, escape),
+        };
+
+        ByteArray::from(compressed)
+    }
+
+    /// Restores the bytes compressed with [`ByteArray::compress_rle`]
+    ///
+    /// # Returns
+    ///
+    /// A _CodeViolation_ error with the index of a header or escape byte whose run is
+    /// cut off or has a length of 0
+    pub fn decompress_rle(&self, variant: RleVariant) -> Result<ByteArray, UberByteError> {
+        let data = self.to_u8_vec();
+
+        match variant {
+            RleVariant::PackBits => unpack_bits(&data),
+            RleVariant::Escape(escape) => unescape_runs(&data, escape),
+        }
+    }
+}
+
+/// Counts the bytes equal to the byte at the start, up to the limit
+fn run_length(data: &[u8], limit: usize) -> usize {
+    data.iter()
+        .take(limit)
+        .take_while(|byte| **byte == data[0])
+        .count()
+}
+
+fn pack_bits(data: &[u8]) -> Vec<u8> {
+    let mut packed = vec![];
+    let mut index = 0;
+
+    while index < data.len() {
+        let run = run_length(&data[index..], PACK_BITS_MAX_RUN);
+        if run >= 3 {
+            packed.push((257 - run) as u8);
+            packed.push(data[index]);
+            index += run;
+            continue;
+        }
+
+        let mut end = index + 1;
+        while end < data.len() && end - index < PACK_BITS_MAX_RUN && run_length(&data[end..], 3) < 3
+        {
+            end += 1;
+        }
+        packed.push((end - index - 1) as u8);
+        packed.extend(&data[index..end]);
+        index = end;
+    }
+
+    packed
+}
+
+fn unpack_bits(data: &[u8]) -> Result<ByteArray, UberByteError> {
+    let mut unpacked = ByteArray::default();
+    let mut index = 0;
+
+    while index < data.len() {
+        let header = data[index] as i8;
+        match header {
+            PACK_BITS_NO_OP => index += 1,
+            0.. => {
+                let end = index + 2 + header as usize;
+                let literal = data
+                    .get(index + 1..end)
+                    .ok_or(UberByteError::CodeViolation(index))?;
+                for byte in literal {
+                    unpacked.add_mut(UberByte::from(*byte));
+                }
+                index = end;
+            }
+            _ => {
+                let byte = data
+                    .get(index + 1)
+                    .ok_or(UberByteError::CodeViolation(index))?;
+                for _ in 0..1 - header as isize {
+                    unpacked.add_mut(UberByte::from(*byte));
+                }
+                index += 2;
+            }
+        }
+    }
+
+    Ok(unpacked)
+}
+
+fn escape_runs(data: &[u8], escape: u8) -> Vec<u8> {
+    let mut escaped = vec![];
+    let mut index = 0;
+
+    while index < data.len() {
+        let run = run_length(&data[index..], ESCAPE_MAX_RUN);
+        if run >= ESCAPE_MIN_RUN || data[index] == escape {
+            escaped.extend([escape, run as u8, data[index]]);
+            index += run;
+        } else {
+            escaped.push(data[index]);
+            index += 1;
+        }
+    }
+
+    escaped
+}
+
+fn unescape_runs(data: &[u8], escape: u8) -> Result<ByteArray, UberByteError> {
+    let mut unescaped = ByteArray::default();
+    let mut index = 0;
+
+    while index < data.len() {
+        if data[index] != escape {
+            unescaped.add_mut(UberByte::from(data[index]));
+            index += 1;
+            continue;
+        }
+
+        match data.get(index + 1..index + 3) {
+            Some([count, byte]) if *count > 0 => {
+                for _ in 0..*count {
+                    unescaped.add_mut(UberByte::from(*byte));
+                }
+                index += 3;
+            }
+            _ => return Err(UberByteError::CodeViolation(index)),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compression::test::sample_data;
+
+    #[test]
+    fn pack_bits_reference_vector() {
+        let data = ByteArray::from(vec![
+            0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0xAA, 0xAA, 0xAA, 0xAA, 0x80, 0x00, 0x2A, 0x22,
+            0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA,
+        ]);
+
+        let packed = data.compress_rle(RleVariant::PackBits);
+
+        assert_eq!(
+            vec![
+                0xFE, 0xAA, 0x02, 0x80, 0x00, 0x2A, 0xFD, 0xAA, 0x03, 0x80, 0x00, 0x2A, 0x22, 0xF7,
+                0xAA
+            ],
+            packed.to_u8_vec()
+        );
+        let with_no_op = ByteArray::from(vec![0x80, 0x00, 0x42, 0x80]);
+        assert_eq!(
+            vec![0x42],
+            with_no_op
+                .decompress_rle(RleVariant::PackBits)
+                .unwrap()
+                .to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn escape_runs() {
+        let data = ByteArray::from(vec![0x01, 0x1B, 0x02, 0x02, 0x02, 0x02, 0x02, 0x03]);
+        let variant = RleVariant::Escape(0x1B);
+
+        let compressed = data.compress_rle(variant);
+
+        assert_eq!(
+            vec![0x01, 0x1B, 0x01, 0x1B, 0x1B, 0x05, 0x02, 0x03],
+            compressed.to_u8_vec()
+        );
+        assert_eq!(
+            data.to_u8_vec(),
+            compressed.decompress_rle(variant).unwrap().to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn round_trip_property() {
+        let variants = [
+            RleVariant::PackBits,
+            RleVariant::Escape(0x00),
+            RleVariant::Escape(0xFF),
+        ];
+
+        for seed in 1..50 {
+            let mut data = ByteArray::from(sample_data(seed, seed as usize * 37));
+            for _ in 0..seed * 7 {
+                data.add_mut(UberByte::from(seed as u8));
+            }
+            for variant in variants {
+                let compressed = data.compress_rle(variant);
+                assert_eq!(
+                    data[..],
+                    compressed.decompress_rle(variant).unwrap()[..],
+                    "seed {} with {:?}",
+                    seed,
+                    variant
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_data() {
+        assert!(matches!(
+            ByteArray::from(vec![0x00, 0x01, 0x03, 0x01, 0x02])
+                .decompress_rle(RleVariant::PackBits),
+            Err(UberByteError::CodeViolation(2))
+        ));
+        assert!(matches!(
+            ByteArray::from(vec![0xFE]).decompress_rle(RleVariant::PackBits),
+            Err(UberByteError::CodeViolation(0))
+        ));
+        assert!(matches!(
+            ByteArray::from(vec![0x01, 0x1B, 0x00, 0x01]).decompress_rle(RleVariant::Escape(0x1B)),
+            Err(UberByteError::CodeViolation(1))
+        ));
+        assert!(matches!(
+            ByteArray::from(vec![0x1B, 0x04]).decompress_rle(RleVariant::Escape(0x1B)),
+            Err(UberByteError::CodeViolation(0))
+        ));
+    }
+}
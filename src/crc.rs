@@ -0,0 +1,272 @@
+//! Cyclic redundancy checks with the parameters of the commonly used presets
+//!
+//! ```rust
+//! use uberbyte::crc::Crc;
+//! use uberbyte::ByteArray;
+//!
+//! let data = ByteArray::from(b"123456789".to_vec());
+//!
+//! assert_eq!(0x4B37, Crc::CRC_16_MODBUS.checksum(&data));
+//! ```
+
+use crate::{ByteArray, UberByteError};
+
+/// The parameters of a CRC algorithm as used in the CRC catalogue
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::crc::Crc;
+/// use uberbyte::ByteArray;
+///
+/// let crc_5_usb = Crc::new(5, 0x05)
+///     .unwrap()
+///     .with_init(0x1F)
+///     .with_reflection(true, true)
+///     .with_xor_out(0x1F);
+///
+/// assert_eq!(0x19, crc_5_usb.checksum(&ByteArray::from(b"123456789".to_vec())));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc {
+    width: u8,
+    polynomial: u32,
+    init: u32,
+    reflect_in: bool,
+    reflect_out: bool,
+    xor_out: u32,
+}
+
+impl Crc {
+    /// CRC-8 (SMBus)
+    pub const CRC_8: Crc = Crc {
+        width: 8,
+        polynomial: 0x07,
+        init: 0x00,
+        reflect_in: false,
+        reflect_out: false,
+        xor_out: 0x00,
+    };
+
+    /// CRC-16/MODBUS, also used by many other industrial protocols
+    pub const CRC_16_MODBUS: Crc = Crc {
+        width: 16,
+        polynomial: 0x8005,
+        init: 0xFFFF,
+        reflect_in: true,
+        reflect_out: true,
+        xor_out: 0x0000,
+    };
+
+    /// CRC-16/CCITT-FALSE, also known as CRC-16/IBM-3740
+    pub const CRC_16_CCITT_FALSE: Crc = Crc {
+        width: 16,
+        polynomial: 0x1021,
+        init: 0xFFFF,
+        reflect_in: false,
+        reflect_out: false,
+        xor_out: 0x0000,
+    };
+
+    /// CRC-16/XMODEM
+    pub const CRC_16_XMODEM: Crc = Crc {
+        width: 16,
+        polynomial: 0x1021,
+        init: 0x0000,
+        reflect_in: false,
+        reflect_out: false,
+        xor_out: 0x0000,
+    };
+
+    /// CRC-32 as used by Ethernet, ZIP and PNG
+    pub const CRC_32: Crc = Crc {
+        width: 32,
+        polynomial: 0x04C1_1DB7,
+        init: 0xFFFF_FFFF,
+        reflect_in: true,
+        reflect_out: true,
+        xor_out: 0xFFFF_FFFF,
+    };
+
+    /// CRC-32C (Castagnoli)
+    pub const CRC_32C: Crc = Crc {
+        width: 32,
+        polynomial: 0x1EDC_6F41,
+        init: 0xFFFF_FFFF,
+        reflect_in: true,
+        reflect_out: true,
+        xor_out: 0xFFFF_FFFF,
+    };
+
+    /// Creates a new CRC with the given width and generator polynomial
+    ///
+    /// # Remarks
+    ///
+    /// The register starts at 0, nothing is reflected and the result is not XORed.
+    /// Bits of the polynomial, the initial value and the final XOR value above the
+    /// width are ignored.
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the width is 0 or more than 32 bits
+    pub fn new(width: u8, polynomial: u32) -> Result<Self, UberByteError> {
+        if width == 0 || width as u32 > u32::BITS {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        Ok(Crc {
+            width,
+            polynomial,
+            init: 0,
+            reflect_in: false,
+            reflect_out: false,
+            xor_out: 0,
+        })
+    }
+
+    /// Sets the initial value of the register
+    pub fn with_init(mut self, init: u32) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Sets if the input bytes and the final register value are reflected
+    pub fn with_reflection(mut self, reflect_in: bool, reflect_out: bool) -> Self {
+        self.reflect_in = reflect_in;
+        self.reflect_out = reflect_out;
+        self
+    }
+
+    /// Sets the value the final register is XORed with
+    pub fn with_xor_out(mut self, xor_out: u32) -> Self {
+        self.xor_out = xor_out;
+        self
+    }
+
+    /// The number of bits of the checksum, between 1 and 32
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+
+    /// The generator polynomial without the leading bit
+    pub fn polynomial(&self) -> u32 {
+        self.polynomial
+    }
+
+    /// The initial value of the register
+    pub fn init(&self) -> u32 {
+        self.init
+    }
+
+    /// Determines if every input byte is processed least significant bit first
+    pub fn reflect_in(&self) -> bool {
+        self.reflect_in
+    }
+
+    /// Determines if the final register value is reflected
+    pub fn reflect_out(&self) -> bool {
+        self.reflect_out
+    }
+
+    /// The value the final register is XORed with
+    pub fn xor_out(&self) -> u32 {
+        self.xor_out
+    }
+
+    /// Computes the checksum over all bytes of the given byte array
+    ///
+    /// # Remarks
+    ///
+    /// The register is kept aligned to its most significant bit, so widths below
+    /// 8 bits are handled the same way as wider ones
+    ///
+    /// # Returns
+    ///
+    /// The checksum in the lower _width_ bits of the returned value
+    pub fn checksum(&self, data: &ByteArray) -> u32 {
+        let shift = u32::BITS - self.width as u32;
+        let polynomial = self.polynomial << shift;
+        let top_bit = 1 << (u32::BITS - 1);
+
+        let mut register = self.init << shift;
+        for byte in &data[..] {
+            let value = match self.reflect_in {
+                true => byte.into_u8().reverse_bits(),
+                false => byte.into_u8(),
+            };
+            register ^= (value as u32) << (u32::BITS - 8);
+
+            for _ in 0..8 {
+                register = match register & top_bit != 0 {
+                    true => (register << 1) ^ polynomial,
+                    false => register << 1,
+                };
+            }
+        }
+
+        register >>= shift;
+        if self.reflect_out {
+            register = register.reverse_bits() >> shift;
+        }
+
+        (register ^ self.xor_out) & (u32::MAX >> shift)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_values() {
+        let data = ByteArray::from(b"123456789".to_vec());
+
+        assert_eq!(0xF4, Crc::CRC_8.checksum(&data));
+        assert_eq!(0x4B37, Crc::CRC_16_MODBUS.checksum(&data));
+        assert_eq!(0x29B1, Crc::CRC_16_CCITT_FALSE.checksum(&data));
+        assert_eq!(0x31C3, Crc::CRC_16_XMODEM.checksum(&data));
+        assert_eq!(0xCBF4_3926, Crc::CRC_32.checksum(&data));
+        assert_eq!(0xE306_9283, Crc::CRC_32C.checksum(&data));
+    }
+
+    #[test]
+    fn empty_data() {
+        assert_eq!(0xFFFF, Crc::CRC_16_MODBUS.checksum(&ByteArray::default()));
+        assert_eq!(0, Crc::CRC_32.checksum(&ByteArray::default()));
+    }
+
+    #[test]
+    fn narrow_widths() {
+        let data = ByteArray::from(b"123456789".to_vec());
+        let crc_3_gsm = Crc::new(3, 0x3).unwrap().with_xor_out(0x7);
+        let crc_5_usb = Crc::new(5, 0x05)
+            .unwrap()
+            .with_init(0x1F)
+            .with_reflection(true, true)
+            .with_xor_out(0x1F);
+        let crc_7_mmc = Crc::new(7, 0x09).unwrap();
+
+        assert_eq!(0x4, crc_3_gsm.checksum(&data));
+        assert_eq!(0x19, crc_5_usb.checksum(&data));
+        assert_eq!(0x75, crc_7_mmc.checksum(&data));
+    }
+
+    #[test]
+    fn invalid_width() {
+        assert!(matches!(
+            Crc::new(0, 0x1),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            Crc::new(33, 0x1),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert_eq!(
+            Crc::CRC_16_MODBUS,
+            Crc::new(16, 0x8005)
+                .unwrap()
+                .with_init(0xFFFF)
+                .with_reflection(true, true)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Bit level comparison of bytes and byte arrays
+//!
+//! ```rust
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let before = ByteArray::from(vec![0b_0000_0001, 0xFF]);
+//! let after = ByteArray::from(vec![0b_0000_0010, 0xFF, 0x00]);
+//!
+//! let diff = before.diff(&after);
+//!
+//! assert_eq!(2, diff.changes.len());
+//! assert_eq!(
+//!     "[0] 00000001 -> 00000010 ......+-\nlength 2 -> 3\n",
+//!     diff.to_string()
+//! );
+//! ```
+
+use std::fmt::Display;
+
+use crate::{ByteArray, UberByte};
+
+/// The bit changes between two bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteDiff {
+    /// The byte before the change
+    pub old: UberByte,
+    /// The byte after the change
+    pub new: UberByte,
+    /// The mask of all bits that changed from 0 to 1
+    pub rising: UberByte,
+    /// The mask of all bits that changed from 1 to 0
+    pub falling: UberByte,
+}
+
+impl ByteDiff {
+    /// Returns the mask of all bits that changed
+    pub fn changed(&self) -> UberByte {
+        self.rising | self.falling
+    }
+
+    /// Determines if no bit changed
+    pub fn is_empty(&self) -> bool {
+        self.changed().into_u8() == 0
+    }
+}
+
+/// Renders the old and new byte in binary form followed by a marker line
+///
+/// Every rising bit is marked with a `+`, every falling bit with a `-` and
+/// every unchanged bit with a `.`
+impl Display for ByteDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:08b} -> {:08b} ",
+            self.old.into_u8(),
+            self.new.into_u8()
+        )?;
+        for bit_index in (0..8).rev() {
+            let marker = match (
+                self.rising.is_bit_set(bit_index),
+                self.falling.is_bit_set(bit_index),
+            ) {
+                (true, _) => "+",
+                (_, true) => "-",
+                _ => ".",
+            };
+            f.write_str(marker)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single bit that differs between two byte arrays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitChange {
+    /// The index of the byte containing the bit
+    pub byte_index: usize,
+    /// The index of the bit inside the byte
+    pub bit_index: usize,
+    /// The value of the bit before the change
+    pub old: bool,
+    /// The value of the bit after the change
+    pub new: bool,
+}
+
+/// The lengths of two compared byte arrays that differ in size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// The length of the array before the change
+    pub old: usize,
+    /// The length of the array after the change
+    pub new: usize,
+}
+
+/// The bit changes between two byte arrays
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ByteArrayDiff {
+    /// All changed bits ordered by byte and bit index
+    pub changes: Vec<BitChange>,
+    /// Set if the arrays are not of the same length
+    ///
+    /// Only the bytes both arrays share are compared bit by bit
+    pub length_mismatch: Option<LengthMismatch>,
+    bytes: Vec<(usize, ByteDiff)>,
+}
+
+impl ByteArrayDiff {
+    /// Determines if both arrays are equal
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.length_mismatch.is_none()
+    }
+
+    /// Returns the byte diffs of all changed bytes together with their index
+    pub fn changed_bytes(&self) -> &[(usize, ByteDiff)] {
+        &self.bytes
+    }
+}
+
+/// Renders one line per changed byte and a final line if the lengths differ
+impl Display for ByteArrayDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, diff) in &self.bytes {
+            writeln!(f, "[{}] {}", index, diff)?;
+        }
+        if let Some(mismatch) = self.length_mismatch {
+            writeln!(f, "length {} -> {}", mismatch.old, mismatch.new)?;
+        }
+        Ok(())
+    }
+}
+
+impl UberByte {
+    /// Compares the byte with the other byte bit by bit
+    ///
+    /// # Returns
+    ///
+    /// The masks of the bits that rose and fell going from this byte to the other
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::UberByte;
+    ///
+    /// let diff = UberByte::from(0b_0000_0110).diff(&UberByte::from(0b_0000_0011));
+    ///
+    /// assert_eq!(0b_0000_0001, diff.rising.into_u8());
+    /// assert_eq!(0b_0000_0100, diff.falling.into_u8());
+    /// ```
+    pub fn diff(&self, other: &UberByte) -> ByteDiff {
+        let old = self.into_u8();
+        let new = other.into_u8();
+
+        ByteDiff {
+            old: *self,
+            new: *other,
+            rising: UberByte::from(!old & new),
+            falling: UberByte::from(old & !new),
+        }
+    }
+}
+
+impl ByteArray {
+    /// Compares the byte array with the other array bit by bit
+    ///
+    /// # Returns
+    ///
+    /// All bits that changed going from this array to the other and the lengths
+    /// of both arrays if they differ
+    pub fn diff(&self, other: &ByteArray) -> ByteArrayDiff {
+        let mut result = ByteArrayDiff::default();
+
+        for (byte_index, (old, new)) in self[..].iter().zip(other[..].iter()).enumerate() {
+            let diff = old.diff(new);
+            if diff.is_empty() {
+                continue;
+            }
+
+            for bit_index in 0..8 {
+                if diff.changed().is_bit_set(bit_index) {
+                    result.changes.push(BitChange {
+                        byte_index,
+                        bit_index,
+                        old: old.is_bit_set(bit_index),
+                        new: new.is_bit_set(bit_index),
+                    });
+                }
+            }
+            result.bytes.push((byte_index, diff));
+        }
+
+        if self.len() != other.len() {
+            result.length_mismatch = Some(LengthMismatch {
+                old: self.len(),
+                new: other.len(),
+            });
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn byte_diff() {
+        let diff = UberByte::from(0b_1010_0000).diff(&UberByte::from(0b_0110_0001));
+
+        assert_eq!(0b_0100_0001, diff.rising.into_u8());
+        assert_eq!(0b_1000_0000, diff.falling.into_u8());
+        assert_eq!(0b_1100_0001, diff.changed().into_u8());
+        assert_eq!("10100000 -> 01100001 -+.....+", diff.to_string());
+    }
+
+    #[test]
+    fn byte_diff_equal() {
+        let diff = UberByte::from(0x5A).diff(&UberByte::from(0x5A));
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn byte_array_diff() {
+        let before = ByteArray::from(vec![0x00, 0x0F, 0xFF]);
+        let after = ByteArray::from(vec![0x00, 0x1E, 0xFF]);
+
+        let diff = before.diff(&after);
+
+        assert_eq!(
+            vec![
+                BitChange {
+                    byte_index: 1,
+                    bit_index: 0,
+                    old: true,
+                    new: false
+                },
+                BitChange {
+                    byte_index: 1,
+                    bit_index: 4,
+                    old: false,
+                    new: true
+                },
+            ],
+            diff.changes
+        );
+        assert!(diff.length_mismatch.is_none());
+        assert_eq!(1, diff.changed_bytes().len());
+        assert_eq!("[1] 00001111 -> 00011110 ...+...-\n", diff.to_string());
+    }
+
+    #[test]
+    fn byte_array_diff_length_mismatch() {
+        let before = ByteArray::from(vec![0x01, 0x02, 0x03]);
+        let after = ByteArray::from(vec![0x01]);
+
+        let diff = before.diff(&after);
+
+        assert!(diff.changes.is_empty());
+        assert_eq!(
+            Some(LengthMismatch { old: 3, new: 1 }),
+            diff.length_mismatch
+        );
+        assert!(!diff.is_empty());
+        assert!(before.diff(&before).is_empty());
+    }
+}
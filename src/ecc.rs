@@ -0,0 +1,486 @@
+//! Error correcting codes based on the Hamming code
+//!
+//! All codewords share the same bit layout. The bit at index _n_ holds the
+//! Hamming position _n_, so the parity bits live at the power of two indexes.
+//! Codes with double error detection store the overall parity at index 0.
+//!
+//! ```rust
+//! use uberbyte::ecc::EccScheme;
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let data = ByteArray::from(vec![0x42]);
+//! let mut encoded = EccScheme::Hamming84.encode(&data).unwrap();
+//!
+//! // flip a single bit "in transit"
+//! encoded[0] ^= UberByte::from(0b_0000_0100);
+//!
+//! let decoded = EccScheme::Hamming84.decode(&encoded).unwrap();
+//! assert_eq!(UberByte::from(0x42), decoded.data[0]);
+//! assert_eq!(1, decoded.corrections.len());
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// The supported error correcting codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EccScheme {
+    /// Hamming(7,4), every nibble is stored in a single byte.
+    /// Corrects single bit errors but can not detect double bit errors.
+    Hamming74,
+    /// Extended Hamming(8,4), every nibble is stored in a single byte.
+    /// Corrects single bit errors and detects double bit errors.
+    Hamming84,
+    /// SECDED(72,64), every 8 data bytes are stored in 9 bytes.
+    /// Corrects single bit errors and detects double bit errors.
+    Secded7264,
+}
+
+/// Describes a single bit that was corrected while decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitCorrection {
+    /// The index of the codeword in the encoded data
+    pub codeword: usize,
+    /// The index of the corrected bit inside the codeword
+    pub bit: usize,
+}
+
+/// The result of successfully decoding error correcting codewords
+#[derive(Debug, Clone)]
+pub struct EccDecoded {
+    /// The decoded data
+    pub data: ByteArray,
+    /// All single bit errors that were found and corrected
+    pub corrections: Vec<BitCorrection>,
+}
+
+impl EccScheme {
+    /// Encodes the given data into codewords
+    ///
+    /// # Returns
+    ///
+    /// The encoded data. Hamming(7,4) and Hamming(8,4) double the length of the data,
+    /// SECDED(72,64) adds one byte for every 8 data bytes.
+    ///
+    /// An _InvalidLength_ error if SECDED(72,64) is used and the length of the data
+    /// is not a multiple of 8
+    pub fn encode(&self, data: &ByteArray) -> Result<ByteArray, UberByteError> {
+        let mut encoded = ByteArray::default();
+
+        match self {
+            EccScheme::Hamming74 | EccScheme::Hamming84 => {
+                for byte in data.clone() {
+                    let value = byte.into_u8();
+                    encoded.add_mut(self.encode_nibble(value >> 4));
+                    encoded.add_mut(self.encode_nibble(value & 0x0F));
+                }
+            }
+            EccScheme::Secded7264 => {
+                if !data.len().is_multiple_of(8) {
+                    return Err(UberByteError::InvalidLength);
+                }
+                for block in data[..].chunks(8) {
+                    let value = block
+                        .iter()
+                        .fold(0u64, |acc, byte| (acc << 8) | byte.into_u8() as u64);
+                    encoded.add_range_mut(encode_secded_72_64(value).to_vec());
+                }
+            }
+        }
+
+        Ok(encoded)
+    }
+
+    /// Decodes the given codewords and corrects all single bit errors
+    ///
+    /// # Returns
+    ///
+    /// The decoded data together with the list of corrected bits
+    ///
+    /// An _InvalidLength_ error if the data is not made out of complete codewords
+    ///
+    /// An _UncorrectableError_ error holding the index of the first codeword
+    /// in which a double bit error was detected
+    pub fn decode(&self, data: &ByteArray) -> Result<EccDecoded, UberByteError> {
+        let mut decoded = EccDecoded {
+            data: ByteArray::default(),
+            corrections: vec![],
+        };
+
+        match self {
+            EccScheme::Hamming74 | EccScheme::Hamming84 => {
+                if !data.len().is_multiple_of(2) {
+                    return Err(UberByteError::InvalidLength);
+                }
+                for (index, pair) in data[..].chunks(2).enumerate() {
+                    let mut value = 0;
+                    for (offset, codeword) in pair.iter().enumerate() {
+                        let codeword_index = index * 2 + offset;
+                        let (nibble, corrected) = match self {
+                            EccScheme::Hamming74 => decode_hamming_7_4(*codeword),
+                            _ => decode_hamming_8_4(*codeword)
+                                .map_err(|_| UberByteError::UncorrectableError(codeword_index))?,
+                        };
+                        if let Some(bit) = corrected {
+                            decoded.corrections.push(BitCorrection {
+                                codeword: codeword_index,
+                                bit,
+                            });
+                        }
+                        value = (value << 4) | nibble;
+                    }
+                    decoded.data.add_mut(UberByte::from(value));
+                }
+            }
+            EccScheme::Secded7264 => {
+                if !data.len().is_multiple_of(9) {
+                    return Err(UberByteError::InvalidLength);
+                }
+                for (index, block) in data[..].chunks(9).enumerate() {
+                    let mut codeword = [UberByte::MIN; 9];
+                    codeword.copy_from_slice(block);
+                    let (value, corrected) = decode_secded_72_64(&codeword)
+                        .map_err(|_| UberByteError::UncorrectableError(index))?;
+                    if let Some(bit) = corrected {
+                        decoded.corrections.push(BitCorrection {
+                            codeword: index,
+                            bit,
+                        });
+                    }
+                    decoded.data += ByteArray::from(value.to_be_bytes().to_vec());
+                }
+            }
+        }
+
+        Ok(decoded)
+    }
+
+    fn encode_nibble(&self, nibble: u8) -> UberByte {
+        match self {
+            EccScheme::Hamming74 => encode_hamming_7_4(nibble),
+            _ => encode_hamming_8_4(nibble),
+        }
+    }
+}
+
+/// Encodes the lower nibble of the given value as a Hamming(7,4) codeword
+///
+/// # Returns
+///
+/// The codeword stored in the bits 1 to 7. Bit 0 is always cleared.
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::ecc::{decode_hamming_7_4, encode_hamming_7_4};
+///
+/// let codeword = encode_hamming_7_4(0b_1011);
+///
+/// assert_eq!((0b_1011, None), decode_hamming_7_4(codeword));
+/// ```
+pub fn encode_hamming_7_4(nibble: u8) -> UberByte {
+    UberByte::from(hamming_encode(nibble as u64, 4, false) as u8)
+}
+
+/// Decodes a Hamming(7,4) codeword
+///
+/// # Returns
+///
+/// The decoded nibble and the index of the corrected bit if a single bit error was found
+///
+/// # Remarks
+///
+/// Bit 0 of the codeword is ignored. Double bit errors can not be detected and
+/// will result in a wrongly corrected nibble.
+pub fn decode_hamming_7_4(codeword: UberByte) -> (u8, Option<usize>) {
+    let (value, corrected) = hamming_correct(codeword.clear(0b_0000_0001).into_u8() as u128, 7);
+    (hamming_extract(value, 4) as u8, corrected)
+}
+
+/// Encodes the lower nibble of the given value as a extended Hamming(8,4) codeword
+///
+/// # Returns
+///
+/// The codeword with the overall parity stored in bit 0
+pub fn encode_hamming_8_4(nibble: u8) -> UberByte {
+    UberByte::from(hamming_encode(nibble as u64, 4, true) as u8)
+}
+
+/// Decodes a extended Hamming(8,4) codeword
+///
+/// # Returns
+///
+/// The decoded nibble and the index of the corrected bit if a single bit error was found
+///
+/// An _UncorrectableError_ error if a double bit error was detected
+pub fn decode_hamming_8_4(codeword: UberByte) -> Result<(u8, Option<usize>), UberByteError> {
+    let (value, corrected) =
+        secded_correct(codeword.into_u8() as u128, 7, overall_parity(&[codeword]))?;
+    Ok((hamming_extract(value, 4) as u8, corrected))
+}
+
+/// Encodes the given value as a SECDED(72,64) codeword
+///
+/// # Returns
+///
+/// The 72 bit codeword as 9 big endian bytes. The overall parity is stored
+/// in bit 0 of the last byte.
+pub fn encode_secded_72_64(value: u64) -> [UberByte; 9] {
+    let codeword = hamming_encode(value, 64, true);
+    let bytes = codeword.to_be_bytes();
+
+    let mut result = [UberByte::MIN; 9];
+    for (target, source) in result.iter_mut().zip(bytes[7..].iter()) {
+        *target = UberByte::from(source);
+    }
+    result
+}
+
+/// Decodes a SECDED(72,64) codeword
+///
+/// # Returns
+///
+/// The decoded value and the index of the corrected bit if a single bit error was found.
+/// Bit index 0 is the overall parity bit, bit index 71 the most significant bit of the first byte.
+///
+/// An _UncorrectableError_ error if a double bit error was detected
+pub fn decode_secded_72_64(
+    codeword: &[UberByte; 9],
+) -> Result<(u64, Option<usize>), UberByteError> {
+    let value = codeword
+        .iter()
+        .fold(0u128, |acc, byte| (acc << 8) | byte.into_u8() as u128);
+
+    let (value, corrected) = secded_correct(value, 71, overall_parity(codeword))?;
+    Ok((hamming_extract(value, 64), corrected))
+}
+
+/// Determines the parity of all bits in the given bytes
+///
+/// # Returns
+///
+/// TRUE if the number of set bits is odd
+fn overall_parity(bytes: &[UberByte]) -> bool {
+    let set_bits: usize = bytes.iter().map(|b| b.count_set_bits() as usize).sum();
+    set_bits % 2 == 1
+}
+
+/// Places the data bits on the non power of two positions and computes the parity bits
+fn hamming_encode(data: u64, data_bits: usize, with_overall_parity: bool) -> u128 {
+    let mut codeword = 0u128;
+    let mut position: usize = 1;
+    let mut index = 0;
+
+    while index < data_bits {
+        if !position.is_power_of_two() {
+            if (data >> index) & 1 == 1 {
+                codeword |= 1 << position;
+            }
+            index += 1;
+        }
+        position += 1;
+    }
+
+    // setting every parity bit to the matching syndrome bit zeroes the syndrome
+    let syndrome = hamming_syndrome(codeword, position - 1);
+    let mut parity_position = 1;
+    while parity_position < position {
+        if syndrome & parity_position != 0 {
+            codeword |= 1 << parity_position;
+        }
+        parity_position <<= 1;
+    }
+
+    if with_overall_parity && codeword.count_ones() % 2 == 1 {
+        codeword |= 1;
+    }
+
+    codeword
+}
+
+/// Computes the syndrome as the XOR of all positions of set bits
+fn hamming_syndrome(codeword: u128, last_position: usize) -> usize {
+    (1..=last_position)
+        .filter(|position| (codeword >> position) & 1 == 1)
+        .fold(0, |syndrome, position| syndrome ^ position)
+}
+
+fn hamming_correct(codeword: u128, last_position: usize) -> (u128, Option<usize>) {
+    match hamming_syndrome(codeword, last_position) {
+        0 => (codeword, None),
+        position => (codeword ^ (1 << position), Some(position)),
+    }
+}
+
+fn secded_correct(
+    codeword: u128,
+    last_position: usize,
+    parity_error: bool,
+) -> Result<(u128, Option<usize>), UberByteError> {
+    let syndrome = hamming_syndrome(codeword, last_position);
+
+    match (syndrome, parity_error) {
+        (0, false) => Ok((codeword, None)),
+        (0, true) => Ok((codeword ^ 1, Some(0))),
+        (position, true) if position <= last_position => {
+            Ok((codeword ^ (1 << position), Some(position)))
+        }
+        (_, _) => Err(UberByteError::UncorrectableError(0)),
+    }
+}
+
+/// Collects the data bits from the non power of two positions
+fn hamming_extract(codeword: u128, data_bits: usize) -> u64 {
+    let mut data = 0u64;
+    let mut position: usize = 1;
+    let mut index = 0;
+
+    while index < data_bits {
+        if !position.is_power_of_two() {
+            if (codeword >> position) & 1 == 1 {
+                data |= 1 << index;
+            }
+            index += 1;
+        }
+        position += 1;
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hamming_7_4_all_nibbles() {
+        for nibble in 0..16 {
+            let codeword = encode_hamming_7_4(nibble);
+
+            assert!(!codeword.is_bit_0_set());
+            assert_eq!((nibble, None), decode_hamming_7_4(codeword));
+
+            for bit in 1..8 {
+                let corrupted = UberByte::from(codeword.into_u8() ^ (1 << bit));
+                assert_eq!((nibble, Some(bit)), decode_hamming_7_4(corrupted));
+            }
+        }
+    }
+
+    #[test]
+    fn hamming_7_4_known_codeword() {
+        // d1..d4 = 1 0 1 1 end up on the positions 3 5 6 7, only the parity bit 2 is needed
+        assert_eq!(UberByte::from(0b_1100_1100), encode_hamming_7_4(0b_1101));
+    }
+
+    #[test]
+    fn hamming_8_4_all_nibbles() {
+        for nibble in 0..16 {
+            let codeword = encode_hamming_8_4(nibble);
+
+            assert_eq!(0, codeword.count_set_bits() % 2);
+            assert_eq!((nibble, None), decode_hamming_8_4(codeword).unwrap());
+
+            for bit in 0..8 {
+                let corrupted = UberByte::from(codeword.into_u8() ^ (1 << bit));
+                assert_eq!((nibble, Some(bit)), decode_hamming_8_4(corrupted).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn hamming_8_4_double_error() {
+        let codeword = encode_hamming_8_4(0b_1001);
+
+        for first in 0..8 {
+            for second in (first + 1)..8 {
+                let corrupted = UberByte::from(codeword.into_u8() ^ (1 << first) ^ (1 << second));
+                assert!(decode_hamming_8_4(corrupted).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn secded_72_64_single_error() {
+        let value = 0x0123_4567_89AB_CDEF;
+        let codeword = encode_secded_72_64(value);
+
+        assert_eq!((value, None), decode_secded_72_64(&codeword).unwrap());
+
+        for bit in 0..72 {
+            let mut corrupted = codeword;
+            let byte = 8 - bit / 8;
+            corrupted[byte] = UberByte::from(corrupted[byte].into_u8() ^ (1 << (bit % 8)));
+
+            assert_eq!((value, Some(bit)), decode_secded_72_64(&corrupted).unwrap());
+        }
+    }
+
+    #[test]
+    fn secded_72_64_double_error() {
+        let mut codeword = encode_secded_72_64(u64::MAX);
+        codeword[0] ^= UberByte::from(0b_0000_0011);
+
+        assert!(decode_secded_72_64(&codeword).is_err());
+    }
+
+    #[test]
+    fn byte_array_round_trip() {
+        let data = ByteArray::from(vec![0x00, 0xFF, 0x5A, 0xA5, 0x12, 0x34, 0x56, 0x78]);
+
+        for scheme in [
+            EccScheme::Hamming74,
+            EccScheme::Hamming84,
+            EccScheme::Secded7264,
+        ] {
+            let encoded = scheme.encode(&data).unwrap();
+            let decoded = scheme.decode(&encoded).unwrap();
+
+            assert_eq!(data[..], decoded.data[..]);
+            assert!(decoded.corrections.is_empty());
+        }
+    }
+
+    #[test]
+    fn byte_array_corrections() {
+        let data = ByteArray::from(vec![0x5A, 0xA5]);
+        let mut encoded = EccScheme::Hamming84.encode(&data).unwrap();
+        encoded[3] ^= UberByte::from(0b_0001_0000);
+
+        let decoded = EccScheme::Hamming84.decode(&encoded).unwrap();
+
+        assert_eq!(data[..], decoded.data[..]);
+        assert_eq!(
+            vec![BitCorrection {
+                codeword: 3,
+                bit: 4
+            }],
+            decoded.corrections
+        );
+    }
+
+    #[test]
+    fn byte_array_uncorrectable() {
+        let data = ByteArray::from(vec![0x5A, 0xA5]);
+        let mut encoded = EccScheme::Hamming84.encode(&data).unwrap();
+        encoded[2] ^= UberByte::from(0b_0000_0011);
+
+        assert!(matches!(
+            EccScheme::Hamming84.decode(&encoded),
+            Err(UberByteError::UncorrectableError(2))
+        ));
+    }
+
+    #[test]
+    fn byte_array_invalid_length() {
+        let data = ByteArray::from(vec![0x01, 0x02, 0x03]);
+
+        assert!(matches!(
+            EccScheme::Secded7264.encode(&data),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            EccScheme::Hamming84.decode(&data),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+}
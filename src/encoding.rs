@@ -0,0 +1,8 @@
+//! Byte stuffing and text encodings for transporting binary data
+
+pub mod ascii85;
+pub mod base16;
+pub mod base32;
+pub mod base64;
+pub mod cobs;
+pub mod slip;
@@ -0,0 +1,248 @@
+//! Ascii85 and Z85 encoding, packing four bytes into five printable characters
+//!
+//! ```rust
+//! use uberbyte::encoding::ascii85::Ascii85Variant;
+//! use uberbyte::ByteArray;
+//!
+//! let data = ByteArray::from(vec![0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B]);
+//!
+//! assert_eq!("HelloWorld", data.to_ascii85(Ascii85Variant::Z85).unwrap());
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const ASCII85_OFFSET: u8 = b'!';
+const ASCII85_ZERO: u8 = b'z';
+
+/// The supported flavours of base 85 encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Ascii85Variant {
+    /// The Adobe flavour using the characters `!` to `u`
+    ///
+    /// A group of four zero bytes is written as `z` and the last group may be partial.
+    /// Decoding ignores whitespace and the optional `<~` and `~>` delimiters
+    #[default]
+    Ascii85,
+    /// The ZeroMQ flavour with a string and source code safe alphabet
+    ///
+    /// Only whole groups of four bytes and five characters are allowed
+    Z85,
+}
+
+impl Ascii85Variant {
+    fn symbol(&self, value: u32) -> char {
+        match self {
+            Ascii85Variant::Ascii85 => (ASCII85_OFFSET + value as u8) as char,
+            Ascii85Variant::Z85 => Z85_ALPHABET[value as usize] as char,
+        }
+    }
+
+    fn value(&self, character: u8) -> Option<u32> {
+        match self {
+            Ascii85Variant::Ascii85 => match character {
+                b'!'..=b'u' => Some((character - ASCII85_OFFSET) as u32),
+                _ => None,
+            },
+            Ascii85Variant::Z85 => Z85_ALPHABET
+                .iter()
+                .position(|symbol| *symbol == character)
+                .map(|value| value as u32),
+        }
+    }
+}
+
+impl ByteArray {
+    /// Encodes the bytes as base 85 text
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if Z85 is used on a length that is not a multiple of 4
+    pub fn to_ascii85(&self, variant: Ascii85Variant) -> Result<String, UberByteError> {
+        if variant == Ascii85Variant::Z85 && !self.len().is_multiple_of(4) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let bytes = self.to_u8_vec();
+        let mut text = String::new();
+
+        for chunk in bytes.chunks(4) {
+            let mut group = [0u8; 4];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let mut block = u32::from_be_bytes(group);
+
+            if variant == Ascii85Variant::Ascii85 && chunk.len() == 4 && block == 0 {
+                text.push(ASCII85_ZERO as char);
+                continue;
+            }
+
+            let mut digits = [' '; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = variant.symbol(block % 85);
+                block /= 85;
+            }
+            text.extend(&digits[..=chunk.len()]);
+        }
+
+        Ok(text)
+    }
+
+    /// Decodes base 85 text
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidCharacter_ error with the position of the first character outside the alphabet
+    /// or of a group that does not fit into four bytes
+    ///
+    /// An _InvalidLength_ error if the last group has a single character, or for Z85 if the
+    /// length is not a multiple of 5
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::encoding::ascii85::Ascii85Variant;
+    /// use uberbyte::ByteArray;
+    ///
+    /// let data = ByteArray::from_ascii85("<~9jqo^z~>", Ascii85Variant::Ascii85).unwrap();
+    ///
+    /// assert_eq!(b"Man \0\0\0\0"[..], data[..]);
+    /// ```
+    pub fn from_ascii85(text: &str, variant: Ascii85Variant) -> Result<ByteArray, UberByteError> {
+        let mut body = text;
+        let mut offset = 0;
+        if variant == Ascii85Variant::Ascii85 {
+            offset = text.len() - text.trim_start().len();
+            body = body.trim();
+            if let Some(rest) = body.strip_prefix("<~") {
+                offset += 2;
+                body = rest;
+            }
+            body = body.strip_suffix("~>").unwrap_or(body);
+        } else if !body.len().is_multiple_of(5) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut decoded = ByteArray::default();
+        let mut block = 0u32;
+        let mut count = 0;
+
+        for (index, character) in body.bytes().enumerate() {
+            let position = offset + index;
+            if variant == Ascii85Variant::Ascii85 {
+                if character.is_ascii_whitespace() {
+                    continue;
+                }
+                if character == ASCII85_ZERO && count == 0 {
+                    for _ in 0..4 {
+                        decoded.add_mut(UberByte::MIN);
+                    }
+                    continue;
+                }
+            }
+
+            let value = variant
+                .value(character)
+                .ok_or(UberByteError::InvalidCharacter(position))?;
+            block = block
+                .checked_mul(85)
+                .and_then(|block| block.checked_add(value))
+                .ok_or(UberByteError::InvalidCharacter(position))?;
+            count += 1;
+
+            if count == 5 {
+                for byte in block.to_be_bytes() {
+                    decoded.add_mut(UberByte::from(byte));
+                }
+                block = 0;
+                count = 0;
+            }
+        }
+
+        match count {
+            0 => Ok(decoded),
+            1 => Err(UberByteError::InvalidLength),
+            _ => {
+                for _ in count..5 {
+                    block = block.saturating_mul(85).saturating_add(84);
+                }
+                for byte in &block.to_be_bytes()[..count - 1] {
+                    decoded.add_mut(UberByte::from(*byte));
+                }
+                Ok(decoded)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ascii85_round_trip() {
+        let data = ByteArray::from(b"Man is distinguished".to_vec());
+
+        let encoded = data.to_ascii85(Ascii85Variant::Ascii85).unwrap();
+
+        assert_eq!("9jqo^BlbD-BleB1DJ+*+F(f,q", encoded);
+        for length in 0..data.len() {
+            let part = ByteArray::from(&data[..length]);
+            let encoded = part.to_ascii85(Ascii85Variant::Ascii85).unwrap();
+            assert_eq!(
+                part[..],
+                ByteArray::from_ascii85(&encoded, Ascii85Variant::Ascii85).unwrap()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn ascii85_zeros_and_delimiters() {
+        let data = ByteArray::from(vec![0, 0, 0, 0, 0]);
+
+        assert_eq!("z!!", data.to_ascii85(Ascii85Variant::Ascii85).unwrap());
+        assert_eq!(
+            data[..],
+            ByteArray::from_ascii85(" <~z\n!!~> ", Ascii85Variant::Ascii85).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn z85_spec_vector() {
+        let data = ByteArray::from(vec![0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B]);
+
+        assert_eq!("HelloWorld", data.to_ascii85(Ascii85Variant::Z85).unwrap());
+        assert_eq!(
+            data[..],
+            ByteArray::from_ascii85("HelloWorld", Ascii85Variant::Z85).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            ByteArray::from(vec![1, 2, 3]).to_ascii85(Ascii85Variant::Z85),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            ByteArray::from_ascii85("<~Hel{o~>", Ascii85Variant::Ascii85),
+            Err(UberByteError::InvalidCharacter(5))
+        ));
+        assert!(matches!(
+            ByteArray::from_ascii85("Hell~", Ascii85Variant::Z85),
+            Err(UberByteError::InvalidCharacter(4))
+        ));
+        assert!(matches!(
+            ByteArray::from_ascii85("Hello!", Ascii85Variant::Z85),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            ByteArray::from_ascii85("9jqo^B", Ascii85Variant::Ascii85),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            ByteArray::from_ascii85("uuuuu", Ascii85Variant::Ascii85),
+            Err(UberByteError::InvalidCharacter(4))
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+//! Base16 encoding, better known as hexadecimal text
+//!
+//! ```rust
+//! use uberbyte::ByteArray;
+//!
+//! let data = ByteArray::from(vec![0xDE, 0xAD]);
+//!
+//! assert_eq!("DEAD", data.to_base16());
+//! assert_eq!(data[..], ByteArray::from_base16("dead").unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+const ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+
+impl ByteArray {
+    /// Encodes the bytes as upper case hexadecimal text
+    pub fn to_base16(&self) -> String {
+        let mut text = String::with_capacity(self.len() * 2);
+        for byte in &self[..] {
+            text.push(ALPHABET[(byte.into_u8() >> 4) as usize] as char);
+            text.push(ALPHABET[(byte.into_u8() & 0x0F) as usize] as char);
+        }
+        text
+    }
+
+    /// Decodes hexadecimal text, ignoring the case of the letters
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidCharacter_ error with the position of the first character that is no hex digit
+    ///
+    /// An _InvalidLength_ error if the number of characters is odd
+    pub fn from_base16(text: &str) -> Result<ByteArray, UberByteError> {
+        let characters = text.as_bytes();
+        if let Some(position) = characters
+            .iter()
+            .position(|character| !character.is_ascii_hexdigit())
+        {
+            return Err(UberByteError::InvalidCharacter(position));
+        }
+        if !characters.len().is_multiple_of(2) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut decoded = ByteArray::default();
+        for pair in characters.chunks(2) {
+            let high = (pair[0] as char).to_digit(16).unwrap_or_default();
+            let low = (pair[1] as char).to_digit(16).unwrap_or_default();
+            decoded.add_mut(UberByte::from((high << 4 | low) as u8));
+        }
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let data = ByteArray::from(vec![0x00, 0x7F, 0x80, 0xFF]);
+
+        assert_eq!("007F80FF", data.to_base16());
+        assert_eq!(data[..], ByteArray::from_base16("007f80Ff").unwrap()[..]);
+        assert!(ByteArray::from_base16("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(
+            ByteArray::from_base16("00G0"),
+            Err(UberByteError::InvalidCharacter(2))
+        ));
+        assert!(matches!(
+            ByteArray::from_base16("000"),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+}
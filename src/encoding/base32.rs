@@ -0,0 +1,198 @@
+//! Base32 encoding with the RFC 4648 and Crockford alphabets
+//!
+//! ```rust
+//! use uberbyte::encoding::base32::Base32Alphabet;
+//! use uberbyte::ByteArray;
+//!
+//! let data = ByteArray::from(b"foobar".to_vec());
+//!
+//! assert_eq!("MZXW6YTBOI======", data.to_base32(Base32Alphabet::Rfc4648));
+//! assert_eq!("CSQPYRK1E8", data.to_base32(Base32Alphabet::Crockford));
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+const RFC_4648_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const PADDING: u8 = b'=';
+
+/// The supported Base32 alphabets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Base32Alphabet {
+    /// The alphabet of RFC 4648, padded with `=` to a multiple of 8 characters
+    #[default]
+    Rfc4648,
+    /// The alphabet of Douglas Crockford without padding
+    ///
+    /// Decoding ignores the case and hyphens and reads `I` and `L` as `1` and `O` as `0`
+    Crockford,
+}
+
+impl Base32Alphabet {
+    fn symbols(&self) -> &'static [u8; 32] {
+        match self {
+            Base32Alphabet::Rfc4648 => RFC_4648_ALPHABET,
+            Base32Alphabet::Crockford => CROCKFORD_ALPHABET,
+        }
+    }
+
+    fn value(&self, character: u8) -> Option<u8> {
+        let character = match self {
+            Base32Alphabet::Rfc4648 => character,
+            Base32Alphabet::Crockford => match character.to_ascii_uppercase() {
+                b'I' | b'L' => b'1',
+                b'O' => b'0',
+                other => other,
+            },
+        };
+
+        self.symbols()
+            .iter()
+            .position(|symbol| *symbol == character)
+            .map(|value| value as u8)
+    }
+}
+
+impl ByteArray {
+    /// Encodes the bytes as Base32 text
+    pub fn to_base32(&self, alphabet: Base32Alphabet) -> String {
+        let symbols = alphabet.symbols();
+        let mut text = String::new();
+        let mut buffer = 0u16;
+        let mut bits = 0;
+
+        for byte in &self[..] {
+            buffer = (buffer << 8) | byte.into_u8() as u16;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                text.push(symbols[(buffer >> bits) as usize & 0x1F] as char);
+            }
+        }
+        if bits > 0 {
+            text.push(symbols[(buffer << (5 - bits)) as usize & 0x1F] as char);
+        }
+
+        if alphabet == Base32Alphabet::Rfc4648 {
+            while !text.len().is_multiple_of(8) {
+                text.push(PADDING as char);
+            }
+        }
+
+        text
+    }
+
+    /// Decodes Base32 text
+    ///
+    /// # Remarks
+    ///
+    /// Missing padding of RFC 4648 text is accepted
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidCharacter_ error with the position of the first character outside the alphabet
+    ///
+    /// An _InvalidLength_ error if the last group has a number of characters no bytes encode to
+    pub fn from_base32(text: &str, alphabet: Base32Alphabet) -> Result<ByteArray, UberByteError> {
+        let characters = text.as_bytes();
+        let end = match alphabet {
+            Base32Alphabet::Rfc4648 => {
+                characters.len()
+                    - characters
+                        .iter()
+                        .rev()
+                        .take(6)
+                        .take_while(|character| **character == PADDING)
+                        .count()
+            }
+            Base32Alphabet::Crockford => characters.len(),
+        };
+
+        let mut decoded = ByteArray::default();
+        let mut buffer = 0u16;
+        let mut bits = 0;
+        let mut symbols = 0;
+
+        for (position, character) in characters[..end].iter().enumerate() {
+            if alphabet == Base32Alphabet::Crockford && *character == b'-' {
+                continue;
+            }
+
+            let value = alphabet
+                .value(*character)
+                .ok_or(UberByteError::InvalidCharacter(position))?;
+            buffer = (buffer << 5) | value as u16;
+            bits += 5;
+            symbols += 1;
+            if bits >= 8 {
+                bits -= 8;
+                decoded.add_mut(UberByte::from((buffer >> bits) as u8));
+            }
+        }
+
+        match symbols % 8 {
+            1 | 3 | 6 => Err(UberByteError::InvalidLength),
+            _ => Ok(decoded),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "MY======"),
+            ("fo", "MZXQ===="),
+            ("foo", "MZXW6==="),
+            ("foob", "MZXW6YQ="),
+            ("fooba", "MZXW6YTB"),
+            ("foobar", "MZXW6YTBOI======"),
+        ];
+
+        for (plain, encoded) in vectors {
+            let data = ByteArray::from(plain.as_bytes().to_vec());
+            assert_eq!(encoded, data.to_base32(Base32Alphabet::Rfc4648));
+            assert_eq!(
+                data[..],
+                ByteArray::from_base32(encoded, Base32Alphabet::Rfc4648).unwrap()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn crockford() {
+        let data = ByteArray::from(vec![0xFF, 0x00]);
+
+        let encoded = data.to_base32(Base32Alphabet::Crockford);
+
+        assert_eq!("ZW00", encoded);
+        assert_eq!(
+            data[..],
+            ByteArray::from_base32("zw-Oo", Base32Alphabet::Crockford).unwrap()[..]
+        );
+        assert_eq!(
+            ByteArray::from_base32("11", Base32Alphabet::Crockford).unwrap()[..],
+            ByteArray::from_base32("iL", Base32Alphabet::Crockford).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(
+            ByteArray::from_base32("MZXW1===", Base32Alphabet::Rfc4648),
+            Err(UberByteError::InvalidCharacter(4))
+        ));
+        assert!(matches!(
+            ByteArray::from_base32("MZX", Base32Alphabet::Rfc4648),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            ByteArray::from_base32("ZU", Base32Alphabet::Crockford),
+            Err(UberByteError::InvalidCharacter(1))
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+//! Base64 encoding with the standard and URL safe alphabets as defined in RFC 4648
+//!
+//! ```rust
+//! use uberbyte::encoding::base64::{self, Base64Config};
+//! use uberbyte::ByteArray;
+//!
+//! let data = ByteArray::from(b"uber".to_vec());
+//!
+//! assert_eq!("dWJlcg==", base64::encode(&data));
+//! assert_eq!("dWJlcg", data.to_base64(Base64Config::URL_SAFE_NO_PAD));
+//! assert_eq!(data[..], base64::decode("dWJlcg==").unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const PADDING: u8 = b'=';
+
+/// Selects the alphabet and the padding of the Base64 text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Base64Config {
+    /// Use `-` and `_` instead of `+` and `/`
+    pub url_safe: bool,
+    /// Pad the text with `=` to a multiple of 4 characters
+    pub padding: bool,
+}
+
+impl Base64Config {
+    /// The standard alphabet with padding
+    pub const STANDARD: Base64Config = Base64Config {
+        url_safe: false,
+        padding: true,
+    };
+
+    /// The standard alphabet without padding
+    pub const STANDARD_NO_PAD: Base64Config = Base64Config {
+        url_safe: false,
+        padding: false,
+    };
+
+    /// The URL and filename safe alphabet with padding
+    pub const URL_SAFE: Base64Config = Base64Config {
+        url_safe: true,
+        padding: true,
+    };
+
+    /// The URL and filename safe alphabet without padding
+    pub const URL_SAFE_NO_PAD: Base64Config = Base64Config {
+        url_safe: true,
+        padding: false,
+    };
+
+    fn alphabet(&self) -> &'static [u8; 64] {
+        match self.url_safe {
+            true => URL_SAFE_ALPHABET,
+            false => STANDARD_ALPHABET,
+        }
+    }
+}
+
+impl Default for Base64Config {
+    fn default() -> Self {
+        Base64Config::STANDARD
+    }
+}
+
+/// Encodes the bytes as padded Base64 text with the standard alphabet
+pub fn encode(data: &ByteArray) -> String {
+    data.to_base64(Base64Config::STANDARD)
+}
+
+/// Decodes padded Base64 text with the standard alphabet
+///
+/// # Returns
+///
+/// The same errors as [`ByteArray::from_base64`]
+pub fn decode(text: &str) -> Result<ByteArray, UberByteError> {
+    ByteArray::from_base64(text, Base64Config::STANDARD)
+}
+
+impl ByteArray {
+    /// Encodes the bytes as Base64 text
+    pub fn to_base64(&self, config: Base64Config) -> String {
+        let alphabet = config.alphabet();
+        let bytes = self.to_u8_vec();
+        let mut text = String::new();
+
+        for chunk in bytes.chunks(3) {
+            let block = chunk.iter().enumerate().fold(0u32, |block, (index, byte)| {
+                block | (*byte as u32) << (16 - index * 8)
+            });
+
+            for index in 0..=chunk.len() {
+                text.push(alphabet[(block >> (18 - index * 6)) as usize & 0x3F] as char);
+            }
+            if config.padding {
+                for _ in chunk.len()..3 {
+                    text.push(PADDING as char);
+                }
+            }
+        }
+
+        text
+    }
+
+    /// Decodes Base64 text
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidCharacter_ error with the position of the first character outside the alphabet
+    ///
+    /// An _InvalidLength_ error if the padded text is not a multiple of 4 characters long
+    /// or the text ends with a single character of a group
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::encoding::base64::Base64Config;
+    /// use uberbyte::{ByteArray, UberByteError};
+    ///
+    /// let data = ByteArray::from_base64("-_8", Base64Config::URL_SAFE_NO_PAD).unwrap();
+    ///
+    /// assert_eq!(0xFB, data[0].into_u8());
+    /// assert!(matches!(
+    ///     ByteArray::from_base64("-_8=", Base64Config::STANDARD),
+    ///     Err(UberByteError::InvalidCharacter(0))
+    /// ));
+    /// ```
+    pub fn from_base64(text: &str, config: Base64Config) -> Result<ByteArray, UberByteError> {
+        let characters = text.as_bytes();
+        let mut end = characters.len();
+        if config.padding {
+            if !end.is_multiple_of(4) {
+                return Err(UberByteError::InvalidLength);
+            }
+            end -= characters
+                .iter()
+                .rev()
+                .take(2)
+                .take_while(|character| **character == PADDING)
+                .count();
+        }
+        if end % 4 == 1 {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let alphabet = config.alphabet();
+        let mut decoded = ByteArray::default();
+        for (chunk_index, chunk) in characters[..end].chunks(4).enumerate() {
+            let mut block = 0u32;
+            for (index, character) in chunk.iter().enumerate() {
+                let value = alphabet
+                    .iter()
+                    .position(|symbol| symbol == character)
+                    .ok_or(UberByteError::InvalidCharacter(chunk_index * 4 + index))?;
+                block |= (value as u32) << (18 - index * 6);
+            }
+
+            for index in 0..chunk.len() - 1 {
+                decoded.add_mut(UberByte::from((block >> (16 - index * 8)) as u8));
+            }
+        }
+
+        Ok(decoded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rfc_4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (plain, encoded) in vectors {
+            let data = ByteArray::from(plain.as_bytes().to_vec());
+            assert_eq!(encoded, encode(&data));
+            assert_eq!(data[..], decode(encoded).unwrap()[..]);
+
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(unpadded, data.to_base64(Base64Config::STANDARD_NO_PAD));
+            assert_eq!(
+                data[..],
+                ByteArray::from_base64(unpadded, Base64Config::STANDARD_NO_PAD).unwrap()[..]
+            );
+        }
+    }
+
+    #[test]
+    fn url_safe() {
+        let data = ByteArray::from(vec![0xFB, 0xFF, 0xBF]);
+
+        assert_eq!("+/+/", data.to_base64(Base64Config::STANDARD));
+        assert_eq!("-_-_", data.to_base64(Base64Config::URL_SAFE));
+        assert_eq!(
+            data[..],
+            ByteArray::from_base64("-_-_", Base64Config::URL_SAFE).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(decode("Zm9"), Err(UberByteError::InvalidLength)));
+        assert!(matches!(
+            ByteArray::from_base64("Zm9vY", Base64Config::STANDARD_NO_PAD),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            decode("Zm9vY*=="),
+            Err(UberByteError::InvalidCharacter(5))
+        ));
+        assert!(matches!(
+            decode("Zm=v"),
+            Err(UberByteError::InvalidCharacter(2))
+        ));
+        assert!(matches!(
+            ByteArray::from_base64("Zg==", Base64Config::STANDARD_NO_PAD),
+            Err(UberByteError::InvalidCharacter(2))
+        ));
+    }
+}
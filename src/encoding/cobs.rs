@@ -0,0 +1,133 @@
+//! Consistent Overhead Byte Stuffing
+//!
+//! ```rust
+//! use uberbyte::encoding::cobs;
+//! use uberbyte::ByteArray;
+//!
+//! let frame = ByteArray::from(vec![0x11, 0x00, 0x22]);
+//!
+//! let encoded = cobs::encode(&frame);
+//!
+//! assert_eq!(4, encoded.len());
+//! assert_eq!(frame[..], cobs::decode(&encoded).unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// Encodes the bytes so the result contains no zero byte
+///
+/// # Remarks
+///
+/// The zero byte delimiting the frame is not added
+pub fn encode(data: &ByteArray) -> ByteArray {
+    let mut encoded = vec![0u8];
+    let mut code_index = 0;
+
+    for byte in &data[..] {
+        if encoded.len() - code_index == 0xFF {
+            encoded[code_index] = 0xFF;
+            code_index = encoded.len();
+            encoded.push(0);
+        }
+
+        match byte.into_u8() {
+            0 => {
+                encoded[code_index] = (encoded.len() - code_index) as u8;
+                code_index = encoded.len();
+                encoded.push(0);
+            }
+            value => encoded.push(value),
+        }
+    }
+    encoded[code_index] = (encoded.len() - code_index) as u8;
+
+    ByteArray::from(encoded)
+}
+
+/// Restores the bytes of a single encoded frame without its zero delimiter
+///
+/// # Returns
+///
+/// A _CodeViolation_ error with the index of a zero byte or a code pointing past the end
+pub fn decode(data: &ByteArray) -> Result<ByteArray, UberByteError> {
+    let bytes = data.to_u8_vec();
+    let mut decoded = ByteArray::default();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let code = bytes[index] as usize;
+        if code == 0 || index + code > bytes.len() {
+            return Err(UberByteError::CodeViolation(index));
+        }
+
+        for (offset, byte) in bytes.iter().enumerate().take(index + code).skip(index + 1) {
+            if *byte == 0 {
+                return Err(UberByteError::CodeViolation(offset));
+            }
+            decoded.add_mut(UberByte::from(*byte));
+        }
+
+        index += code;
+        if code != 0xFF && index < bytes.len() {
+            decoded.add_mut(UberByte::MIN);
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_vectors() {
+        let vectors: Vec<(Vec<u8>, Vec<u8>)> = vec![
+            (vec![], vec![0x01]),
+            (vec![0x00], vec![0x01, 0x01]),
+            (vec![0x00, 0x00], vec![0x01, 0x01, 0x01]),
+            (
+                vec![0x11, 0x22, 0x00, 0x33],
+                vec![0x03, 0x11, 0x22, 0x02, 0x33],
+            ),
+            (
+                vec![0x11, 0x00, 0x00, 0x00],
+                vec![0x02, 0x11, 0x01, 0x01, 0x01],
+            ),
+        ];
+
+        for (data, expected) in vectors {
+            let encoded = encode(&ByteArray::from(data.clone()));
+            assert_eq!(expected, encoded.to_u8_vec());
+            assert_eq!(data, decode(&encoded).unwrap().to_u8_vec());
+        }
+    }
+
+    #[test]
+    fn long_runs() {
+        let data: Vec<u8> = (1..=255).collect();
+
+        let encoded = encode(&ByteArray::from(data.clone()));
+
+        assert_eq!(257, encoded.len());
+        assert_eq!(0xFF, encoded[0].into_u8());
+        assert_eq!(0x02, encoded[255].into_u8());
+        assert_eq!(data, decode(&encoded).unwrap().to_u8_vec());
+
+        let full_block = encode(&ByteArray::from(data[..254].to_vec()));
+        assert_eq!(255, full_block.len());
+        assert_eq!(data[..254], decode(&full_block).unwrap().to_u8_vec()[..]);
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(
+            decode(&ByteArray::from(vec![0x03, 0x11, 0x00])),
+            Err(UberByteError::CodeViolation(2))
+        ));
+        assert!(matches!(
+            decode(&ByteArray::from(vec![0x05, 0x11])),
+            Err(UberByteError::CodeViolation(0))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+//! Serial Line Internet Protocol framing as defined in RFC 1055
+//!
+//! ```rust
+//! use uberbyte::encoding::slip;
+//! use uberbyte::ByteArray;
+//!
+//! let frame = ByteArray::from(vec![0x01, slip::END, 0x02]);
+//!
+//! let encoded = slip::encode(&frame);
+//!
+//! assert_eq!(5, encoded.len());
+//! assert_eq!(frame[..], slip::decode(&encoded).unwrap()[..]);
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// Marks the end of a frame
+pub const END: u8 = 0xC0;
+/// Starts an escape sequence
+pub const ESC: u8 = 0xDB;
+/// Follows _ESC_ to represent an _END_ data byte
+pub const ESC_END: u8 = 0xDC;
+/// Follows _ESC_ to represent an _ESC_ data byte
+pub const ESC_ESC: u8 = 0xDD;
+
+/// Escapes the bytes and terminates them with _END_
+pub fn encode(data: &ByteArray) -> ByteArray {
+    let mut encoded = vec![];
+    for byte in &data[..] {
+        match byte.into_u8() {
+            END => encoded.extend([ESC, ESC_END]),
+            ESC => encoded.extend([ESC, ESC_ESC]),
+            value => encoded.push(value),
+        }
+    }
+    encoded.push(END);
+
+    ByteArray::from(encoded)
+}
+
+/// Restores the bytes of a single frame
+///
+/// # Remarks
+///
+/// _END_ bytes at the start or the end of the frame are ignored
+///
+/// # Returns
+///
+/// A _CodeViolation_ error with the index of an invalid escape sequence or an _END_
+/// byte inside the frame
+pub fn decode(data: &ByteArray) -> Result<ByteArray, UberByteError> {
+    let bytes = data.to_u8_vec();
+    let start = bytes.iter().take_while(|byte| **byte == END).count();
+    let end = bytes.len()
+        - bytes[start..]
+            .iter()
+            .rev()
+            .take_while(|byte| **byte == END)
+            .count();
+
+    let mut decoded = ByteArray::default();
+    let mut index = start;
+    while index < end {
+        let value = match bytes[index] {
+            END => return Err(UberByteError::CodeViolation(index)),
+            ESC => {
+                index += 1;
+                match bytes.get(index).filter(|_| index < end) {
+                    Some(&ESC_END) => END,
+                    Some(&ESC_ESC) => ESC,
+                    _ => return Err(UberByteError::CodeViolation(index - 1)),
+                }
+            }
+            value => value,
+        };
+        decoded.add_mut(UberByte::from(value));
+        index += 1;
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_escapes() {
+        let data = ByteArray::from(vec![0x01, END, ESC, 0x02]);
+
+        let encoded = encode(&data);
+
+        assert_eq!(
+            vec![0x01, ESC, ESC_END, ESC, ESC_ESC, 0x02, END],
+            encoded.to_u8_vec()
+        );
+        assert_eq!(data.to_u8_vec(), decode(&encoded).unwrap().to_u8_vec());
+    }
+
+    #[test]
+    fn decode_surrounding_end() {
+        let data = ByteArray::from(vec![END, END, 0x01, END]);
+
+        assert_eq!(vec![0x01], decode(&data).unwrap().to_u8_vec());
+        assert!(decode(&ByteArray::from(vec![END])).unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_errors() {
+        assert!(matches!(
+            decode(&ByteArray::from(vec![0x01, ESC, 0x02])),
+            Err(UberByteError::CodeViolation(1))
+        ));
+        assert!(matches!(
+            decode(&ByteArray::from(vec![0x01, ESC, END])),
+            Err(UberByteError::CodeViolation(1))
+        ));
+        assert!(matches!(
+            decode(&ByteArray::from(vec![0x01, END, 0x02])),
+            Err(UberByteError::CodeViolation(1))
+        ));
+    }
+}
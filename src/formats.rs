@@ -0,0 +1,51 @@
+//! Readers and writers for firmware file formats
+
+pub mod ihex;
+pub mod srec;
+
+use crate::{memory_image::MemoryImage, ByteArray, UberByteError};
+
+/// Converts a string of hexadecimal digit pairs into bytes
+///
+/// # Returns
+///
+/// A _MalformedRecord_ error with the given line number if the digits are invalid
+pub(crate) fn parse_hex_bytes(digits: &str, line: usize) -> Result<Vec<u8>, UberByteError> {
+    if !digits.len().is_multiple_of(2) || !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(UberByteError::MalformedRecord(line));
+    }
+
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| {
+            u8::from_str_radix(&digits[index..index + 2], 16)
+                .map_err(|_| UberByteError::MalformedRecord(line))
+        })
+        .collect()
+}
+
+/// Adds the data of a record to the image without overwriting earlier records
+///
+/// # Returns
+///
+/// An _OverlappingRecord_ error with the given line number if an address already holds a byte
+///
+/// A _MalformedRecord_ error with the given line number if the data exceeds the address space
+pub(crate) fn insert_record(
+    image: &mut MemoryImage,
+    address: u32,
+    data: &[u8],
+    line: usize,
+) -> Result<(), UberByteError> {
+    image
+        .insert(address, &ByteArray::from(data))
+        .map_err(|error| match error {
+            UberByteError::AddressOverlap(_) => UberByteError::OverlappingRecord(line),
+            _ => UberByteError::MalformedRecord(line),
+        })
+}
+
+/// Formats the bytes as upper case hexadecimal digit pairs
+pub(crate) fn to_hex_digits(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
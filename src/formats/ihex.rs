@@ -0,0 +1,271 @@
+//! Intel HEX reader and writer
+//!
+//! ```rust
+//! use uberbyte::formats::ihex;
+//!
+//! let image = ihex::parse(
+//!     ":020000040800F2\n:0400000001020304F2\n:00000001FF\n",
+//! )
+//! .unwrap();
+//!
+//! let (address, bytes) = image.segments().next().unwrap();
+//! assert_eq!(0x0800_0000, address);
+//! assert_eq!(4, bytes.len());
+//! ```
+
+use crate::{memory_image::MemoryImage, UberByteError};
+
+use super::{insert_record, parse_hex_bytes, to_hex_digits};
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Parses Intel HEX text into a memory image
+///
+/// # Remarks
+///
+/// Empty lines are skipped, start address records are ignored and parsing stops at the
+/// end of file record
+///
+/// # Returns
+///
+/// A _MalformedRecord_ error with the line number of the first record that can not be read
+///
+/// A _RecordChecksumMismatch_ error with the line number of the first record with a wrong checksum
+///
+/// An _OverlappingRecord_ error with the line number of the first record that writes to an
+/// address already written by an earlier record
+pub fn parse(text: &str) -> Result<MemoryImage, UberByteError> {
+    let mut image = MemoryImage::new();
+    let mut base: u32 = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let digits = line
+            .strip_prefix(':')
+            .ok_or(UberByteError::MalformedRecord(line_number))?;
+        let bytes = parse_hex_bytes(digits, line_number)?;
+        if bytes.len() < 5 || bytes[0] as usize + 5 != bytes.len() {
+            return Err(UberByteError::MalformedRecord(line_number));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(UberByteError::RecordChecksumMismatch(line_number));
+        }
+
+        let address = u16::from_be_bytes([bytes[1], bytes[2]]) as u32;
+        let data = &bytes[4..bytes.len() - 1];
+        match (bytes[3], data.len()) {
+            (DATA, _) => insert_record(&mut image, base + address, data, line_number)?,
+            (END_OF_FILE, 0) => break,
+            (EXTENDED_SEGMENT_ADDRESS, 2) => {
+                base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 4
+            }
+            (EXTENDED_LINEAR_ADDRESS, 2) => {
+                base = (u16::from_be_bytes([data[0], data[1]]) as u32) << 16
+            }
+            (START_SEGMENT_ADDRESS | START_LINEAR_ADDRESS, 4) => {}
+            _ => return Err(UberByteError::MalformedRecord(line_number)),
+        }
+    }
+
+    Ok(image)
+}
+
+/// Writes the memory image as Intel HEX text
+///
+/// # Remarks
+///
+/// Extended linear address records are emitted whenever the upper 16 bits of the
+/// address change. Data records never cross a 64 KiB boundary.
+///
+/// # Returns
+///
+/// One record per line, ending with the end of file record
+///
+/// An _InvalidLength_ error if the record length is 0
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::formats::ihex;
+/// use uberbyte::memory_image::MemoryImage;
+/// use uberbyte::ByteArray;
+///
+/// let mut image = MemoryImage::new();
+/// image.write(0x0100, &ByteArray::from(vec![0x01, 0x02, 0x03])).unwrap();
+///
+/// assert_eq!(
+///     ":020100000102FA\n:0101020003F9\n:00000001FF\n",
+///     ihex::write(&image, 2).unwrap()
+/// );
+/// ```
+pub fn write(image: &MemoryImage, record_length: u8) -> Result<String, UberByteError> {
+    if record_length == 0 {
+        return Err(UberByteError::InvalidLength);
+    }
+
+    let mut text = String::new();
+    let mut upper: u16 = 0;
+
+    for (start, segment) in image.segments() {
+        let bytes = segment.to_u8_vec();
+        let mut offset = 0;
+
+        while offset < bytes.len() {
+            let address = start + offset as u32;
+            if (address >> 16) as u16 != upper {
+                upper = (address >> 16) as u16;
+                text.push_str(&record(EXTENDED_LINEAR_ADDRESS, 0, &upper.to_be_bytes()));
+            }
+
+            let page_left = 0x1_0000 - (address & 0xFFFF) as usize;
+            let length = (record_length as usize)
+                .min(bytes.len() - offset)
+                .min(page_left);
+            text.push_str(&record(
+                DATA,
+                address as u16,
+                &bytes[offset..offset + length],
+            ));
+            offset += length;
+        }
+    }
+
+    text.push_str(&record(END_OF_FILE, 0, &[]));
+    Ok(text)
+}
+
+fn record(kind: u8, address: u16, data: &[u8]) -> String {
+    let mut bytes = vec![data.len() as u8];
+    bytes.extend(address.to_be_bytes());
+    bytes.push(kind);
+    bytes.extend(data);
+
+    let sum = bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+    bytes.push(sum.wrapping_neg());
+
+    format!(":{}\n", to_hex_digits(&bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ByteArray;
+
+    #[test]
+    fn parse_data_records() {
+        let text = ":10010000214601360121470136007EFE09D2190140\n\
+                    :100110002146017E17C20001FF5F16002148011928\n\
+                    :00000001FF\n";
+
+        let image = parse(text).unwrap();
+
+        let (address, bytes) = image.segments().next().unwrap();
+        assert_eq!(1, image.len());
+        assert_eq!(0x0100, address);
+        assert_eq!(32, bytes.len());
+        assert_eq!(0x21, bytes[0].into_u8());
+        assert_eq!(0x19, bytes[31].into_u8());
+    }
+
+    #[test]
+    fn parse_extended_addresses() {
+        let text = ":020000021000EC\n:0100000011EE\n:020000040001F9\n:0100100022CD\n";
+
+        let image = parse(text).unwrap();
+
+        let addresses: Vec<u32> = image.segments().map(|(address, _)| address).collect();
+        assert_eq!(vec![0x0001_0000, 0x0001_0010], addresses);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            parse(":00000001FF\n").map(|image| image.len()),
+            Ok(0)
+        ));
+        assert!(matches!(
+            parse("\n:0100000011EF\n"),
+            Err(UberByteError::RecordChecksumMismatch(2))
+        ));
+        assert!(matches!(
+            parse(":0100000011EE\n0100000011EE\n"),
+            Err(UberByteError::MalformedRecord(2))
+        ));
+        assert!(matches!(
+            parse(":0200000011EE\n"),
+            Err(UberByteError::MalformedRecord(1))
+        ));
+        assert!(matches!(
+            parse(":01000006110E8\n"),
+            Err(UberByteError::MalformedRecord(1))
+        ));
+        assert!(matches!(
+            parse(":0100000011EE\n\n:0100000011EE\n"),
+            Err(UberByteError::OverlappingRecord(3))
+        ));
+    }
+
+    #[test]
+    fn write_crosses_64k_boundary() {
+        let mut image = MemoryImage::new();
+        image
+            .write(0xFFFF, &ByteArray::from(vec![0xAA, 0xBB]))
+            .unwrap();
+
+        let text = write(&image, 16).unwrap();
+
+        assert_eq!(
+            ":01FFFF00AA57\n:020000040001F9\n:01000000BB44\n:00000001FF\n",
+            text
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut image = MemoryImage::new();
+        let data: Vec<u8> = (0..=255).collect();
+        image.write(0x0800_0000, &ByteArray::from(data)).unwrap();
+        image
+            .write(0x0808_0000, &ByteArray::from(vec![0x42]))
+            .unwrap();
+
+        let parsed = parse(&write(&image, 32).unwrap()).unwrap();
+
+        let segments: Vec<(u32, usize)> = parsed
+            .segments()
+            .map(|(address, bytes)| (address, bytes.len()))
+            .collect();
+        assert_eq!(vec![(0x0800_0000, 256), (0x0808_0000, 1)], segments);
+        assert!(matches!(
+            write(&image, 0),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn parse_large_image() {
+        let mut image = MemoryImage::new();
+        let data: Vec<u8> = (0..256 * 1024)
+            .map(|index| (index * 7 % 251) as u8)
+            .collect();
+        image
+            .write(0x0800_0000, &ByteArray::from(data.clone()))
+            .unwrap();
+        let text = write(&image, 16).unwrap();
+
+        let parsed = parse(&text).unwrap();
+
+        assert_eq!(1, parsed.len());
+        assert_eq!(data, parsed.segments().next().unwrap().1.to_u8_vec());
+    }
+}
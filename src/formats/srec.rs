@@ -0,0 +1,237 @@
+//! Motorola S-record reader and writer
+//!
+//! ```rust
+//! use uberbyte::formats::srec;
+//!
+//! let image = srec::parse("S107010001020304ED\nS9030000FC\n").unwrap();
+//!
+//! let (address, bytes) = image.segments().next().unwrap();
+//! assert_eq!(0x0100, address);
+//! assert_eq!(4, bytes.len());
+//! ```
+
+use crate::{memory_image::MemoryImage, UberByteError};
+
+use super::{insert_record, parse_hex_bytes, to_hex_digits};
+
+/// Parses S-record text into a memory image
+///
+/// # Remarks
+///
+/// Empty lines are skipped, header and count records are ignored and parsing stops at
+/// the first termination record
+///
+/// # Returns
+///
+/// A _MalformedRecord_ error with the line number of the first record that can not be read
+///
+/// A _RecordChecksumMismatch_ error with the line number of the first record with a wrong checksum
+///
+/// An _OverlappingRecord_ error with the line number of the first record that writes to an
+/// address already written by an earlier record
+pub fn parse(text: &str) -> Result<MemoryImage, UberByteError> {
+    let mut image = MemoryImage::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let kind = line
+            .strip_prefix('S')
+            .and_then(|rest| rest.chars().next())
+            .and_then(|kind| kind.to_digit(10))
+            .ok_or(UberByteError::MalformedRecord(line_number))?;
+        let bytes = parse_hex_bytes(&line[2..], line_number)?;
+        let address_length = match kind {
+            0 | 1 | 5 | 9 => 2,
+            2 | 6 | 8 => 3,
+            3 | 7 => 4,
+            _ => return Err(UberByteError::MalformedRecord(line_number)),
+        };
+        if bytes.len() < address_length + 2 || bytes[0] as usize + 1 != bytes.len() {
+            return Err(UberByteError::MalformedRecord(line_number));
+        }
+        if checksum(&bytes[..bytes.len() - 1]) != bytes[bytes.len() - 1] {
+            return Err(UberByteError::RecordChecksumMismatch(line_number));
+        }
+
+        let address = bytes[1..=address_length]
+            .iter()
+            .fold(0u32, |address, byte| (address << 8) | *byte as u32);
+        let data = &bytes[address_length + 1..bytes.len() - 1];
+        match kind {
+            1..=3 => insert_record(&mut image, address, data, line_number)?,
+            7..=9 => break,
+            _ => {}
+        }
+    }
+
+    Ok(image)
+}
+
+/// Writes the memory image as S-record text
+///
+/// # Remarks
+///
+/// The smallest address width that fits all segments is used, so S1, S2 or S3 data
+/// records are written together with the matching termination record
+///
+/// # Returns
+///
+/// One record per line, starting with an empty header record
+///
+/// An _InvalidLength_ error if the record length is 0 or too big to fit into a record
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::formats::srec;
+/// use uberbyte::memory_image::MemoryImage;
+/// use uberbyte::ByteArray;
+///
+/// let mut image = MemoryImage::new();
+/// image.write(0x0100, &ByteArray::from(vec![0x01, 0x02, 0x03, 0x04])).unwrap();
+///
+/// assert_eq!(
+///     "S0030000FC\nS107010001020304ED\nS9030000FC\n",
+///     srec::write(&image, 16).unwrap()
+/// );
+/// ```
+pub fn write(image: &MemoryImage, record_length: u8) -> Result<String, UberByteError> {
+    let end = image
+        .segments()
+        .map(|(address, bytes)| address as u64 + bytes.len() as u64)
+        .max()
+        .unwrap_or_default();
+    let (address_length, data_kind, termination_kind) = match end {
+        0..=0x1_0000 => (2, 1, 9),
+        0x1_0001..=0x100_0000 => (3, 2, 8),
+        _ => (4, 3, 7),
+    };
+    if record_length == 0 || record_length as usize + address_length + 1 > u8::MAX as usize {
+        return Err(UberByteError::InvalidLength);
+    }
+
+    let mut text = record(0, 0, 2, &[]);
+    for (start, segment) in image.segments() {
+        let bytes = segment.to_u8_vec();
+        for (index, chunk) in bytes.chunks(record_length as usize).enumerate() {
+            let address = start + (index * record_length as usize) as u32;
+            text.push_str(&record(data_kind, address, address_length, chunk));
+        }
+    }
+    text.push_str(&record(termination_kind, 0, address_length, &[]));
+
+    Ok(text)
+}
+
+fn record(kind: u8, address: u32, address_length: usize, data: &[u8]) -> String {
+    let mut bytes = vec![(address_length + data.len() + 1) as u8];
+    bytes.extend(&address.to_be_bytes()[4 - address_length..]);
+    bytes.extend(data);
+    bytes.push(checksum(&bytes));
+
+    format!("S{}{}\n", kind, to_hex_digits(&bytes))
+}
+
+/// The ones' complement of the lowest byte of the sum of all bytes
+fn checksum(bytes: &[u8]) -> u8 {
+    !bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ByteArray;
+
+    #[test]
+    fn parse_records() {
+        let text = "S00F000068656C6C6F202020202000003C\n\
+                    S11F00007C0802A6900100049421FFF07C6C1B787C8C23783C6000003863000026\n\
+                    S11F001C4BFFFFE5398000007D83637880010014382100107C0803A64E800020E9\n\
+                    S111003848656C6C6F20776F726C642E0A0042\n\
+                    S5030003F9\n\
+                    S9030000FC\n";
+
+        let image = parse(text).unwrap();
+
+        let (address, bytes) = image.segments().next().unwrap();
+        assert_eq!(1, image.len());
+        assert_eq!(0, address);
+        assert_eq!(0x46, bytes.len());
+        assert_eq!(b'H', bytes[0x38].into_u8());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            parse("S0030000FC\nS107010001020304EE\n"),
+            Err(UberByteError::RecordChecksumMismatch(2))
+        ));
+        assert!(matches!(
+            parse("S4030000FC\n"),
+            Err(UberByteError::MalformedRecord(1))
+        ));
+        assert!(matches!(
+            parse("\n\nS1080100010203046D\n"),
+            Err(UberByteError::MalformedRecord(3))
+        ));
+        assert!(matches!(
+            parse(":0100000011EE\n"),
+            Err(UberByteError::MalformedRecord(1))
+        ));
+        assert!(matches!(
+            parse("S10501000102F6\nS104010103F6\n"),
+            Err(UberByteError::OverlappingRecord(2))
+        ));
+    }
+
+    #[test]
+    fn write_address_widths() {
+        let mut image = MemoryImage::new();
+        image
+            .write(0x01_0000, &ByteArray::from(vec![0xAA]))
+            .unwrap();
+
+        assert_eq!(
+            "S0030000FC\nS205010000AA4F\nS804000000FB\n",
+            write(&image, 16).unwrap()
+        );
+
+        image
+            .write(0x0800_0000, &ByteArray::from(vec![0xBB]))
+            .unwrap();
+        let text = write(&image, 16).unwrap();
+        assert!(text.contains("S30608000000BB"));
+        assert!(text.ends_with("S70500000000FA\n"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut image = MemoryImage::new();
+        let data: Vec<u8> = (0..=255).collect();
+        image.write(0x0800_0000, &ByteArray::from(data)).unwrap();
+        image
+            .write(0x0808_0000, &ByteArray::from(vec![0x42]))
+            .unwrap();
+
+        let parsed = parse(&write(&image, 32).unwrap()).unwrap();
+
+        let segments: Vec<(u32, usize)> = parsed
+            .segments()
+            .map(|(address, bytes)| (address, bytes.len()))
+            .collect();
+        assert_eq!(vec![(0x0800_0000, 256), (0x0808_0000, 1)], segments);
+        assert!(matches!(
+            write(&image, 0),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            write(&image, 251),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+}
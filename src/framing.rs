@@ -0,0 +1,123 @@
+//! Splitting byte streams into frames and composing frames out of their parts
+
+pub mod accumulator;
+pub mod builder;
+
+use crate::{Endianness, UberByte, UberByteError};
+
+/// The width and byte order of a length field inside a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthWidth {
+    /// The length is a single byte
+    #[default]
+    U8,
+    /// The length is made out of two bytes in the given order
+    U16(Endianness),
+    /// The length is made out of four bytes in the given order
+    U32(Endianness),
+}
+
+impl LengthWidth {
+    /// The number of bytes the length field occupies
+    pub fn size(&self) -> usize {
+        match self {
+            LengthWidth::U8 => 1,
+            LengthWidth::U16(_) => 2,
+            LengthWidth::U32(_) => 4,
+        }
+    }
+
+    /// The largest length the field can hold
+    pub fn max_value(&self) -> u32 {
+        match self {
+            LengthWidth::U8 => u8::MAX as u32,
+            LengthWidth::U16(_) => u16::MAX as u32,
+            LengthWidth::U32(_) => u32::MAX,
+        }
+    }
+
+    fn endianness(&self) -> Endianness {
+        match self {
+            LengthWidth::U8 => Endianness::Big,
+            LengthWidth::U16(endianness) | LengthWidth::U32(endianness) => *endianness,
+        }
+    }
+
+    /// Reads the length from the start of the given bytes
+    ///
+    /// # Returns
+    ///
+    /// None if there are not enough bytes for the field
+    pub fn read(&self, bytes: &[UberByte]) -> Option<u32> {
+        let bytes = bytes.get(..self.size())?;
+        let fold = |value: u32, byte: &UberByte| (value << 8) | byte.into_u8() as u32;
+
+        match self.endianness() {
+            Endianness::Big => Some(bytes.iter().fold(0, fold)),
+            Endianness::Little => Some(bytes.iter().rev().fold(0, fold)),
+        }
+    }
+
+    /// Encodes the length into the bytes of the field
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the length does not fit into the field
+    pub fn to_bytes(&self, length: usize) -> Result<Vec<u8>, UberByteError> {
+        let length = u32::try_from(length)
+            .ok()
+            .filter(|length| *length <= self.max_value())
+            .ok_or(UberByteError::ValueOverflow)?;
+
+        let bytes = &length.to_be_bytes()[4 - self.size()..];
+        match self.endianness() {
+            Endianness::Big => Ok(bytes.to_vec()),
+            Endianness::Little => Ok(bytes.iter().rev().copied().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_length() {
+        let bytes: Vec<UberByte> = [0x01, 0x02, 0x03, 0x04]
+            .into_iter()
+            .map(UberByte::from)
+            .collect();
+
+        assert_eq!(Some(0x01), LengthWidth::U8.read(&bytes));
+        assert_eq!(
+            Some(0x0201),
+            LengthWidth::U16(Endianness::Little).read(&bytes)
+        );
+        assert_eq!(
+            Some(0x01020304),
+            LengthWidth::U32(Endianness::Big).read(&bytes)
+        );
+        assert_eq!(None, LengthWidth::U32(Endianness::Big).read(&bytes[1..]));
+    }
+
+    #[test]
+    fn encode_length() {
+        assert_eq!(vec![0x12], LengthWidth::U8.to_bytes(0x12).unwrap());
+        assert_eq!(
+            vec![0x34, 0x12],
+            LengthWidth::U16(Endianness::Little)
+                .to_bytes(0x1234)
+                .unwrap()
+        );
+        assert_eq!(
+            vec![0x00, 0x01, 0x00, 0x00],
+            LengthWidth::U32(Endianness::Big)
+                .to_bytes(0x1_0000)
+                .unwrap()
+        );
+        assert!(matches!(
+            LengthWidth::U8.to_bytes(256),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+}
@@ -0,0 +1,522 @@
+//! Reassembling frames out of arbitrarily chunked reads
+//!
+//! A [`FrameAccumulator`] buffers the received bytes and uses a [`FrameSpec`] to find
+//! out where a frame starts and ends. Bytes that can not start a frame are dropped, so
+//! the accumulator resynchronises on its own after line noise.
+//!
+//! ```rust
+//! use uberbyte::framing::accumulator::{FrameAccumulator, LengthPrefixed, SyncWord};
+//! use uberbyte::framing::LengthWidth;
+//!
+//! let spec = SyncWord::new(&[0xAA, 0x55], LengthPrefixed::new(2, LengthWidth::U8));
+//! let mut accumulator = FrameAccumulator::new(spec);
+//!
+//! accumulator.push(&[0x13, 0xAA, 0x55, 0x02]);
+//! assert!(accumulator.next_frame().is_none());
+//!
+//! accumulator.push(&[0x01, 0x02, 0xAA]);
+//! let frame = accumulator.next_frame().unwrap();
+//!
+//! assert_eq!(5, frame.len());
+//! assert_eq!(1, accumulator.dropped_bytes());
+//! assert_eq!(1, accumulator.buffered());
+//! ```
+
+use std::io::Write;
+
+use crate::{ByteArray, UberByte};
+
+use super::LengthWidth;
+
+/// The result of checking the start of the buffered bytes for a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameCheck {
+    /// A complete frame of the given length starts at the first byte
+    Complete(usize),
+    /// More bytes are needed to decide
+    Incomplete,
+    /// The given number of bytes at the start can not belong to a frame and are dropped
+    Discard(usize),
+}
+
+/// Describes how frames are delimited inside a byte stream
+pub trait FrameSpec {
+    /// Checks if the buffered bytes start with a complete frame
+    ///
+    /// # Remarks
+    ///
+    /// The accumulator treats a complete frame of length 0 like a single discarded byte,
+    /// and always discards at least one byte
+    fn check(&self, buffer: &[UberByte]) -> FrameCheck;
+
+    /// Checks the buffered bytes like [`FrameSpec::check`], knowing that the first
+    /// `searched` bytes were already checked without finding a complete frame
+    ///
+    /// # Remarks
+    ///
+    /// The accumulator calls this so that specs which scan the buffer only look at the
+    /// newly received bytes. The default checks the whole buffer again.
+    fn check_from(&self, buffer: &[UberByte], searched: usize) -> FrameCheck {
+        let _ = searched;
+        self.check(buffer)
+    }
+}
+
+/// Every frame has the same length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedLength {
+    /// The number of bytes in a frame
+    pub length: usize,
+}
+
+impl FixedLength {
+    /// Creates a new specification for frames of the given length
+    pub fn new(length: usize) -> Self {
+        FixedLength { length }
+    }
+}
+
+impl FrameSpec for FixedLength {
+    fn check(&self, buffer: &[UberByte]) -> FrameCheck {
+        match buffer.len() >= self.length {
+            true => FrameCheck::Complete(self.length),
+            false => FrameCheck::Incomplete,
+        }
+    }
+}
+
+/// The frame contains a length field at a fixed offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthPrefixed {
+    /// The position of the length field counted from the start of the frame
+    pub offset: usize,
+    /// The width and byte order of the length field
+    pub width: LengthWidth,
+    /// Added to the length value to get the number of bytes following the length field
+    pub adjustment: isize,
+    /// Frames longer than this are treated as garbage
+    pub max_length: usize,
+}
+
+impl LengthPrefixed {
+    /// Creates a new specification where the length counts the bytes following the length field
+    pub fn new(offset: usize, width: LengthWidth) -> Self {
+        LengthPrefixed {
+            offset,
+            width,
+            adjustment: 0,
+            max_length: usize::MAX,
+        }
+    }
+
+    /// Sets the value added to the length, e.g. for a trailing checksum not counted by the length
+    pub fn with_adjustment(mut self, adjustment: isize) -> Self {
+        self.adjustment = adjustment;
+        self
+    }
+
+    /// Sets the length above which a frame is treated as garbage
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+}
+
+impl FrameSpec for LengthPrefixed {
+    fn check(&self, buffer: &[UberByte]) -> FrameCheck {
+        let Some(value) = buffer
+            .get(self.offset..)
+            .and_then(|field| self.width.read(field))
+        else {
+            return FrameCheck::Incomplete;
+        };
+
+        let header = self.offset + self.width.size();
+        let length = (header as i64)
+            .saturating_add(value as i64)
+            .saturating_add(self.adjustment as i64);
+        match usize::try_from(length) {
+            Ok(length) if length >= header && length <= self.max_length => {
+                match buffer.len() >= length {
+                    true => FrameCheck::Complete(length),
+                    false => FrameCheck::Incomplete,
+                }
+            }
+            _ => FrameCheck::Discard(1),
+        }
+    }
+}
+
+/// Every frame ends with the delimiter
+///
+/// # Remarks
+///
+/// The returned frames include the delimiter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimited {
+    /// The bytes marking the end of a frame
+    pub delimiter: Vec<u8>,
+    /// Frames longer than this are treated as garbage
+    pub max_length: usize,
+}
+
+impl Delimited {
+    /// Creates a new specification for frames ending with the given bytes
+    pub fn new(delimiter: &[u8]) -> Self {
+        Delimited {
+            delimiter: delimiter.to_vec(),
+            max_length: usize::MAX,
+        }
+    }
+
+    /// Sets the length above which a frame is treated as garbage
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+}
+
+impl FrameSpec for Delimited {
+    fn check(&self, buffer: &[UberByte]) -> FrameCheck {
+        self.check_from(buffer, 0)
+    }
+
+    fn check_from(&self, buffer: &[UberByte], searched: usize) -> FrameCheck {
+        if self.delimiter.is_empty() {
+            return FrameCheck::Discard(buffer.len());
+        }
+
+        // a delimiter may have been cut off at the end of the searched bytes
+        let from = searched
+            .saturating_sub(self.delimiter.len() - 1)
+            .min(buffer.len());
+        match buffer[from..]
+            .windows(self.delimiter.len())
+            .position(|window| window == &self.delimiter[..])
+            .map(|position| from + position)
+        {
+            Some(position) if position + self.delimiter.len() <= self.max_length => {
+                FrameCheck::Complete(position + self.delimiter.len())
+            }
+            Some(position) => FrameCheck::Discard(position + self.delimiter.len()),
+            None if buffer.len() >= self.max_length => {
+                FrameCheck::Discard((buffer.len() + 1).saturating_sub(self.delimiter.len()))
+            }
+            None => FrameCheck::Incomplete,
+        }
+    }
+}
+
+/// Every frame starts with a sync word followed by a length prefixed part
+///
+/// # Remarks
+///
+/// The offset of the length field is counted from the start of the sync word.
+/// When a frame is broken the bytes up to the next possible sync word are dropped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncWord {
+    /// The bytes every frame starts with
+    pub sync_word: Vec<u8>,
+    /// The length field following the sync word
+    pub length: LengthPrefixed,
+}
+
+impl SyncWord {
+    /// Creates a new specification for frames starting with the sync word
+    pub fn new(sync_word: &[u8], length: LengthPrefixed) -> Self {
+        SyncWord {
+            sync_word: sync_word.to_vec(),
+            length,
+        }
+    }
+
+    fn starts_with_sync_word(&self, bytes: &[UberByte]) -> bool {
+        let length = bytes.len().min(self.sync_word.len());
+        bytes[..length] == self.sync_word[..length]
+    }
+}
+
+impl FrameSpec for SyncWord {
+    fn check(&self, buffer: &[UberByte]) -> FrameCheck {
+        if !self.starts_with_sync_word(buffer) {
+            let next = (1..buffer.len())
+                .find(|start| self.starts_with_sync_word(&buffer[*start..]))
+                .unwrap_or(buffer.len());
+            return FrameCheck::Discard(next);
+        }
+        if buffer.len() < self.sync_word.len() {
+            return FrameCheck::Incomplete;
+        }
+
+        self.length.check(buffer)
+    }
+}
+
+/// Buffers received bytes and yields the complete frames found in them
+///
+/// # Remarks
+///
+/// Taken bytes are only skipped over and the buffer is compacted once they make up
+/// half of it, so taking many frames out of a large buffer stays linear
+#[derive(Debug, Clone)]
+pub struct FrameAccumulator<S> {
+    spec: S,
+    buffer: Vec<UberByte>,
+    start: usize,
+    searched: usize,
+    dropped_bytes: usize,
+    frame_count: usize,
+}
+
+impl<S: FrameSpec> FrameAccumulator<S> {
+    /// Creates a new empty accumulator for frames described by the spec
+    pub fn new(spec: S) -> Self {
+        FrameAccumulator {
+            spec,
+            buffer: vec![],
+            start: 0,
+            searched: 0,
+            dropped_bytes: 0,
+            frame_count: 0,
+        }
+    }
+
+    /// The specification used to find frames
+    pub fn spec(&self) -> &S {
+        &self.spec
+    }
+
+    /// Appends the received bytes to the buffer
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer
+            .extend(bytes.iter().map(|byte| UberByte::from(*byte)));
+    }
+
+    /// Appends the received byte array to the buffer
+    pub fn push_array(&mut self, bytes: &ByteArray) {
+        self.buffer.extend_from_slice(&bytes[..]);
+    }
+
+    /// Takes the next complete frame out of the buffer
+    ///
+    /// # Returns
+    ///
+    /// None if the buffered bytes do not contain a complete frame yet
+    pub fn next_frame(&mut self) -> Option<ByteArray> {
+        while self.buffered() > 0 {
+            let pending = &self.buffer[self.start..];
+            match self.spec.check_from(pending, self.searched) {
+                FrameCheck::Complete(length) if length > 0 && length <= pending.len() => {
+                    let frame = ByteArray::from(&pending[..length]);
+                    self.consume(length);
+                    self.frame_count += 1;
+                    return Some(frame);
+                }
+                FrameCheck::Complete(0) => self.discard(1),
+                FrameCheck::Complete(_) | FrameCheck::Incomplete => {
+                    self.searched = pending.len();
+                    return None;
+                }
+                FrameCheck::Discard(count) => self.discard(count),
+            }
+        }
+
+        None
+    }
+
+    /// Takes all complete frames out of the buffer
+    pub fn frames(&mut self) -> impl Iterator<Item = ByteArray> + '_ {
+        std::iter::from_fn(|| self.next_frame())
+    }
+
+    /// The number of bytes waiting for the rest of their frame
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// The number of bytes dropped because they did not belong to a frame
+    pub fn dropped_bytes(&self) -> usize {
+        self.dropped_bytes
+    }
+
+    /// The number of frames taken out of the accumulator
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Drops all buffered bytes, counting them as dropped
+    pub fn clear(&mut self) {
+        self.discard(self.buffered());
+    }
+
+    fn discard(&mut self, count: usize) {
+        let count = count.clamp(1, self.buffered());
+        self.consume(count);
+        self.dropped_bytes += count;
+    }
+
+    /// Skips the bytes at the start of the buffer
+    fn consume(&mut self, count: usize) {
+        self.start += count;
+        self.searched = 0;
+        if self.start * 2 >= self.buffer.len() {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+    }
+}
+
+impl<S: FrameSpec> Write for FrameAccumulator<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.push(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::Endianness;
+
+    /// Counts the bytes the wrapped spec is asked to search
+    struct Counting<S> {
+        spec: S,
+        unsearched: Cell<usize>,
+    }
+
+    impl<S: FrameSpec> FrameSpec for Counting<S> {
+        fn check(&self, buffer: &[UberByte]) -> FrameCheck {
+            self.check_from(buffer, 0)
+        }
+
+        fn check_from(&self, buffer: &[UberByte], searched: usize) -> FrameCheck {
+            self.unsearched
+                .set(self.unsearched.get() + buffer.len() - searched);
+            self.spec.check_from(buffer, searched)
+        }
+    }
+
+    #[test]
+    fn fixed_length_across_reads() {
+        let mut accumulator = FrameAccumulator::new(FixedLength::new(3));
+
+        accumulator.push(&[1, 2]);
+        assert!(accumulator.next_frame().is_none());
+        accumulator.push(&[3, 4, 5, 6, 7]);
+
+        let frames: Vec<Vec<u8>> = accumulator
+            .frames()
+            .map(|frame| frame.to_u8_vec())
+            .collect();
+        assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], frames);
+        assert_eq!(1, accumulator.buffered());
+        assert_eq!(2, accumulator.frame_count());
+    }
+
+    #[test]
+    fn length_prefixed_with_adjustment() {
+        let spec = LengthPrefixed::new(1, LengthWidth::U16(Endianness::Little))
+            .with_adjustment(2)
+            .with_max_length(16);
+        let mut accumulator = FrameAccumulator::new(spec);
+
+        accumulator.push(&[0x01, 0x02, 0x00, 0xAA, 0xBB, 0xC1, 0xC2]);
+        let frame = accumulator.next_frame().unwrap();
+
+        assert_eq!(
+            vec![0x01, 0x02, 0x00, 0xAA, 0xBB, 0xC1, 0xC2],
+            frame.to_u8_vec()
+        );
+        assert_eq!(0, accumulator.buffered());
+    }
+
+    #[test]
+    fn length_prefixed_drops_oversized_frames() {
+        let spec = LengthPrefixed::new(0, LengthWidth::U8).with_max_length(4);
+        let mut accumulator = FrameAccumulator::new(spec);
+
+        accumulator.push(&[0xFF, 0x7F, 0x02, 0x0A, 0x0B]);
+
+        assert_eq!(
+            vec![0x02, 0x0A, 0x0B],
+            accumulator.next_frame().unwrap().to_u8_vec()
+        );
+        assert_eq!(2, accumulator.dropped_bytes());
+    }
+
+    #[test]
+    fn delimited() {
+        let mut accumulator = FrameAccumulator::new(Delimited::new(b"\r\n").with_max_length(8));
+
+        write!(accumulator, "OK\r").unwrap();
+        assert!(accumulator.next_frame().is_none());
+        write!(accumulator, "\nthis line is too long\r\nAT\r\n").unwrap();
+
+        let frames: Vec<Vec<u8>> = accumulator
+            .frames()
+            .map(|frame| frame.to_u8_vec())
+            .collect();
+        assert_eq!(vec![b"OK\r\n".to_vec(), b"AT\r\n".to_vec()], frames);
+        assert_eq!(23, accumulator.dropped_bytes());
+    }
+
+    #[test]
+    fn sync_word_resynchronises() {
+        let spec = SyncWord::new(&[0xAA, 0x55], LengthPrefixed::new(2, LengthWidth::U8));
+        let mut accumulator = FrameAccumulator::new(spec);
+
+        accumulator.push(&[0x00, 0xAA, 0x00, 0xAA]);
+        assert!(accumulator.next_frame().is_none());
+        assert_eq!(3, accumulator.dropped_bytes());
+        assert_eq!(1, accumulator.buffered());
+
+        accumulator.push(&[0x55, 0x01, 0x42, 0xAA, 0x55]);
+        assert_eq!(
+            vec![0xAA, 0x55, 0x01, 0x42],
+            accumulator.next_frame().unwrap().to_u8_vec()
+        );
+        assert_eq!(2, accumulator.buffered());
+
+        accumulator.clear();
+        assert_eq!(5, accumulator.dropped_bytes());
+        assert_eq!(0, accumulator.buffered());
+    }
+
+    #[test]
+    fn many_frames_in_one_read() {
+        let mut accumulator = FrameAccumulator::new(FixedLength::new(4));
+        accumulator.push(&vec![0x42; 4 * 100_000]);
+
+        let count = accumulator.frames().count();
+
+        assert_eq!(100_000, count);
+        assert_eq!(0, accumulator.buffered());
+    }
+
+    #[test]
+    fn long_delimited_frame_byte_by_byte() {
+        let mut accumulator = FrameAccumulator::new(Counting {
+            spec: Delimited::new(b"\r\n"),
+            unsearched: Cell::new(0),
+        });
+
+        for _ in 0..100_000 {
+            accumulator.push(b"x");
+            assert!(accumulator.next_frame().is_none());
+        }
+        accumulator.push(b"\r");
+        assert!(accumulator.next_frame().is_none());
+        accumulator.push(b"\nAT\r\n");
+        let frames: Vec<usize> = accumulator.frames().map(|frame| frame.len()).collect();
+
+        assert_eq!(vec![100_002, 4], frames);
+        // every byte is searched once, the bytes behind the first frame once more
+        assert_eq!(100_006 + 4, accumulator.spec().unsearched.get());
+    }
+}
@@ -0,0 +1,373 @@
+//! Composing frames whose length and checksum fields are only known at the end
+//!
+//! A [`FrameBuilder`] collects the parts of a frame. Length and checksum fields are
+//! reserved where they belong and filled in by [`FrameBuilder::finish`] once all the
+//! bytes they cover are known.
+//!
+//! ```rust
+//! use uberbyte::framing::builder::{Checksum, FrameBuilder};
+//! use uberbyte::framing::LengthWidth;
+//!
+//! let mut builder = FrameBuilder::new();
+//! builder.add_bytes(&[0x7E]);
+//! let start = builder.mark();
+//! builder.reserve_length(LengthWidth::U8);
+//! builder.add_bytes(&[0x01, 0x02, 0x03]);
+//! let end = builder.mark();
+//! let checksum = builder.reserve_checksum(Checksum::Xor8);
+//! builder.cover(checksum, start, end).unwrap();
+//!
+//! let frame = builder.finish().unwrap();
+//!
+//! assert_eq!([0x7E, 0x04, 0x01, 0x02, 0x03, 0x04][..], frame[..]);
+//! ```
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::crc::Crc;
+use crate::{ByteArray, Endianness, UberByteError};
+
+use super::LengthWidth;
+
+/// The algorithms available for checksum fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Checksum {
+    /// A cyclic redundancy check stored with the given byte order
+    Crc(Crc, Endianness),
+    /// The lowest byte of the sum of all bytes
+    Sum8,
+    /// All bytes XORed together
+    Xor8,
+}
+
+impl Checksum {
+    /// The number of bytes the checksum field occupies
+    pub fn size(&self) -> usize {
+        match self {
+            Checksum::Crc(crc, _) => (crc.width() as usize).div_ceil(8),
+            Checksum::Sum8 | Checksum::Xor8 => 1,
+        }
+    }
+
+    /// Calculates the bytes of the checksum field for the given data
+    pub fn calculate(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Checksum::Crc(crc, endianness) => {
+                let checksum = crc.checksum(&ByteArray::from(data)).to_be_bytes();
+                let bytes = &checksum[4 - self.size()..];
+                match endianness {
+                    Endianness::Big => bytes.to_vec(),
+                    Endianness::Little => bytes.iter().rev().copied().collect(),
+                }
+            }
+            Checksum::Sum8 => vec![data.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))],
+            Checksum::Xor8 => vec![data.iter().fold(0u8, |xor, byte| xor ^ byte)],
+        }
+    }
+}
+
+/// Hands out a distinct id to every builder, so marks and placeholders remember their builder
+static NEXT_BUILDER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A position between two parts of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    builder: usize,
+    index: usize,
+}
+
+/// A reserved length or checksum field of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder {
+    builder: usize,
+    index: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Bytes(Vec<u8>),
+    Length {
+        width: LengthWidth,
+        range: Option<(Mark, Mark)>,
+    },
+    Checksum {
+        checksum: Checksum,
+        range: Option<(Mark, Mark)>,
+    },
+    Nested(FrameBuilder),
+}
+
+/// Collects the parts of a frame and fills in the reserved fields when finished
+///
+/// # Remarks
+///
+/// A clone accepts the marks and placeholders of the builder it was cloned from
+#[derive(Debug, Clone)]
+pub struct FrameBuilder {
+    id: usize,
+    parts: Vec<Part>,
+}
+
+impl Default for FrameBuilder {
+    fn default() -> Self {
+        FrameBuilder {
+            id: NEXT_BUILDER_ID.fetch_add(1, Ordering::Relaxed),
+            parts: vec![],
+        }
+    }
+}
+
+impl FrameBuilder {
+    /// Creates a new empty builder
+    pub fn new() -> Self {
+        FrameBuilder::default()
+    }
+
+    /// Appends the bytes to the frame
+    pub fn add(&mut self, bytes: &ByteArray) {
+        self.parts.push(Part::Bytes(bytes.to_u8_vec()));
+    }
+
+    /// Appends the raw bytes to the frame
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        self.parts.push(Part::Bytes(bytes.to_vec()));
+    }
+
+    /// Appends a frame that is finished together with this one
+    ///
+    /// # Remarks
+    ///
+    /// The reserved fields of the nested frame only refer to its own bytes
+    pub fn add_nested(&mut self, nested: FrameBuilder) {
+        self.parts.push(Part::Nested(nested));
+    }
+
+    /// Marks the current end of the frame as the start or end of a covered range
+    pub fn mark(&self) -> Mark {
+        Mark {
+            builder: self.id,
+            index: self.parts.len(),
+        }
+    }
+
+    /// Reserves a length field
+    ///
+    /// # Remarks
+    ///
+    /// Unless changed with [`FrameBuilder::cover`] the length counts all bytes following the field
+    pub fn reserve_length(&mut self, width: LengthWidth) -> Placeholder {
+        self.parts.push(Part::Length { width, range: None });
+        self.placeholder()
+    }
+
+    /// Reserves a checksum field
+    ///
+    /// # Remarks
+    ///
+    /// Unless changed with [`FrameBuilder::cover`] the checksum covers all bytes preceding the field
+    pub fn reserve_checksum(&mut self, checksum: Checksum) -> Placeholder {
+        self.parts.push(Part::Checksum {
+            checksum,
+            range: None,
+        });
+        self.placeholder()
+    }
+
+    /// Sets the range of bytes a reserved field counts or checks
+    ///
+    /// # Returns
+    ///
+    /// An _IndexOutOfRange_ error if the placeholder was not reserved by this builder
+    /// or one of the marks was not made by it
+    pub fn cover(
+        &mut self,
+        placeholder: Placeholder,
+        start: Mark,
+        end: Mark,
+    ) -> Result<(), UberByteError> {
+        if [placeholder.builder, start.builder, end.builder]
+            .iter()
+            .any(|builder| *builder != self.id)
+        {
+            return Err(UberByteError::IndexOutOfRange);
+        }
+
+        match self.parts.get_mut(placeholder.index) {
+            Some(Part::Length { range, .. }) | Some(Part::Checksum { range, .. }) => {
+                *range = Some((start, end));
+                Ok(())
+            }
+            _ => Err(UberByteError::IndexOutOfRange),
+        }
+    }
+
+    /// Assembles the frame and fills in all reserved fields
+    ///
+    /// # Remarks
+    ///
+    /// Lengths are filled in first, then the checksums in the order they were reserved
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if a length does not fit into its field
+    ///
+    /// An _IndexOutOfRange_ error if a covered range ends before it starts
+    pub fn finish(self) -> Result<ByteArray, UberByteError> {
+        self.assemble().map(ByteArray::from)
+    }
+
+    fn placeholder(&self) -> Placeholder {
+        Placeholder {
+            builder: self.id,
+            index: self.parts.len() - 1,
+        }
+    }
+
+    fn assemble(&self) -> Result<Vec<u8>, UberByteError> {
+        let mut bytes: Vec<u8> = vec![];
+        let mut offsets = Vec::with_capacity(self.parts.len() + 1);
+
+        for part in &self.parts {
+            offsets.push(bytes.len());
+            match part {
+                Part::Bytes(data) => bytes.extend(data),
+                Part::Length { width, .. } => bytes.resize(bytes.len() + width.size(), 0),
+                Part::Checksum { checksum, .. } => bytes.resize(bytes.len() + checksum.size(), 0),
+                Part::Nested(nested) => bytes.extend(nested.assemble()?),
+            }
+        }
+        offsets.push(bytes.len());
+
+        let resolve = |range: Option<(Mark, Mark)>, default: Range<usize>| match range {
+            Some((start, end)) => match (offsets.get(start.index), offsets.get(end.index)) {
+                (Some(start), Some(end)) if start <= end => Ok(*start..*end),
+                _ => Err(UberByteError::IndexOutOfRange),
+            },
+            None => Ok(default),
+        };
+
+        for (index, part) in self.parts.iter().enumerate() {
+            if let Part::Length { width, range } = part {
+                let covered = resolve(*range, offsets[index + 1]..bytes.len())?;
+                let field = width.to_bytes(covered.len())?;
+                bytes[offsets[index]..offsets[index + 1]].copy_from_slice(&field);
+            }
+        }
+
+        for (index, part) in self.parts.iter().enumerate() {
+            if let Part::Checksum { checksum, range } = part {
+                let covered = resolve(*range, 0..offsets[index])?;
+                let field = checksum.calculate(&bytes[covered]);
+                bytes[offsets[index]..offsets[index + 1]].copy_from_slice(&field);
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocols::modbus::ModbusFrame;
+
+    #[test]
+    fn default_ranges() {
+        let mut builder = FrameBuilder::new();
+        builder.reserve_length(LengthWidth::U16(Endianness::Big));
+        builder.add_bytes(&[0x10, 0x20]);
+        builder.reserve_checksum(Checksum::Sum8);
+
+        let frame = builder.finish().unwrap();
+
+        assert_eq!(vec![0x00, 0x03, 0x10, 0x20, 0x33], frame.to_u8_vec());
+    }
+
+    #[test]
+    fn crc_matches_modbus_rtu() {
+        let mut builder = FrameBuilder::new();
+        builder.add_bytes(&[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03]);
+        builder.reserve_checksum(Checksum::Crc(Crc::CRC_16_MODBUS, Endianness::Little));
+
+        let frame = builder.finish().unwrap();
+
+        let expected = ModbusFrame::read_holding_registers(0x11, 0x006B, 3)
+            .unwrap()
+            .to_rtu();
+        assert_eq!(expected.to_u8_vec(), frame.to_u8_vec());
+    }
+
+    #[test]
+    fn narrow_crc() {
+        let crc_5_usb = Crc::new(5, 0x05)
+            .unwrap()
+            .with_init(0x1F)
+            .with_reflection(true, true)
+            .with_xor_out(0x1F);
+        let mut builder = FrameBuilder::new();
+        builder.add_bytes(b"123456789");
+        builder.reserve_checksum(Checksum::Crc(crc_5_usb, Endianness::Big));
+
+        let frame = builder.finish().unwrap();
+
+        assert_eq!(10, frame.len());
+        assert_eq!(0x19, frame[9].into_u8());
+    }
+
+    #[test]
+    fn nested_frames() {
+        let mut inner = FrameBuilder::new();
+        inner.add_bytes(&[0x01]);
+        inner.reserve_length(LengthWidth::U8);
+        inner.add_bytes(&[0xAA, 0xBB]);
+
+        let mut outer = FrameBuilder::new();
+        let length = outer.reserve_length(LengthWidth::U16(Endianness::Little));
+        let start = outer.mark();
+        outer.add_nested(inner);
+        let end = outer.mark();
+        outer.add_bytes(&[0xFF]);
+        outer.cover(length, start, end).unwrap();
+
+        let frame = outer.finish().unwrap();
+
+        assert_eq!(
+            vec![0x04, 0x00, 0x01, 0x02, 0xAA, 0xBB, 0xFF],
+            frame.to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut builder = FrameBuilder::new();
+        builder.reserve_length(LengthWidth::U8);
+        builder.add_bytes(&[0; 256]);
+        assert!(matches!(
+            builder.finish(),
+            Err(UberByteError::ValueOverflow)
+        ));
+
+        let mut builder = FrameBuilder::new();
+        let mut nested = FrameBuilder::new();
+        let foreign = nested.reserve_length(LengthWidth::U8);
+        let start = builder.mark();
+        builder.add_bytes(&[0x01]);
+        let end = builder.mark();
+        assert!(matches!(
+            builder.cover(foreign, start, end),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+        assert!(matches!(
+            nested.cover(foreign, start, end),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+
+        let checksum = builder.reserve_checksum(Checksum::Xor8);
+        builder.cover(checksum, end, start).unwrap();
+        assert!(matches!(
+            builder.finish(),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+//! Arithmetic in the Galois field GF(2^8)
+//!
+//! The field is generated by the primitive polynomial
+//! x^8 + x^4 + x^3 + x^2 + 1 (`0x11D`) with 2 as the generator.
+//!
+//! ```rust
+//! use uberbyte::gf256::Gf256;
+//!
+//! let a = Gf256::from(0x53);
+//! let b = Gf256::from(0xCA);
+//!
+//! assert_eq!(Gf256::ONE, a * a.inverse().unwrap());
+//! assert_eq!(a, (a * b) / b);
+//! ```
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::UberByte;
+
+/// The primitive polynomial used to reduce products
+const PRIMITIVE_POLYNOMIAL: u16 = 0x11D;
+
+const fn exp_table() -> [u8; 512] {
+    let mut table = [0u8; 512];
+    let mut value: u16 = 1;
+    let mut index = 0;
+
+    while index < 255 {
+        table[index] = value as u8;
+        table[index + 255] = value as u8;
+        value <<= 1;
+        if value & 0x100 != 0 {
+            value ^= PRIMITIVE_POLYNOMIAL;
+        }
+        index += 1;
+    }
+
+    table
+}
+
+const fn log_table(exp: &[u8; 512]) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut index = 0;
+
+    while index < 255 {
+        table[exp[index] as usize] = index as u8;
+        index += 1;
+    }
+
+    table
+}
+
+/// Powers of the generator, duplicated so that the sum of two logarithms can be looked up directly
+const EXP: [u8; 512] = exp_table();
+/// Discrete logarithms of all non zero field elements
+const LOG: [u8; 256] = log_table(&EXP);
+
+/// A element of GF(256) backed by a _UberByte_
+///
+/// Addition and subtraction are both a XOR of the two bytes, multiplication and
+/// division are carried out using logarithm tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Gf256 {
+    value: UberByte,
+}
+
+impl Gf256 {
+    /// The additive identity
+    pub const ZERO: Gf256 = Gf256 {
+        value: UberByte::MIN,
+    };
+
+    /// The multiplicative identity
+    pub const ONE: Gf256 = Gf256::from_u8(1);
+
+    /// The generator of the multiplicative group
+    pub const GENERATOR: Gf256 = Gf256::from_u8(2);
+
+    const fn from_u8(value: u8) -> Gf256 {
+        Gf256 {
+            value: UberByte::from_u8(value),
+        }
+    }
+
+    /// Returns the generator raised to the given power
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::gf256::Gf256;
+    ///
+    /// assert_eq!(Gf256::from(8), Gf256::exp(3));
+    /// ```
+    pub fn exp(power: usize) -> Gf256 {
+        Gf256::from(EXP[power % 255])
+    }
+
+    /// Returns the discrete logarithm of the element
+    ///
+    /// # Returns
+    ///
+    /// The power to which the generator has to be raised to get this element.
+    /// None if the element is zero.
+    pub fn log(&self) -> Option<usize> {
+        match self.is_zero() {
+            true => None,
+            false => Some(LOG[self.into_u8() as usize] as usize),
+        }
+    }
+
+    /// Raises the element to the given power
+    pub fn pow(&self, power: usize) -> Gf256 {
+        match self.log() {
+            Some(log) => Gf256::exp((log * (power % 255)) % 255),
+            None if power == 0 => Gf256::ONE,
+            None => Gf256::ZERO,
+        }
+    }
+
+    /// Returns the multiplicative inverse of the element
+    ///
+    /// # Returns
+    ///
+    /// None if the element is zero, because zero has no inverse
+    pub fn inverse(&self) -> Option<Gf256> {
+        self.log().map(|log| Gf256::exp(255 - log))
+    }
+
+    /// Determines if this is the additive identity
+    pub fn is_zero(&self) -> bool {
+        self.value == UberByte::MIN
+    }
+
+    /// Gets the element as a u8
+    pub fn into_u8(&self) -> u8 {
+        self.value.into_u8()
+    }
+}
+
+impl From<u8> for Gf256 {
+    fn from(value: u8) -> Self {
+        Gf256::from_u8(value)
+    }
+}
+
+impl From<UberByte> for Gf256 {
+    fn from(value: UberByte) -> Self {
+        Gf256 { value }
+    }
+}
+
+impl From<Gf256> for UberByte {
+    fn from(value: Gf256) -> Self {
+        value.value
+    }
+}
+
+impl Add for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Gf256 {
+            value: self.value ^ rhs.value,
+        }
+    }
+}
+
+impl AddAssign for Gf256 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + rhs
+    }
+}
+
+impl SubAssign for Gf256 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self::Output {
+        match (self.log(), rhs.log()) {
+            (Some(lhs), Some(rhs)) => Gf256::from(EXP[lhs + rhs]),
+            _ => Gf256::ZERO,
+        }
+    }
+}
+
+impl MulAssign for Gf256 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Gf256 {
+    type Output = Self;
+
+    /// Divides the element by the given divisor
+    ///
+    /// # Panics
+    ///
+    /// Like the integer division this panics if the divisor is zero
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        match rhs.inverse() {
+            Some(inverse) => self * inverse,
+            None => panic!("attempt to divide by zero"),
+        }
+    }
+}
+
+impl DivAssign for Gf256 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Carry-less multiplication reduced by the primitive polynomial
+    fn slow_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        while b > 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+            let carry = a & 0x80 != 0;
+            a <<= 1;
+            if carry {
+                a ^= (PRIMITIVE_POLYNOMIAL & 0xFF) as u8;
+            }
+            b >>= 1;
+        }
+        product
+    }
+
+    #[test]
+    fn add_is_xor() {
+        assert_eq!(
+            Gf256::from(0b_0101_0011),
+            Gf256::from(0b_1100_1010) + Gf256::from(0b_1001_1001)
+        );
+        assert_eq!(Gf256::ZERO, Gf256::from(42) - Gf256::from(42));
+    }
+
+    #[test]
+    fn mul_matches_carry_less_multiplication() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(Gf256::from(slow_mul(a, b)), Gf256::from(a) * Gf256::from(b));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        assert!(Gf256::ZERO.inverse().is_none());
+
+        for a in 1..=255u8 {
+            let element = Gf256::from(a);
+            assert_eq!(Gf256::ONE, element * element.inverse().unwrap());
+        }
+    }
+
+    #[test]
+    fn div() {
+        let a = Gf256::from(0x53);
+        let b = Gf256::from(0xCA);
+
+        assert_eq!(a, (a * b) / b);
+        assert_eq!(Gf256::ZERO, Gf256::ZERO / b);
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let _result = Gf256::ONE / Gf256::ZERO;
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(Gf256::ONE, Gf256::from(7).pow(0));
+        assert_eq!(Gf256::ONE, Gf256::ZERO.pow(0));
+        assert_eq!(Gf256::ZERO, Gf256::ZERO.pow(3));
+        assert_eq!(Gf256::from(7) * Gf256::from(7), Gf256::from(7).pow(2));
+        assert_eq!(Gf256::ONE, Gf256::GENERATOR.pow(255));
+    }
+
+    #[test]
+    fn uberbyte_conversion() {
+        let byte = UberByte::from(0xA5);
+
+        assert_eq!(byte, UberByte::from(Gf256::from(byte)));
+    }
+}
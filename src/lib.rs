@@ -3,14 +3,14 @@
 //! A easy to use utility for bit manipulation
 //!
 //! ```rust
-//! use uberbyte::UberByte;
+//! use uberbyte::{BitOrder, UberByte};
 //!
 //! fn main() {
 //!     let my_byte: UberByte = UberByte::from(42);
 //!
 //!     println!("{:b}", my_byte);
-//!     for index in 0..7 {
-//!         if my_byte.is_bit_set(index) {
+//!     for (index, is_set) in my_byte.bits(BitOrder::LsbFirst).enumerate() {
+//!         if is_set {
 //!             println!("Bit on position {} is set", index);
 //!         } else {
 //!             println!("Bit on position {} is not set", index);
@@ -23,9 +23,33 @@
 //!
 //! Additional resources are available at (GitHub project page)[https://github.com/dejanfajfar/uberbyte.rs]
 
+pub mod bit_array;
+pub mod bit_diagram;
 pub mod byte_array;
+pub mod byte_queue;
+pub mod byte_ring;
+pub mod compression;
+pub mod crc;
+pub mod diff;
+pub mod ecc;
+pub mod encoding;
+pub mod formats;
+pub mod framing;
+pub mod gf256;
+pub mod line_coding;
+pub mod memory_image;
+pub mod parity;
+pub mod protocols;
+pub mod reed_solomon;
+pub mod register_map;
+#[cfg(feature = "serde")]
+pub mod serialization;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod tlv;
 pub mod uberbyte;
 
+pub use bit_array::*;
 pub use byte_array::*;
 pub use uberbyte::*;
 
@@ -99,4 +123,41 @@ pub enum UberByteError {
     ValueUnderflow,
     /// The desired index is exceeding the length of the array
     IndexOutOfRange,
+    /// The tag starting at the given offset could not be decoded
+    MalformedTag(usize),
+    /// The length field starting at the given offset could not be decoded
+    /// or points past the end of the data
+    MalformedLength(usize),
+    /// The length of the provided data does not fit the expected format
+    InvalidLength,
+    /// More errors than can be corrected were detected in the codeword with the given index
+    UncorrectableError(usize),
+    /// The parity check failed for the byte with the given index
+    ParityError(usize),
+    /// The symbol at the given index can not be produced by the line code
+    CodeViolation(usize),
+    /// The checksum stored in the data does not match the calculated checksum
+    ChecksumMismatch,
+    /// The character at the given position is not allowed in the parsed text
+    InvalidCharacter(usize),
+    /// The given address is already occupied
+    AddressOverlap(u32),
+    /// Nothing is mapped at the given address
+    UnknownAddress(u32),
+    /// The record on the given line number is malformed
+    MalformedRecord(usize),
+    /// The checksum of the record on the given line number does not match its content
+    RecordChecksumMismatch(usize),
+    /// The record on the given line number writes to an address that already holds a byte
+    OverlappingRecord(usize),
+}
+
+/// Defines the order in which the bytes of a multi byte value are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    /// The most significant byte is stored first
+    #[default]
+    Big,
+    /// The least significant byte is stored first
+    Little,
 }
@@ -0,0 +1,625 @@
+//! Conversion between data bytes and line coded bit streams
+//!
+//! Every encoder turns a [`ByteArray`] into the [`BitArray`] of symbols as they
+//! appear on the wire, the first symbol being the first one sent. The decoders
+//! do the reverse and report symbols that can not be produced by the encoding
+//! as a _CodeViolation_ holding the index of the offending symbol.
+//!
+//! ```rust
+//! use uberbyte::line_coding::{decode_manchester, encode_manchester, ManchesterConvention};
+//! use uberbyte::{BitOrder, ByteArray};
+//!
+//! let data = ByteArray::from(vec![0xA5]);
+//! let line = encode_manchester(&data, ManchesterConvention::Ieee, BitOrder::LsbFirst);
+//!
+//! assert_eq!(16, line.len());
+//! let decoded = decode_manchester(&line, ManchesterConvention::Ieee, BitOrder::LsbFirst).unwrap();
+//! assert_eq!(data[..], decoded[..]);
+//! ```
+
+use crate::{BitArray, BitOrder, ByteArray, UberByte, UberByteError};
+
+/// The two conventions for the meaning of a Manchester transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManchesterConvention {
+    /// IEEE 802.3: a 0 is a high to low transition, a 1 a low to high transition
+    Ieee,
+    /// G.E. Thomas: a 0 is a low to high transition, a 1 a high to low transition
+    Thomas,
+}
+
+impl ManchesterConvention {
+    fn symbols(&self, bit: bool) -> (bool, bool) {
+        match (self, bit) {
+            (ManchesterConvention::Ieee, false) | (ManchesterConvention::Thomas, true) => {
+                (true, false)
+            }
+            _ => (false, true),
+        }
+    }
+}
+
+/// Encodes the data with the Manchester code, two symbols per data bit
+pub fn encode_manchester(
+    data: &ByteArray,
+    convention: ManchesterConvention,
+    order: BitOrder,
+) -> BitArray {
+    let mut line = BitArray::default();
+
+    for bit in BitArray::from_byte_array(data, order) {
+        let (first, second) = convention.symbols(bit);
+        line.add_mut(first);
+        line.add_mut(second);
+    }
+
+    line
+}
+
+/// Decodes a Manchester coded bit stream
+///
+/// # Returns
+///
+/// The decoded bytes
+///
+/// An _InvalidLength_ error if the symbols do not add up to complete bytes
+///
+/// A _CodeViolation_ error if a bit period has no transition in the middle
+pub fn decode_manchester(
+    line: &BitArray,
+    convention: ManchesterConvention,
+    order: BitOrder,
+) -> Result<ByteArray, UberByteError> {
+    if !line.len().is_multiple_of(16) {
+        return Err(UberByteError::InvalidLength);
+    }
+
+    let mut bits = BitArray::default();
+    for (index, pair) in line[..].chunks(2).enumerate() {
+        if pair[0] == pair[1] {
+            return Err(UberByteError::CodeViolation(index * 2));
+        }
+        bits.add_mut(convention.symbols(true) == (pair[0], pair[1]));
+    }
+
+    bits.to_byte_array(order)
+}
+
+/// Encodes the data with the differential Manchester code
+///
+/// Every bit period has a transition in the middle. A 0 additionally has a
+/// transition at the start of the period, a 1 has none.
+///
+/// # Arguments
+///
+/// * `initial_level` - the line level before the first symbol
+pub fn encode_differential_manchester(
+    data: &ByteArray,
+    initial_level: bool,
+    order: BitOrder,
+) -> BitArray {
+    let mut line = BitArray::default();
+    let mut level = initial_level;
+
+    for bit in BitArray::from_byte_array(data, order) {
+        if !bit {
+            level = !level;
+        }
+        line.add_mut(level);
+        level = !level;
+        line.add_mut(level);
+    }
+
+    line
+}
+
+/// Decodes a differential Manchester coded bit stream
+///
+/// # Returns
+///
+/// The decoded bytes
+///
+/// An _InvalidLength_ error if the symbols do not add up to complete bytes
+///
+/// A _CodeViolation_ error if a bit period has no transition in the middle
+pub fn decode_differential_manchester(
+    line: &BitArray,
+    initial_level: bool,
+    order: BitOrder,
+) -> Result<ByteArray, UberByteError> {
+    if !line.len().is_multiple_of(16) {
+        return Err(UberByteError::InvalidLength);
+    }
+
+    let mut bits = BitArray::default();
+    let mut level = initial_level;
+    for (index, pair) in line[..].chunks(2).enumerate() {
+        if pair[0] == pair[1] {
+            return Err(UberByteError::CodeViolation(index * 2));
+        }
+        bits.add_mut(pair[0] == level);
+        level = pair[1];
+    }
+
+    bits.to_byte_array(order)
+}
+
+/// Encodes the data with NRZI, a 1 toggles the line level and a 0 keeps it
+///
+/// # Arguments
+///
+/// * `initial_level` - the line level before the first symbol
+pub fn encode_nrzi(data: &ByteArray, initial_level: bool, order: BitOrder) -> BitArray {
+    let mut level = initial_level;
+
+    BitArray::from_byte_array(data, order)
+        .into_iter()
+        .map(|bit| {
+            level ^= bit;
+            level
+        })
+        .collect::<Vec<bool>>()
+        .into()
+}
+
+/// Decodes a NRZI coded bit stream
+///
+/// # Returns
+///
+/// The decoded bytes
+///
+/// An _InvalidLength_ error if the symbols do not add up to complete bytes
+pub fn decode_nrzi(
+    line: &BitArray,
+    initial_level: bool,
+    order: BitOrder,
+) -> Result<ByteArray, UberByteError> {
+    let mut level = initial_level;
+
+    let bits: BitArray = line[..]
+        .iter()
+        .map(|symbol| {
+            let bit = *symbol != level;
+            level = *symbol;
+            bit
+        })
+        .collect::<Vec<bool>>()
+        .into();
+
+    bits.to_byte_array(order)
+}
+
+/// The 4B5B code groups for the data nibbles 0 to F, first sent bit on the left
+const CODE_4B5B: [u8; 16] = [
+    0b_11110, 0b_01001, 0b_10100, 0b_10101, 0b_01010, 0b_01011, 0b_01110, 0b_01111, 0b_10010,
+    0b_10011, 0b_10110, 0b_10111, 0b_11010, 0b_11011, 0b_11100, 0b_11101,
+];
+
+/// Encodes the data with 4B5B, every nibble becomes a 5 bit code group
+///
+/// # Remarks
+///
+/// The bit order selects which nibble of a byte is sent first. With _MsbFirst_ the
+/// high nibble is sent first. The code groups themselves are always sent as listed
+/// in the 4B5B table.
+pub fn encode_4b5b(data: &ByteArray, order: BitOrder) -> BitArray {
+    let mut line = BitArray::default();
+
+    for byte in &data[..] {
+        let value = byte.into_u8();
+        let nibbles = match order {
+            BitOrder::MsbFirst => [value >> 4, value & 0x0F],
+            BitOrder::LsbFirst => [value & 0x0F, value >> 4],
+        };
+        for nibble in nibbles {
+            let code = CODE_4B5B[nibble as usize];
+            for bit in (0..5).rev() {
+                line.add_mut((code >> bit) & 1 == 1);
+            }
+        }
+    }
+
+    line
+}
+
+/// Decodes a 4B5B coded bit stream
+///
+/// # Returns
+///
+/// The decoded bytes
+///
+/// An _InvalidLength_ error if the symbols do not add up to complete bytes
+///
+/// A _CodeViolation_ error if a 5 bit group is not a data code group
+pub fn decode_4b5b(line: &BitArray, order: BitOrder) -> Result<ByteArray, UberByteError> {
+    if !line.len().is_multiple_of(10) {
+        return Err(UberByteError::InvalidLength);
+    }
+
+    let mut nibbles = vec![];
+    for (index, group) in line[..].chunks(5).enumerate() {
+        let code = group
+            .iter()
+            .fold(0u8, |acc, bit| (acc << 1) | u8::from(*bit));
+        match CODE_4B5B.iter().position(|c| *c == code) {
+            Some(nibble) => nibbles.push(nibble as u8),
+            None => return Err(UberByteError::CodeViolation(index * 5)),
+        }
+    }
+
+    let mut bytes = ByteArray::default();
+    for pair in nibbles.chunks(2) {
+        let value = match order {
+            BitOrder::MsbFirst => (pair[0] << 4) | pair[1],
+            BitOrder::LsbFirst => (pair[1] << 4) | pair[0],
+        };
+        bytes.add_mut(UberByte::from(value));
+    }
+
+    Ok(bytes)
+}
+
+/// The running disparity of a 8b/10b coded stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Disparity {
+    /// More zeros than ones were sent so far
+    #[default]
+    Negative,
+    /// More ones than zeros were sent so far
+    Positive,
+}
+
+impl Disparity {
+    fn after(&self, code: u16, width: u32) -> Disparity {
+        let ones = code.count_ones();
+        match (ones * 2).cmp(&width) {
+            std::cmp::Ordering::Equal => *self,
+            std::cmp::Ordering::Greater => Disparity::Positive,
+            std::cmp::Ordering::Less => Disparity::Negative,
+        }
+    }
+}
+
+/// The 5b/6b code groups as `abcdei` for a negative and a positive running disparity
+const CODE_5B6B: [(u16, u16); 32] = [
+    (0b_100111, 0b_011000),
+    (0b_011101, 0b_100010),
+    (0b_101101, 0b_010010),
+    (0b_110001, 0b_110001),
+    (0b_110101, 0b_001010),
+    (0b_101001, 0b_101001),
+    (0b_011001, 0b_011001),
+    (0b_111000, 0b_000111),
+    (0b_111001, 0b_000110),
+    (0b_100101, 0b_100101),
+    (0b_010101, 0b_010101),
+    (0b_110100, 0b_110100),
+    (0b_001101, 0b_001101),
+    (0b_101100, 0b_101100),
+    (0b_011100, 0b_011100),
+    (0b_010111, 0b_101000),
+    (0b_011011, 0b_100100),
+    (0b_100011, 0b_100011),
+    (0b_010011, 0b_010011),
+    (0b_110010, 0b_110010),
+    (0b_001011, 0b_001011),
+    (0b_101010, 0b_101010),
+    (0b_011010, 0b_011010),
+    (0b_111010, 0b_000101),
+    (0b_110011, 0b_001100),
+    (0b_100110, 0b_100110),
+    (0b_010110, 0b_010110),
+    (0b_110110, 0b_001001),
+    (0b_001110, 0b_001110),
+    (0b_101110, 0b_010001),
+    (0b_011110, 0b_100001),
+    (0b_101011, 0b_010100),
+];
+
+/// The 3b/4b code groups as `fghj` for a negative and a positive running disparity
+const CODE_3B4B: [(u16, u16); 8] = [
+    (0b_1011, 0b_0100),
+    (0b_1001, 0b_1001),
+    (0b_0101, 0b_0101),
+    (0b_1100, 0b_0011),
+    (0b_1101, 0b_0010),
+    (0b_1010, 0b_1010),
+    (0b_0110, 0b_0110),
+    (0b_1110, 0b_0001),
+];
+
+/// The alternate D.x.A7 code group used to avoid runs of five equal bits
+const CODE_3B4B_A7: (u16, u16) = (0b_0111, 0b_1000);
+
+/// A 8b/10b codec for data characters that keeps track of the running disparity
+///
+/// The disparity is carried over between calls so a stream can be encoded or
+/// decoded in several parts. Every byte is sent as the 10 bits `abcdeifghj`
+/// where `a` is the least significant bit of the byte.
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::line_coding::Codec8b10b;
+/// use uberbyte::ByteArray;
+///
+/// let data = ByteArray::from(vec![0x00, 0xFF]);
+/// let line = Codec8b10b::default().encode(&data);
+///
+/// let decoded = Codec8b10b::default().decode(&line).unwrap();
+/// assert_eq!(data[..], decoded[..]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Codec8b10b {
+    /// The current running disparity
+    pub running_disparity: Disparity,
+}
+
+impl Codec8b10b {
+    /// Encodes the data and updates the running disparity
+    pub fn encode(&mut self, data: &ByteArray) -> BitArray {
+        let mut line = BitArray::default();
+
+        for byte in &data[..] {
+            let value = byte.into_u8();
+            let x = (value & 0b_0001_1111) as usize;
+            let y = (value >> 5) as usize;
+
+            let six = self.select(CODE_5B6B[x]);
+            self.running_disparity = self.running_disparity.after(six, 6);
+
+            let use_alternate = y == 7
+                && match self.running_disparity {
+                    Disparity::Negative => matches!(x, 17 | 18 | 20),
+                    Disparity::Positive => matches!(x, 11 | 13 | 14),
+                };
+            let four = match use_alternate {
+                true => self.select(CODE_3B4B_A7),
+                false => self.select(CODE_3B4B[y]),
+            };
+            self.running_disparity = self.running_disparity.after(four, 4);
+
+            for bit in (0..6).rev() {
+                line.add_mut((six >> bit) & 1 == 1);
+            }
+            for bit in (0..4).rev() {
+                line.add_mut((four >> bit) & 1 == 1);
+            }
+        }
+
+        line
+    }
+
+    /// Decodes the symbols and updates the running disparity
+    ///
+    /// # Returns
+    ///
+    /// The decoded bytes
+    ///
+    /// An _InvalidLength_ error if the symbols do not add up to complete characters
+    ///
+    /// A _CodeViolation_ error holding the index of the first symbol of a sub-block that
+    /// is not a valid data code group for the current running disparity
+    pub fn decode(&mut self, line: &BitArray) -> Result<ByteArray, UberByteError> {
+        if !line.len().is_multiple_of(10) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut bytes = ByteArray::default();
+        for (index, character) in line[..].chunks(10).enumerate() {
+            let to_code = |bits: &[bool]| bits.iter().fold(0u16, |acc, b| (acc << 1) | *b as u16);
+            let six = to_code(&character[..6]);
+            let four = to_code(&character[6..]);
+
+            let x = CODE_5B6B
+                .iter()
+                .position(|codes| self.select(*codes) == six)
+                .ok_or(UberByteError::CodeViolation(index * 10))?;
+            self.running_disparity = self.running_disparity.after(six, 6);
+
+            let y = match CODE_3B4B
+                .iter()
+                .position(|codes| self.select(*codes) == four)
+            {
+                Some(y) => y,
+                None if self.select(CODE_3B4B_A7) == four => 7,
+                None => return Err(UberByteError::CodeViolation(index * 10 + 6)),
+            };
+            self.running_disparity = self.running_disparity.after(four, 4);
+
+            bytes.add_mut(UberByte::from(((y as u8) << 5) | x as u8));
+        }
+
+        Ok(bytes)
+    }
+
+    fn select(&self, codes: (u16, u16)) -> u16 {
+        match self.running_disparity {
+            Disparity::Negative => codes.0,
+            Disparity::Positive => codes.1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn bits(pattern: &str) -> BitArray {
+        pattern
+            .chars()
+            .filter(|c| *c != ' ')
+            .map(|c| c == '1')
+            .collect::<Vec<bool>>()
+            .into()
+    }
+
+    #[test]
+    fn manchester_conventions() {
+        let data = ByteArray::from(vec![0b_1000_0001]);
+
+        let ieee = encode_manchester(&data, ManchesterConvention::Ieee, BitOrder::MsbFirst);
+        let thomas = encode_manchester(&data, ManchesterConvention::Thomas, BitOrder::MsbFirst);
+
+        assert_eq!("0110101010101001", ieee.to_string());
+        assert_eq!("1001010101010110", thomas.to_string());
+    }
+
+    #[test]
+    fn manchester_round_trip() {
+        let data = ByteArray::from(vec![0x00, 0x5A, 0xFF]);
+
+        for convention in [ManchesterConvention::Ieee, ManchesterConvention::Thomas] {
+            for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+                let line = encode_manchester(&data, convention, order);
+                assert_eq!(
+                    data[..],
+                    decode_manchester(&line, convention, order).unwrap()[..]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn manchester_violation() {
+        let mut line = encode_manchester(
+            &ByteArray::from(vec![0x00]),
+            ManchesterConvention::Ieee,
+            BitOrder::MsbFirst,
+        );
+        line[5] = line[4];
+
+        assert!(matches!(
+            decode_manchester(&line, ManchesterConvention::Ieee, BitOrder::MsbFirst),
+            Err(UberByteError::CodeViolation(4))
+        ));
+        assert!(matches!(
+            decode_manchester(&bits("01"), ManchesterConvention::Ieee, BitOrder::MsbFirst),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn differential_manchester() {
+        let data = ByteArray::from(vec![0b_1100_0000]);
+
+        let line = encode_differential_manchester(&data, false, BitOrder::MsbFirst);
+
+        // 1 keeps the level at the start, 0 toggles it, every period toggles in the middle
+        assert_eq!("0110101010101010", line.to_string());
+        assert_eq!(
+            data[..],
+            decode_differential_manchester(&line, false, BitOrder::MsbFirst).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn differential_manchester_violation() {
+        let line = bits("0101 1001 1001 1000");
+
+        assert!(matches!(
+            decode_differential_manchester(&line, false, BitOrder::MsbFirst),
+            Err(UberByteError::CodeViolation(14))
+        ));
+    }
+
+    #[test]
+    fn nrzi() {
+        let data = ByteArray::from(vec![0b_1011_0001]);
+
+        let line = encode_nrzi(&data, false, BitOrder::MsbFirst);
+
+        assert_eq!("11011110", line.to_string());
+        assert_eq!(
+            data[..],
+            decode_nrzi(&line, false, BitOrder::MsbFirst).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn four_b_five_b() {
+        let data = ByteArray::from(vec![0x0F]);
+
+        assert_eq!(
+            "1111011101",
+            encode_4b5b(&data, BitOrder::MsbFirst).to_string()
+        );
+        assert_eq!(
+            "1110111110",
+            encode_4b5b(&data, BitOrder::LsbFirst).to_string()
+        );
+
+        let all = ByteArray::from((0..=255).collect::<Vec<u8>>());
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let line = encode_4b5b(&all, order);
+            assert_eq!(all[..], decode_4b5b(&line, order).unwrap()[..]);
+        }
+    }
+
+    #[test]
+    fn four_b_five_b_violation() {
+        // 00000 is the quiet line state and not a data code group
+        assert!(matches!(
+            decode_4b5b(&bits("11110 00000"), BitOrder::MsbFirst),
+            Err(UberByteError::CodeViolation(5))
+        ));
+    }
+
+    #[test]
+    fn eight_b_ten_b_known_characters() {
+        let mut codec = Codec8b10b::default();
+
+        // D.0.0 starting with a negative running disparity
+        assert_eq!(
+            "1001110100",
+            codec.encode(&ByteArray::from(vec![0x00])).to_string()
+        );
+        // D.0.0 is unbalanced in both sub blocks so the disparity is negative again
+        assert_eq!(Disparity::Negative, codec.running_disparity);
+
+        // D.7.0 with RD- is 111000 1011
+        assert_eq!(
+            "1110001011",
+            codec.encode(&ByteArray::from(vec![0x07])).to_string()
+        );
+        assert_eq!(Disparity::Positive, codec.running_disparity);
+    }
+
+    #[test]
+    fn eight_b_ten_b_alternate_encoding() {
+        // D.17.7 with RD- uses the alternate 0111 code group
+        let mut codec = Codec8b10b::default();
+
+        assert_eq!(
+            "1000110111",
+            codec.encode(&ByteArray::from(vec![0xF1])).to_string()
+        );
+    }
+
+    #[test]
+    fn eight_b_ten_b_round_trip() {
+        let data = ByteArray::from((0..=255).collect::<Vec<u8>>());
+
+        let line = Codec8b10b::default().encode(&data);
+
+        // never more than five equal symbols in a row
+        let mut run = 0;
+        for window in line[..].windows(2) {
+            run = if window[0] == window[1] { run + 1 } else { 0 };
+            assert!(run < 5);
+        }
+
+        assert_eq!(data[..], Codec8b10b::default().decode(&line).unwrap()[..]);
+    }
+
+    #[test]
+    fn eight_b_ten_b_disparity_violation() {
+        // D.0.0 as encoded for RD+ is not valid when the decoder expects RD-
+        let line = bits("011000 1011");
+
+        assert!(matches!(
+            Codec8b10b::default().decode(&line),
+            Err(UberByteError::CodeViolation(0))
+        ));
+    }
+}
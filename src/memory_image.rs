@@ -0,0 +1,490 @@
+//! A sparse model of device memory made of address tagged byte arrays
+//!
+//! ```rust
+//! use uberbyte::memory_image::MemoryImage;
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let mut image = MemoryImage::new();
+//! image.write(0x1000, &ByteArray::from(vec![0x01, 0x02])).unwrap();
+//! image.write(0x1002, &ByteArray::from(vec![0x03])).unwrap();
+//! image.write(0x2000, &ByteArray::from(vec![0xFF])).unwrap();
+//!
+//! // touching writes end up in the same segment
+//! assert_eq!(2, image.len());
+//! assert_eq!(3, image.segments().next().unwrap().1.len());
+//!
+//! // inserting never overwrites existing bytes
+//! assert!(image.insert(0x1001, &ByteArray::from(vec![0x00])).is_err());
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::{crc::Crc, ByteArray, UberByte, UberByteError};
+
+/// A set of non overlapping segments, each a byte array starting at an address
+#[derive(Debug, Clone, Default)]
+pub struct MemoryImage {
+    segments: BTreeMap<u32, ByteArray>,
+}
+
+impl MemoryImage {
+    /// Simple alias for the __default__
+    pub fn new() -> Self {
+        MemoryImage::default()
+    }
+
+    /// Iterates over all segments ordered by their start address
+    pub fn segments(&self) -> impl Iterator<Item = (u32, &ByteArray)> {
+        self.segments
+            .iter()
+            .map(|(address, bytes)| (*address, bytes))
+    }
+
+    /// Returns the number of segments
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Determines if the image contains no segments
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Writes the bytes starting at the given address
+    ///
+    /// # Remarks
+    ///
+    /// Existing bytes are overwritten. Segments that overlap or touch the written range
+    /// are merged into a single segment.
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the bytes do not fit into the 32 bit address space
+    pub fn write(&mut self, address: u32, bytes: &ByteArray) -> Result<(), UberByteError> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let end = end_address(address, bytes)?;
+
+        let (start, mut merged) = match self.segments.range(..=address).next_back() {
+            Some((start, segment)) if end_address_unchecked(*start, segment) >= address as u64 => {
+                let start = *start;
+                (start, self.segments.remove(&start).unwrap_or_default())
+            }
+            _ => (address, ByteArray::default()),
+        };
+
+        let offset = (address - start) as usize;
+        let overwritten = merged.len().saturating_sub(offset).min(bytes.len());
+        for (index, byte) in bytes[..overwritten].iter().enumerate() {
+            merged[offset + index] = *byte;
+        }
+        merged.add_range_mut(bytes[overwritten..].to_vec());
+
+        let following: Vec<u32> = self
+            .segments
+            .range(address..)
+            .take_while(|(segment_start, _)| **segment_start as u64 <= end)
+            .map(|(segment_start, _)| *segment_start)
+            .collect();
+        for segment_start in following {
+            let segment = self.segments.remove(&segment_start).unwrap_or_default();
+            let covered = (end - segment_start as u64) as usize;
+            if covered < segment.len() {
+                merged.add_range_mut(segment[covered..].to_vec());
+            }
+        }
+
+        self.segments.insert(start, merged);
+        Ok(())
+    }
+
+    /// Reads a range of bytes
+    ///
+    /// # Returns
+    ///
+    /// A copy of the bytes
+    ///
+    /// An _UnknownAddress_ error with the first address of the range that holds no byte
+    ///
+    /// A _ValueOverflow_ error if the range does not fit into the 32 bit address space
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::memory_image::MemoryImage;
+    /// use uberbyte::ByteArray;
+    ///
+    /// let mut image = MemoryImage::new();
+    /// image.write(0x0800_0000, &ByteArray::from(vec![0x01, 0x02, 0x03])).unwrap();
+    ///
+    /// assert_eq!(0x02, image.read(0x0800_0001, 2).unwrap()[0].into_u8());
+    /// assert!(image.read(0x0800_0002, 2).is_err());
+    /// ```
+    pub fn read(&self, address: u32, length: usize) -> Result<ByteArray, UberByteError> {
+        if length == 0 {
+            return Ok(ByteArray::default());
+        }
+        let Some((start, segment)) = self.segment_containing(address) else {
+            return Err(UberByteError::UnknownAddress(address));
+        };
+
+        let offset = (address - start) as usize;
+        match offset + length <= segment.len() {
+            true => Ok(ByteArray::from(&segment[offset..offset + length])),
+            false => match u32::try_from(end_address_unchecked(start, segment)) {
+                Ok(missing) => Err(UberByteError::UnknownAddress(missing)),
+                Err(_) => Err(UberByteError::ValueOverflow),
+            },
+        }
+    }
+
+    /// Adds the bytes at the given address without touching any existing byte
+    ///
+    /// # Remarks
+    ///
+    /// Segments that touch the inserted range are merged into a single segment
+    ///
+    /// # Returns
+    ///
+    /// An _AddressOverlap_ error with the first address that already holds a byte
+    ///
+    /// A _ValueOverflow_ error if the bytes do not fit into the 32 bit address space
+    pub fn insert(&mut self, address: u32, bytes: &ByteArray) -> Result<(), UberByteError> {
+        end_address(address, bytes)?;
+        if let Some(overlap) = self.first_overlap(address, bytes.len()) {
+            return Err(UberByteError::AddressOverlap(overlap));
+        }
+
+        self.write(address, bytes)
+    }
+
+    /// Determines the first address of the range that already holds a byte
+    ///
+    /// # Returns
+    ///
+    /// The address, None if the range does not overlap any segment
+    pub fn first_overlap(&self, address: u32, length: usize) -> Option<u32> {
+        let end = address as u64 + length as u64;
+        let before = self
+            .segments
+            .range(..=address)
+            .next_back()
+            .filter(|(start, segment)| {
+                (**start as u64) < end && end_address_unchecked(**start, segment) > address as u64
+            })
+            .map(|_| address);
+
+        before.or_else(|| {
+            self.segments
+                .range(address..)
+                .map(|(start, _)| *start)
+                .find(|start| (*start as u64) < end)
+        })
+    }
+
+    /// Inserts all segments of the other image
+    ///
+    /// # Returns
+    ///
+    /// An _AddressOverlap_ error with the first overlapping address, in which case
+    /// this image is left unchanged
+    pub fn merge(&mut self, other: &MemoryImage) -> Result<(), UberByteError> {
+        for (address, segment) in other.segments() {
+            if let Some(overlap) = self.first_overlap(address, segment.len()) {
+                return Err(UberByteError::AddressOverlap(overlap));
+            }
+        }
+
+        for (address, segment) in other.segments() {
+            self.write(address, segment)?;
+        }
+        Ok(())
+    }
+
+    /// Fills every address of the range that holds no byte with the pad byte
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the range does not fit into the 32 bit address space
+    ///
+    /// # Remarks
+    ///
+    /// Allocates `length` bytes, up to 4 GiB for a range covering the whole address space
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::memory_image::MemoryImage;
+    /// use uberbyte::{ByteArray, UberByte};
+    ///
+    /// let mut image = MemoryImage::new();
+    /// image.write(0x0002, &ByteArray::from(vec![0x42])).unwrap();
+    ///
+    /// image.fill_range(0x0000, 4, UberByte::MAX).unwrap();
+    ///
+    /// assert_eq!(1, image.len());
+    /// assert_eq!(0x42, image.read(0x0002, 1).unwrap()[0].into_u8());
+    /// assert_eq!(0xFF, image.read(0x0003, 1).unwrap()[0].into_u8());
+    /// ```
+    pub fn fill_range(
+        &mut self,
+        address: u32,
+        length: usize,
+        pad: UberByte,
+    ) -> Result<(), UberByteError> {
+        let end = address as u64 + length as u64;
+        if end > u32::MAX as u64 + 1 {
+            return Err(UberByteError::ValueOverflow);
+        }
+
+        let mut filled = ByteArray::from(vec![pad.into_u8(); length]);
+        for (start, segment) in self.segments() {
+            let segment_end = end_address_unchecked(start, segment);
+            if segment_end <= address as u64 || start as u64 >= end {
+                continue;
+            }
+
+            let from = start.max(address);
+            let to = segment_end.min(end);
+            for target in from as u64..to {
+                filled[(target - address as u64) as usize] =
+                    segment[(target - start as u64) as usize];
+            }
+        }
+
+        self.write(address, &filled)
+    }
+
+    /// Computes the checksum of every segment
+    ///
+    /// # Returns
+    ///
+    /// The start address and checksum of each segment ordered by address
+    pub fn segment_checksums(&self, crc: &Crc) -> Vec<(u32, u32)> {
+        self.segments()
+            .map(|(address, segment)| (address, crc.checksum(segment)))
+            .collect()
+    }
+
+    fn segment_containing(&self, address: u32) -> Option<(u32, &ByteArray)> {
+        self.segments
+            .range(..=address)
+            .next_back()
+            .filter(|(start, segment)| end_address_unchecked(**start, segment) > address as u64)
+            .map(|(start, segment)| (*start, segment))
+    }
+
+    /// Joins all segments into one, filling the holes between them with the pad byte
+    ///
+    /// # Remarks
+    ///
+    /// Allocates every byte from the first to the last segment, up to 4 GiB for
+    /// segments at both ends of the address space
+    pub fn fill_gaps(&mut self, pad: UberByte) {
+        let mut segments = std::mem::take(&mut self.segments).into_iter();
+        let Some((start, mut filled)) = segments.next() else {
+            return;
+        };
+
+        for (address, segment) in segments {
+            let gap = address as u64 - end_address_unchecked(start, &filled);
+            filled.add_range_mut(vec![pad; gap as usize]);
+            filled.add_range_mut(segment[..].to_vec());
+        }
+        self.segments.insert(start, filled);
+    }
+}
+
+/// Returns the address directly after the last byte
+fn end_address(address: u32, bytes: &ByteArray) -> Result<u64, UberByteError> {
+    let end = end_address_unchecked(address, bytes);
+    match end > u32::MAX as u64 + 1 {
+        true => Err(UberByteError::ValueOverflow),
+        false => Ok(end),
+    }
+}
+
+fn end_address_unchecked(address: u32, bytes: &ByteArray) -> u64 {
+    address as u64 + bytes.len() as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_overlapping() {
+        let mut image = MemoryImage::new();
+
+        image
+            .write(0x10, &ByteArray::from(vec![1, 2, 3, 4]))
+            .unwrap();
+        image.write(0x0E, &ByteArray::from(vec![9, 9, 9])).unwrap();
+        image.write(0x20, &ByteArray::from(vec![5])).unwrap();
+
+        let segments: Vec<(u32, Vec<u8>)> = image
+            .segments()
+            .map(|(address, bytes)| (address, bytes.to_u8_vec()))
+            .collect();
+        assert_eq!(
+            vec![(0x0E, vec![9, 9, 9, 2, 3, 4]), (0x20, vec![5])],
+            segments
+        );
+    }
+
+    #[test]
+    fn write_inside_segment() {
+        let mut image = MemoryImage::new();
+        image
+            .write(0x10, &ByteArray::from(vec![1, 2, 3, 4]))
+            .unwrap();
+        image.write(0x20, &ByteArray::from(vec![5, 6])).unwrap();
+
+        image.write(0x11, &ByteArray::from(vec![9, 9])).unwrap();
+        image.write(0x1F, &ByteArray::from(vec![8, 8, 8])).unwrap();
+
+        let segments: Vec<(u32, Vec<u8>)> = image
+            .segments()
+            .map(|(address, bytes)| (address, bytes.to_u8_vec()))
+            .collect();
+        assert_eq!(
+            vec![(0x10, vec![1, 9, 9, 4]), (0x1F, vec![8, 8, 8])],
+            segments
+        );
+    }
+
+    #[test]
+    fn write_bridging_segments() {
+        let mut image = MemoryImage::new();
+        image.write(0x00, &ByteArray::from(vec![1])).unwrap();
+        image.write(0x02, &ByteArray::from(vec![3])).unwrap();
+
+        image.write(0x01, &ByteArray::from(vec![2])).unwrap();
+
+        assert_eq!(1, image.len());
+        assert_eq!(
+            vec![1, 2, 3],
+            image.segments().next().unwrap().1.to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn write_out_of_address_space() {
+        let mut image = MemoryImage::new();
+
+        assert!(image.write(0xFFFF_FFFF, &ByteArray::from(vec![1])).is_ok());
+        assert!(matches!(
+            image.write(0xFFFF_FFFF, &ByteArray::from(vec![1, 2])),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+
+    #[test]
+    fn fill_gaps() {
+        let mut image = MemoryImage::new();
+        image.write(0x00, &ByteArray::from(vec![1])).unwrap();
+        image.write(0x03, &ByteArray::from(vec![2])).unwrap();
+
+        image.fill_gaps(UberByte::MAX);
+
+        assert_eq!(1, image.len());
+        assert_eq!(
+            vec![1, 0xFF, 0xFF, 2],
+            image.segments().next().unwrap().1.to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn read() {
+        let mut image = MemoryImage::new();
+        image.write(0x10, &ByteArray::from(vec![1, 2, 3])).unwrap();
+
+        assert_eq!(vec![2, 3], image.read(0x11, 2).unwrap().to_u8_vec());
+        assert_eq!(0, image.read(0x13, 0).map_or(1, |bytes| bytes.len()));
+        assert!(matches!(
+            image.read(0x0F, 1),
+            Err(UberByteError::UnknownAddress(0x0F))
+        ));
+        assert!(matches!(
+            image.read(0x12, 2),
+            Err(UberByteError::UnknownAddress(0x13))
+        ));
+    }
+
+    #[test]
+    fn insert() {
+        let mut image = MemoryImage::new();
+        image.insert(0x10, &ByteArray::from(vec![1, 2])).unwrap();
+
+        image.insert(0x12, &ByteArray::from(vec![3])).unwrap();
+
+        assert_eq!(1, image.len());
+        assert!(matches!(
+            image.insert(0x0E, &ByteArray::from(vec![0, 0, 0])),
+            Err(UberByteError::AddressOverlap(0x10))
+        ));
+        assert!(matches!(
+            image.insert(0x12, &ByteArray::from(vec![0])),
+            Err(UberByteError::AddressOverlap(0x12))
+        ));
+        assert_eq!(None, image.first_overlap(0x0F, 1));
+        assert_eq!(Some(0x11), image.first_overlap(0x11, 8));
+        assert_eq!(None, image.first_overlap(0x13, 4));
+        assert_eq!(vec![1, 2, 3], image.read(0x10, 3).unwrap().to_u8_vec());
+    }
+
+    #[test]
+    fn merge() {
+        let mut flash = MemoryImage::new();
+        flash
+            .write(0x0800_0000, &ByteArray::from(vec![1, 2]))
+            .unwrap();
+        let mut eeprom = MemoryImage::new();
+        eeprom
+            .write(0x0808_0000, &ByteArray::from(vec![3]))
+            .unwrap();
+
+        flash.merge(&eeprom).unwrap();
+
+        assert_eq!(2, flash.len());
+        assert!(matches!(
+            flash.merge(&eeprom),
+            Err(UberByteError::AddressOverlap(0x0808_0000))
+        ));
+        assert_eq!(2, flash.len());
+    }
+
+    #[test]
+    fn fill_range() {
+        let mut image = MemoryImage::new();
+        image.write(0x02, &ByteArray::from(vec![1])).unwrap();
+        image.write(0x08, &ByteArray::from(vec![2, 3])).unwrap();
+
+        image.fill_range(0x00, 9, UberByte::MIN).unwrap();
+
+        assert_eq!(1, image.len());
+        assert_eq!(
+            vec![0, 0, 1, 0, 0, 0, 0, 0, 2, 3],
+            image.read(0x00, 10).unwrap().to_u8_vec()
+        );
+        assert!(matches!(
+            image.fill_range(0xFFFF_FFFF, 2, UberByte::MIN),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+
+    #[test]
+    fn segment_checksums() {
+        let mut image = MemoryImage::new();
+        image
+            .write(0x00, &ByteArray::from(b"123456789".to_vec()))
+            .unwrap();
+        image.write(0x100, &ByteArray::from(vec![0x00])).unwrap();
+
+        let checksums = image.segment_checksums(&Crc::CRC_32);
+
+        assert_eq!((0x00, 0xCBF4_3926), checksums[0]);
+        assert_eq!(0x100, checksums[1].0);
+    }
+}
@@ -0,0 +1,441 @@
+//! Parity calculation and verification for single bytes and byte arrays
+//!
+//! ```rust
+//! use uberbyte::parity::Parity;
+//! use uberbyte::ByteArray;
+//!
+//! // 7 bit ASCII with even parity in bit 7, as sent over a 7E1 serial line
+//! let line = ByteArray::from(vec![b'O', b'K']).with_parity_bits(Parity::Even).unwrap();
+//!
+//! assert!(line.verify_parity_bits(Parity::Even).is_ok());
+//! ```
+
+use crate::{ByteArray, UberByte, UberByteError, SEVENTH_BIT_MASK};
+
+/// The supported ways to compute a parity bit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// The parity bit makes the total number of set bits odd
+    Odd,
+    /// The parity bit makes the total number of set bits even
+    Even,
+    /// The parity bit is always set
+    Mark,
+    /// The parity bit is always cleared
+    Space,
+}
+
+impl Parity {
+    /// Computes the parity bit for the given number of set data bits
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::parity::Parity;
+    ///
+    /// assert!(Parity::Even.bit(3));
+    /// assert!(!Parity::Odd.bit(3));
+    /// ```
+    pub fn bit(&self, set_bits: u32) -> bool {
+        match self {
+            Parity::Odd => set_bits.is_multiple_of(2),
+            Parity::Even => !set_bits.is_multiple_of(2),
+            Parity::Mark => true,
+            Parity::Space => false,
+        }
+    }
+}
+
+/// The row and column parity of a byte array
+///
+/// Every byte is a row and every bit index is a column. A single flipped data bit
+/// shows up in exactly one row and one column and can therefore be located.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwoDimensionalParity {
+    /// The parity mode used for rows and columns
+    pub parity: Parity,
+    /// The parity bit of every byte
+    pub rows: Vec<bool>,
+    /// The parity of every bit column
+    pub columns: UberByte,
+}
+
+impl TwoDimensionalParity {
+    /// Locates a single flipped bit by comparing the given data with this parity
+    ///
+    /// # Returns
+    ///
+    /// None if the data matches the parity
+    ///
+    /// The byte index and bit index of the flipped bit if exactly one row and one column do not match
+    ///
+    /// An _InvalidLength_ error if the data has a different number of bytes than parity rows
+    ///
+    /// A _ParityError_ error holding the first mismatching row if the mismatch can not be explained by a
+    /// single flipped data bit. If only columns mismatch the length of the data is used as the row.
+    pub fn locate_error(&self, data: &ByteArray) -> Result<Option<(usize, usize)>, UberByteError> {
+        if data.len() != self.rows.len() {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let actual = data.two_dimensional_parity(self.parity);
+
+        let rows: Vec<usize> = (0..self.rows.len())
+            .filter(|row| self.rows[*row] != actual.rows[*row])
+            .collect();
+        let columns = (self.columns ^ actual.columns).into_u8();
+
+        match (rows.len(), columns.count_ones()) {
+            (0, 0) => Ok(None),
+            (1, 1) => Ok(Some((rows[0], columns.trailing_zeros() as usize))),
+            _ => Err(UberByteError::ParityError(
+                rows.first().copied().unwrap_or(data.len()),
+            )),
+        }
+    }
+}
+
+impl UberByte {
+    /// Computes the parity bit over all 8 bits of the byte
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::parity::Parity;
+    /// use uberbyte::UberByte;
+    ///
+    /// assert!(UberByte::from(0b_0000_0111).parity_bit(Parity::Even));
+    /// ```
+    pub fn parity_bit(&self, parity: Parity) -> bool {
+        parity.bit(self.count_set_bits() as u32)
+    }
+
+    /// Returns a new UberByte with the parity over the bits 0 to 6 stored in bit 7
+    ///
+    /// # Remarks
+    ///
+    /// The original content of bit 7 is ignored and overwritten
+    pub fn with_parity_bit(&self, parity: Parity) -> UberByte {
+        let data = self.clear(SEVENTH_BIT_MASK);
+
+        match data.parity_bit(parity) {
+            true => data.set(SEVENTH_BIT_MASK),
+            false => data,
+        }
+    }
+
+    /// Determines if bit 7 holds the correct parity of the bits 0 to 6
+    pub fn has_valid_parity_bit(&self, parity: Parity) -> bool {
+        self.with_parity_bit(parity) == *self
+    }
+}
+
+impl ByteArray {
+    /// Computes the parity bit over all 8 bits of every byte
+    ///
+    /// # Returns
+    ///
+    /// One parity bit per byte, in the order of the bytes
+    pub fn parity_bits(&self, parity: Parity) -> Vec<bool> {
+        self[..]
+            .iter()
+            .map(|byte| byte.parity_bit(parity))
+            .collect()
+    }
+
+    /// Verifies 8 bit data against separately transmitted parity bits
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the number of parity bits does not match the number of bytes
+    ///
+    /// A _ParityError_ error with the index of the first byte whose parity bit does not match
+    pub fn verify_separate_parity_bits(
+        &self,
+        parity_bits: &[bool],
+        parity: Parity,
+    ) -> Result<(), UberByteError> {
+        if parity_bits.len() != self.len() {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        match self
+            .parity_bits(parity)
+            .iter()
+            .zip(parity_bits)
+            .position(|(expected, actual)| expected != actual)
+        {
+            Some(index) => Err(UberByteError::ParityError(index)),
+            None => Ok(()),
+        }
+    }
+
+    /// Packs 7 bit data with a parity bit stored in bit 7 of every byte
+    ///
+    /// # Returns
+    ///
+    /// The bytes with the parity bits set
+    ///
+    /// A _ValueOverflow_ error if any byte does not fit into 7 bits
+    pub fn with_parity_bits(&self, parity: Parity) -> Result<ByteArray, UberByteError> {
+        let mut packed = ByteArray::default();
+
+        for byte in &self[..] {
+            if byte.is_bit_7_set() {
+                return Err(UberByteError::ValueOverflow);
+            }
+            packed.add_mut(byte.with_parity_bit(parity));
+        }
+
+        Ok(packed)
+    }
+
+    /// Verifies that bit 7 of every byte holds the parity of the bits 0 to 6
+    ///
+    /// # Returns
+    ///
+    /// A _ParityError_ error with the index of the first byte with a wrong parity bit
+    pub fn verify_parity_bits(&self, parity: Parity) -> Result<(), UberByteError> {
+        match self[..]
+            .iter()
+            .position(|byte| !byte.has_valid_parity_bit(parity))
+        {
+            Some(index) => Err(UberByteError::ParityError(index)),
+            None => Ok(()),
+        }
+    }
+
+    /// Removes the parity bits stored in bit 7 of every byte
+    ///
+    /// # Returns
+    ///
+    /// A copy of the bytes with bit 7 cleared
+    pub fn strip_parity_bits(&self) -> ByteArray {
+        let stripped: Vec<UberByte> = self[..]
+            .iter()
+            .map(|byte| byte.clear(SEVENTH_BIT_MASK))
+            .collect();
+        ByteArray::from(&stripped[..])
+    }
+
+    /// Computes the longitudinal (column) parity byte
+    ///
+    /// # Returns
+    ///
+    /// A byte in which every bit is the parity of the same bit in all bytes.
+    /// With even parity this is the XOR of all bytes.
+    pub fn longitudinal_parity(&self, parity: Parity) -> UberByte {
+        let xor = self[..]
+            .iter()
+            .fold(UberByte::MIN, |acc, byte| acc ^ *byte)
+            .into_u8();
+
+        let mut column_parity = UberByte::MIN;
+        for bit in 0..8 {
+            if parity.bit((xor >> bit) as u32 & 1) {
+                column_parity.set_mut(1 << bit);
+            }
+        }
+        column_parity
+    }
+
+    /// Appends the longitudinal parity byte to the byte array
+    pub fn add_longitudinal_parity_mut(&mut self, parity: Parity) {
+        let column_parity = self.longitudinal_parity(parity);
+        self.add_mut(column_parity);
+    }
+
+    /// Verifies that the last byte is the longitudinal parity of all other bytes
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the byte array is empty
+    ///
+    /// A _ParityError_ error holding the index of the parity byte if it does not match
+    pub fn verify_longitudinal_parity(&self, parity: Parity) -> Result<(), UberByteError> {
+        if self.is_empty() {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let last = self.len() - 1;
+        let data = ByteArray::from(&self[..last]);
+
+        match data.longitudinal_parity(parity) == self[last] {
+            true => Ok(()),
+            false => Err(UberByteError::ParityError(last)),
+        }
+    }
+
+    /// Computes the row and column parity of the byte array
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::parity::Parity;
+    /// use uberbyte::{ByteArray, UberByte};
+    ///
+    /// let mut data = ByteArray::from(vec![0x12, 0x34, 0x56]);
+    /// let parity = data.two_dimensional_parity(Parity::Even);
+    ///
+    /// data[1] ^= UberByte::from(0b_0000_1000);
+    ///
+    /// assert_eq!(Some((1, 3)), parity.locate_error(&data).unwrap());
+    /// ```
+    pub fn two_dimensional_parity(&self, parity: Parity) -> TwoDimensionalParity {
+        TwoDimensionalParity {
+            parity,
+            rows: self.parity_bits(parity),
+            columns: self.longitudinal_parity(parity),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parity_modes() {
+        let byte = UberByte::from(0b_0001_0011);
+
+        assert!(byte.parity_bit(Parity::Even));
+        assert!(!byte.parity_bit(Parity::Odd));
+        assert!(byte.parity_bit(Parity::Mark));
+        assert!(!byte.parity_bit(Parity::Space));
+
+        assert!(!UberByte::MIN.parity_bit(Parity::Even));
+        assert!(UberByte::MIN.parity_bit(Parity::Odd));
+    }
+
+    #[test]
+    fn with_parity_bit() {
+        let byte = UberByte::from(0b_0000_0001);
+
+        assert_eq!(
+            UberByte::from(0b_1000_0001),
+            byte.with_parity_bit(Parity::Even)
+        );
+        assert_eq!(
+            UberByte::from(0b_0000_0001),
+            byte.with_parity_bit(Parity::Odd)
+        );
+        assert_eq!(
+            UberByte::from(0b_1000_0001),
+            UberByte::from(0b_0000_0001).with_parity_bit(Parity::Mark)
+        );
+        assert_eq!(
+            UberByte::from(0b_0000_0001),
+            UberByte::from(0b_1000_0001).with_parity_bit(Parity::Space)
+        );
+    }
+
+    #[test]
+    fn has_valid_parity_bit() {
+        assert!(UberByte::from(0b_1000_0001).has_valid_parity_bit(Parity::Even));
+        assert!(!UberByte::from(0b_1000_0011).has_valid_parity_bit(Parity::Even));
+    }
+
+    #[test]
+    fn separate_parity_bits() {
+        let data = ByteArray::from(vec![0x01, 0x03, 0xFF]);
+        let bits = data.parity_bits(Parity::Odd);
+
+        assert_eq!(vec![false, true, true], bits);
+        assert!(data.verify_separate_parity_bits(&bits, Parity::Odd).is_ok());
+        assert!(matches!(
+            data.verify_separate_parity_bits(&[false, false, true], Parity::Odd),
+            Err(UberByteError::ParityError(1))
+        ));
+        assert!(matches!(
+            data.verify_separate_parity_bits(&[false], Parity::Odd),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn seven_bit_packing() {
+        let data = ByteArray::from(vec![b'A', b'C']);
+
+        let packed = data.with_parity_bits(Parity::Even).unwrap();
+
+        // 'A' = 0x41 has two set bits, 'C' = 0x43 has three
+        assert_eq!(UberByte::from(0x41), packed[0]);
+        assert_eq!(UberByte::from(0xC3), packed[1]);
+        assert!(packed.verify_parity_bits(Parity::Even).is_ok());
+        assert!(matches!(
+            packed.verify_parity_bits(Parity::Odd),
+            Err(UberByteError::ParityError(0))
+        ));
+        assert_eq!(data[..], packed.strip_parity_bits()[..]);
+    }
+
+    #[test]
+    fn seven_bit_packing_overflow() {
+        let data = ByteArray::from(vec![0x80]);
+
+        assert!(matches!(
+            data.with_parity_bits(Parity::Even),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+
+    #[test]
+    fn longitudinal_parity() {
+        let mut data = ByteArray::from(vec![0b_0000_0011, 0b_0000_0110]);
+
+        assert_eq!(
+            UberByte::from(0b_0000_0101),
+            data.longitudinal_parity(Parity::Even)
+        );
+        assert_eq!(
+            UberByte::from(0b_1111_1010),
+            data.longitudinal_parity(Parity::Odd)
+        );
+        assert_eq!(UberByte::MAX, data.longitudinal_parity(Parity::Mark));
+
+        data.add_longitudinal_parity_mut(Parity::Even);
+        assert_eq!(3, data.len());
+        assert!(data.verify_longitudinal_parity(Parity::Even).is_ok());
+
+        data[0] = UberByte::MIN;
+        assert!(matches!(
+            data.verify_longitudinal_parity(Parity::Even),
+            Err(UberByteError::ParityError(2))
+        ));
+    }
+
+    #[test]
+    fn two_dimensional_parity() {
+        let original = ByteArray::from(vec![0x12, 0x34, 0x56, 0x78]);
+        let parity = original.two_dimensional_parity(Parity::Odd);
+
+        assert_eq!(None, parity.locate_error(&original).unwrap());
+
+        for byte in 0..4 {
+            for bit in 0..8 {
+                let mut corrupted = original.clone();
+                corrupted[byte] ^= UberByte::from(1 << bit);
+                assert_eq!(Some((byte, bit)), parity.locate_error(&corrupted).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn two_dimensional_parity_multiple_errors() {
+        let original = ByteArray::from(vec![0x12, 0x34, 0x56, 0x78]);
+        let parity = original.two_dimensional_parity(Parity::Even);
+
+        let mut corrupted = original.clone();
+        corrupted[1] ^= UberByte::from(0b_0000_0001);
+        corrupted[2] ^= UberByte::from(0b_0000_0010);
+
+        assert!(matches!(
+            parity.locate_error(&corrupted),
+            Err(UberByteError::ParityError(1))
+        ));
+        assert!(matches!(
+            parity.locate_error(&ByteArray::default()),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+}
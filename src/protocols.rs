@@ -0,0 +1,3 @@
+//! Frame builders and parsers for common industrial protocols
+
+pub mod modbus;
@@ -0,0 +1,714 @@
+//! Modbus RTU and ASCII frames
+//!
+//! A [`ModbusFrame`] holds the slave id, the function code and the payload.
+//! The checksum and the framing are added when the frame is converted into
+//! its RTU or ASCII representation and are verified when parsing.
+//!
+//! ```rust
+//! use uberbyte::protocols::modbus::ModbusFrame;
+//!
+//! let request = ModbusFrame::read_holding_registers(0x11, 0x006B, 3).unwrap();
+//! let rtu = request.to_rtu();
+//!
+//! assert_eq!(8, rtu.len());
+//! assert_eq!(":1103006B00037E\r\n", request.to_ascii());
+//! assert_eq!(request, ModbusFrame::from_rtu(&rtu).unwrap());
+//! ```
+
+use crate::crc::Crc;
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// The function codes for accessing coils and registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionCode {
+    /// Reads the state of one or more coils
+    ReadCoils = 0x01,
+    /// Reads the state of one or more discrete inputs
+    ReadDiscreteInputs = 0x02,
+    /// Reads one or more holding registers
+    ReadHoldingRegisters = 0x03,
+    /// Reads one or more input registers
+    ReadInputRegisters = 0x04,
+    /// Sets a single coil to on or off
+    WriteSingleCoil = 0x05,
+    /// Writes a single holding register
+    WriteSingleRegister = 0x06,
+    /// Sets multiple coils to on or off
+    WriteMultipleCoils = 0x0F,
+    /// Writes multiple holding registers
+    WriteMultipleRegisters = 0x10,
+}
+
+/// The exception codes a slave can respond with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExceptionCode {
+    /// The function code is not supported by the slave
+    IllegalFunction = 0x01,
+    /// The data address is not available on the slave
+    IllegalDataAddress = 0x02,
+    /// A value in the request is not allowed
+    IllegalDataValue = 0x03,
+    /// A unrecoverable error occurred while processing the request
+    SlaveDeviceFailure = 0x04,
+    /// The request was accepted but takes a long time to process
+    Acknowledge = 0x05,
+    /// The slave is busy processing a long running request
+    SlaveDeviceBusy = 0x06,
+    /// A parity error was detected in the extended memory
+    MemoryParityError = 0x08,
+    /// The gateway could not allocate a path to the target
+    GatewayPathUnavailable = 0x0A,
+    /// The target device behind the gateway did not respond
+    GatewayTargetFailedToRespond = 0x0B,
+}
+
+impl TryFrom<u8> for ExceptionCode {
+    type Error = UberByteError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(ExceptionCode::IllegalFunction),
+            0x02 => Ok(ExceptionCode::IllegalDataAddress),
+            0x03 => Ok(ExceptionCode::IllegalDataValue),
+            0x04 => Ok(ExceptionCode::SlaveDeviceFailure),
+            0x05 => Ok(ExceptionCode::Acknowledge),
+            0x06 => Ok(ExceptionCode::SlaveDeviceBusy),
+            0x08 => Ok(ExceptionCode::MemoryParityError),
+            0x0A => Ok(ExceptionCode::GatewayPathUnavailable),
+            0x0B => Ok(ExceptionCode::GatewayTargetFailedToRespond),
+            _ => Err(UberByteError::CodeViolation(value as usize)),
+        }
+    }
+}
+
+/// The bit set in the function code of a exception response
+const EXCEPTION_FLAG: u8 = 0x80;
+
+/// The value used to switch a single coil on
+const COIL_ON: u16 = 0xFF00;
+
+/// The most coils or discrete inputs a single response can carry
+const MAX_READ_COILS: usize = 2000;
+
+/// The most coils a single write request can carry
+const MAX_WRITE_COILS: usize = 1968;
+
+/// The most registers a single response can carry
+const MAX_READ_REGISTERS: usize = 125;
+
+/// The most registers a single write request can carry
+const MAX_WRITE_REGISTERS: usize = 123;
+
+/// A single Modbus frame without the protocol specific framing and checksum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModbusFrame {
+    /// The address of the slave the frame is sent to or received from
+    pub slave_id: u8,
+    /// The function code, with bit 7 set for exception responses
+    pub function: u8,
+    /// The function specific data
+    pub payload: Vec<u8>,
+}
+
+impl ModbusFrame {
+    /// Creates a new frame out of its parts
+    pub fn new(slave_id: u8, function: u8, payload: Vec<u8>) -> Self {
+        ModbusFrame {
+            slave_id,
+            function,
+            payload,
+        }
+    }
+
+    /// Creates a request reading `count` coils starting at `start`
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if `count` is zero or more than 2000
+    pub fn read_coils(slave_id: u8, start: u16, count: u16) -> Result<Self, UberByteError> {
+        check_count(count as usize, MAX_READ_COILS)?;
+        Ok(ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::ReadCoils,
+            start,
+            count,
+        ))
+    }
+
+    /// Creates a request reading `count` discrete inputs starting at `start`
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if `count` is zero or more than 2000
+    pub fn read_discrete_inputs(
+        slave_id: u8,
+        start: u16,
+        count: u16,
+    ) -> Result<Self, UberByteError> {
+        check_count(count as usize, MAX_READ_COILS)?;
+        Ok(ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::ReadDiscreteInputs,
+            start,
+            count,
+        ))
+    }
+
+    /// Creates a request reading `count` holding registers starting at `start`
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if `count` is zero or more than 125
+    pub fn read_holding_registers(
+        slave_id: u8,
+        start: u16,
+        count: u16,
+    ) -> Result<Self, UberByteError> {
+        check_count(count as usize, MAX_READ_REGISTERS)?;
+        Ok(ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::ReadHoldingRegisters,
+            start,
+            count,
+        ))
+    }
+
+    /// Creates a request reading `count` input registers starting at `start`
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if `count` is zero or more than 125
+    pub fn read_input_registers(
+        slave_id: u8,
+        start: u16,
+        count: u16,
+    ) -> Result<Self, UberByteError> {
+        check_count(count as usize, MAX_READ_REGISTERS)?;
+        Ok(ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::ReadInputRegisters,
+            start,
+            count,
+        ))
+    }
+
+    /// Creates a request switching the coil at `address` on or off
+    pub fn write_single_coil(slave_id: u8, address: u16, on: bool) -> Self {
+        let value = if on { COIL_ON } else { 0 };
+        ModbusFrame::address_request(slave_id, FunctionCode::WriteSingleCoil, address, value)
+    }
+
+    /// Creates a request writing `value` into the holding register at `address`
+    pub fn write_single_register(slave_id: u8, address: u16, value: u16) -> Self {
+        ModbusFrame::address_request(slave_id, FunctionCode::WriteSingleRegister, address, value)
+    }
+
+    /// Creates a request setting the coils starting at `start` to the given states
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if there are no states or more than 1968
+    pub fn write_multiple_coils(
+        slave_id: u8,
+        start: u16,
+        states: &[bool],
+    ) -> Result<Self, UberByteError> {
+        check_count(states.len(), MAX_WRITE_COILS)?;
+        let packed = pack_coils(states);
+
+        let mut frame = ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::WriteMultipleCoils,
+            start,
+            states.len() as u16,
+        );
+        frame.payload.push(packed.len() as u8);
+        frame.payload.extend(packed.to_u8_vec());
+        Ok(frame)
+    }
+
+    /// Creates a request writing the values into the holding registers starting at `start`
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if there are no values or more than 123
+    pub fn write_multiple_registers(
+        slave_id: u8,
+        start: u16,
+        values: &[u16],
+    ) -> Result<Self, UberByteError> {
+        check_count(values.len(), MAX_WRITE_REGISTERS)?;
+
+        let mut frame = ModbusFrame::address_request(
+            slave_id,
+            FunctionCode::WriteMultipleRegisters,
+            start,
+            values.len() as u16,
+        );
+        frame.payload.push((values.len() * 2) as u8);
+        for value in values {
+            frame.payload.extend(value.to_be_bytes());
+        }
+        Ok(frame)
+    }
+
+    /// Creates the response to a read coils or read discrete inputs request
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if there are no states or more than 2000
+    pub fn read_bits_response(
+        slave_id: u8,
+        function: FunctionCode,
+        states: &[bool],
+    ) -> Result<Self, UberByteError> {
+        check_count(states.len(), MAX_READ_COILS)?;
+        let packed = pack_coils(states);
+
+        let mut payload = vec![packed.len() as u8];
+        payload.extend(packed.to_u8_vec());
+        Ok(ModbusFrame::new(slave_id, function as u8, payload))
+    }
+
+    /// Creates the response to a read holding registers or read input registers request
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if there are no values or more than 125
+    pub fn read_registers_response(
+        slave_id: u8,
+        function: FunctionCode,
+        values: &[u16],
+    ) -> Result<Self, UberByteError> {
+        check_count(values.len(), MAX_READ_REGISTERS)?;
+
+        let mut payload = vec![(values.len() * 2) as u8];
+        for value in values {
+            payload.extend(value.to_be_bytes());
+        }
+        Ok(ModbusFrame::new(slave_id, function as u8, payload))
+    }
+
+    /// Creates a exception response for the given function code
+    pub fn exception(slave_id: u8, function: u8, code: ExceptionCode) -> Self {
+        ModbusFrame::new(slave_id, function | EXCEPTION_FLAG, vec![code as u8])
+    }
+
+    /// Determines if the frame is a exception response
+    pub fn is_exception(&self) -> bool {
+        self.function & EXCEPTION_FLAG != 0
+    }
+
+    /// Returns the exception code of a exception response
+    ///
+    /// # Returns
+    ///
+    /// None if the frame is not a exception response
+    ///
+    /// An _InvalidLength_ error if the exception response holds no code
+    ///
+    /// A _CodeViolation_ error holding the code if it is unknown
+    pub fn exception_code(&self) -> Result<Option<ExceptionCode>, UberByteError> {
+        if !self.is_exception() {
+            return Ok(None);
+        }
+
+        let code = self.payload.first().ok_or(UberByteError::InvalidLength)?;
+        ExceptionCode::try_from(*code).map(Some)
+    }
+
+    /// Reads the coil or discrete input states from a read response
+    ///
+    /// # Returns
+    ///
+    /// The first `count` states stored in the response
+    ///
+    /// An _InvalidLength_ error if the response does not hold `count` states
+    pub fn bit_states(&self, count: usize) -> Result<Vec<bool>, UberByteError> {
+        let data = self.counted_data()?;
+        if data.len() * 8 < count {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        Ok(unpack_coils(&ByteArray::from(data), count))
+    }
+
+    /// Reads the register values from a read holding registers or read input registers response
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the byte count does not match the payload or is odd
+    pub fn registers(&self) -> Result<Vec<u16>, UberByteError> {
+        let data = self.counted_data()?;
+        if !data.len().is_multiple_of(2) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        Ok(data
+            .chunks(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect())
+    }
+
+    /// Converts the frame into a RTU frame with the CRC-16/MODBUS appended, low byte first
+    pub fn to_rtu(&self) -> ByteArray {
+        let mut frame = ByteArray::from(self.pdu_with_address());
+        let crc = Crc::CRC_16_MODBUS.checksum(&frame) as u16;
+        frame += ByteArray::from(crc.to_le_bytes().to_vec());
+        frame
+    }
+
+    /// Parses a RTU frame
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the frame is shorter than 4 bytes
+    ///
+    /// A _ChecksumMismatch_ error if the CRC does not match the content
+    pub fn from_rtu(frame: &ByteArray) -> Result<Self, UberByteError> {
+        if frame.len() < 4 {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let content = ByteArray::from(&frame[..frame.len() - 2]);
+        let expected = Crc::CRC_16_MODBUS.checksum(&content) as u16;
+        let actual = u16::from_le_bytes([
+            frame[frame.len() - 2].into_u8(),
+            frame[frame.len() - 1].into_u8(),
+        ]);
+        if expected != actual {
+            return Err(UberByteError::ChecksumMismatch);
+        }
+
+        let bytes = content.to_u8_vec();
+        Ok(ModbusFrame::new(bytes[0], bytes[1], bytes[2..].to_vec()))
+    }
+
+    /// Converts the frame into a ASCII frame
+    ///
+    /// # Returns
+    ///
+    /// The frame as upper case hex digits with the LRC appended, framed by `:` and CRLF
+    pub fn to_ascii(&self) -> String {
+        let mut bytes = self.pdu_with_address();
+        bytes.push(lrc(&bytes));
+
+        let hex: String = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        format!(":{}\r\n", hex)
+    }
+
+    /// Parses a ASCII frame
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidCharacter_ error holding the position of the first character that is
+    /// not part of a valid ASCII frame, including a missing `:` or CRLF
+    ///
+    /// An _InvalidLength_ error if the frame holds an odd number of hex digits or less than 3 bytes
+    ///
+    /// A _ChecksumMismatch_ error if the LRC does not match the content
+    pub fn from_ascii(frame: &str) -> Result<Self, UberByteError> {
+        if !frame.starts_with(':') {
+            return Err(UberByteError::InvalidCharacter(0));
+        }
+        if !frame.ends_with("\r\n") {
+            return Err(UberByteError::InvalidCharacter(frame.len()));
+        }
+
+        let digits = &frame.as_bytes()[1..frame.len() - 2];
+        if !digits.len().is_multiple_of(2) {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut bytes = vec![];
+        for (index, pair) in digits.chunks(2).enumerate() {
+            let mut value = 0;
+            for (offset, digit) in pair.iter().enumerate() {
+                let nibble = (*digit as char)
+                    .to_digit(16)
+                    .ok_or(UberByteError::InvalidCharacter(1 + index * 2 + offset))?;
+                value = (value << 4) | nibble as u8;
+            }
+            bytes.push(value);
+        }
+
+        if bytes.len() < 3 {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let checksum = bytes.pop().unwrap_or_default();
+        if lrc(&bytes) != checksum {
+            return Err(UberByteError::ChecksumMismatch);
+        }
+
+        Ok(ModbusFrame::new(bytes[0], bytes[1], bytes[2..].to_vec()))
+    }
+
+    fn address_request(slave_id: u8, function: FunctionCode, address: u16, value: u16) -> Self {
+        let mut payload = address.to_be_bytes().to_vec();
+        payload.extend(value.to_be_bytes());
+        ModbusFrame::new(slave_id, function as u8, payload)
+    }
+
+    fn pdu_with_address(&self) -> Vec<u8> {
+        let mut bytes = vec![self.slave_id, self.function];
+        bytes.extend(&self.payload);
+        bytes
+    }
+
+    /// Returns the data of a response that starts with a byte count
+    fn counted_data(&self) -> Result<&[u8], UberByteError> {
+        let (count, data) = self
+            .payload
+            .split_first()
+            .ok_or(UberByteError::InvalidLength)?;
+
+        match *count as usize == data.len() {
+            true => Ok(data),
+            false => Err(UberByteError::InvalidLength),
+        }
+    }
+}
+
+/// Checks that the number of coils or registers is within the protocol limit
+fn check_count(count: usize, max: usize) -> Result<(), UberByteError> {
+    match count == 0 || count > max {
+        true => Err(UberByteError::InvalidLength),
+        false => Ok(()),
+    }
+}
+
+/// Packs coil states into bytes, the first state into bit 0 of the first byte
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::protocols::modbus::pack_coils;
+/// use uberbyte::UberByte;
+///
+/// let packed = pack_coils(&[true, false, true]);
+///
+/// assert_eq!(UberByte::from(0b_0000_0101), packed[0]);
+/// ```
+pub fn pack_coils(states: &[bool]) -> ByteArray {
+    let mut packed = ByteArray::default();
+
+    for chunk in states.chunks(8) {
+        let mut byte = UberByte::MIN;
+        for (index, state) in chunk.iter().enumerate() {
+            if *state {
+                byte.set_mut(1 << index);
+            }
+        }
+        packed.add_mut(byte);
+    }
+
+    packed
+}
+
+/// Unpacks `count` coil states from the given bytes, starting with bit 0 of the first byte
+pub fn unpack_coils(packed: &ByteArray, count: usize) -> Vec<bool> {
+    packed[..]
+        .iter()
+        .flat_map(|byte| (0..8).map(|index| byte.is_bit_set(index)))
+        .take(count)
+        .collect()
+}
+
+/// Computes the longitudinal redundancy check used by Modbus ASCII
+///
+/// # Returns
+///
+/// The two's complement of the sum of all bytes
+pub fn lrc(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+        .wrapping_neg()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_holding_registers_rtu() {
+        let frame = ModbusFrame::read_holding_registers(0x11, 0x006B, 3).unwrap();
+
+        assert_eq!(
+            vec![0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87],
+            frame.to_rtu().to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn rtu_round_trip() {
+        let frame = ModbusFrame::write_multiple_registers(0x01, 0x0001, &[0x000A, 0x0102]).unwrap();
+
+        let parsed = ModbusFrame::from_rtu(&frame.to_rtu()).unwrap();
+
+        assert_eq!(frame, parsed);
+        assert_eq!(
+            vec![0x00, 0x01, 0x00, 0x02, 0x04, 0x00, 0x0A, 0x01, 0x02],
+            parsed.payload
+        );
+    }
+
+    #[test]
+    fn rtu_checksum_mismatch() {
+        let mut rtu = ModbusFrame::read_coils(0x01, 0, 8).unwrap().to_rtu();
+        rtu[3] = rtu[3].flip();
+
+        assert!(matches!(
+            ModbusFrame::from_rtu(&rtu),
+            Err(UberByteError::ChecksumMismatch)
+        ));
+        assert!(matches!(
+            ModbusFrame::from_rtu(&ByteArray::from(vec![0x01, 0x02, 0x03])),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn ascii_round_trip() {
+        let frame = ModbusFrame::write_single_coil(0xF7, 0x00AC, true);
+        let ascii = frame.to_ascii();
+
+        assert_eq!(":F70500ACFF0059\r\n", ascii);
+        assert_eq!(frame, ModbusFrame::from_ascii(&ascii).unwrap());
+    }
+
+    #[test]
+    fn ascii_errors() {
+        assert!(matches!(
+            ModbusFrame::from_ascii("1103006B00037E\r\n"),
+            Err(UberByteError::InvalidCharacter(0))
+        ));
+        assert!(matches!(
+            ModbusFrame::from_ascii(":1103006B00037E"),
+            Err(UberByteError::InvalidCharacter(15))
+        ));
+        assert!(matches!(
+            ModbusFrame::from_ascii(":1103006X00037E\r\n"),
+            Err(UberByteError::InvalidCharacter(8))
+        ));
+        assert!(matches!(
+            ModbusFrame::from_ascii(":1103006B00037F\r\n"),
+            Err(UberByteError::ChecksumMismatch)
+        ));
+    }
+
+    #[test]
+    fn coils() {
+        let states = [
+            true, false, true, true, false, false, true, true, true, true,
+        ];
+
+        let response =
+            ModbusFrame::read_bits_response(0x04, FunctionCode::ReadCoils, &states).unwrap();
+
+        assert_eq!(vec![0x02, 0b_1100_1101, 0b_0000_0011], response.payload);
+        assert_eq!(states.to_vec(), response.bit_states(10).unwrap());
+        assert!(response.bit_states(17).is_err());
+    }
+
+    #[test]
+    fn write_multiple_coils() {
+        let frame = ModbusFrame::write_multiple_coils(0x01, 0x0013, &[true, false, true]).unwrap();
+
+        assert_eq!(
+            vec![0x00, 0x13, 0x00, 0x03, 0x01, 0b_0000_0101],
+            frame.payload
+        );
+    }
+
+    #[test]
+    fn count_limits() {
+        let coils = [true; 2001];
+        let registers = [0u16; 126];
+
+        assert_eq!(
+            vec![0x00, 0x00, 0x07, 0xB0, 0xF6],
+            ModbusFrame::write_multiple_coils(0x01, 0, &coils[..1968])
+                .unwrap()
+                .payload[..5]
+        );
+        assert_eq!(
+            0xFA,
+            ModbusFrame::read_registers_response(
+                0x01,
+                FunctionCode::ReadHoldingRegisters,
+                &registers[..125]
+            )
+            .unwrap()
+            .payload[0]
+        );
+        for result in [
+            ModbusFrame::write_multiple_coils(0x01, 0, &coils[..1969]),
+            ModbusFrame::write_multiple_coils(0x01, 0, &[]),
+            ModbusFrame::read_bits_response(0x01, FunctionCode::ReadCoils, &coils),
+            ModbusFrame::write_multiple_registers(0x01, 0, &registers[..124]),
+            ModbusFrame::read_coils(0x01, 0, 0),
+            ModbusFrame::read_discrete_inputs(0x01, 0, 2001),
+            ModbusFrame::read_holding_registers(0x01, 0, 126),
+            ModbusFrame::read_input_registers(0x01, 0, 0),
+            ModbusFrame::read_registers_response(
+                0x01,
+                FunctionCode::ReadInputRegisters,
+                &registers,
+            ),
+        ] {
+            assert!(matches!(result, Err(UberByteError::InvalidLength)));
+        }
+        assert!(
+            ModbusFrame::read_bits_response(0x01, FunctionCode::ReadCoils, &coils[..2000]).is_ok()
+        );
+        assert!(ModbusFrame::read_discrete_inputs(0x01, 0, 2000).is_ok());
+        assert!(ModbusFrame::read_input_registers(0x01, 0, 125).is_ok());
+    }
+
+    #[test]
+    fn registers() {
+        let response = ModbusFrame::read_registers_response(
+            0x11,
+            FunctionCode::ReadHoldingRegisters,
+            &[0xAE41, 0x5652],
+        )
+        .unwrap();
+
+        assert_eq!(vec![0xAE41, 0x5652], response.registers().unwrap());
+
+        let broken = ModbusFrame::new(0x11, 0x03, vec![0x04, 0xAE, 0x41]);
+        assert!(broken.registers().is_err());
+    }
+
+    #[test]
+    fn exception() {
+        let frame = ModbusFrame::exception(0x0A, 0x03, ExceptionCode::IllegalDataAddress);
+        let parsed = ModbusFrame::from_rtu(&frame.to_rtu()).unwrap();
+
+        assert!(parsed.is_exception());
+        assert_eq!(0x83, parsed.function);
+        assert!(matches!(
+            parsed.exception_code(),
+            Ok(Some(ExceptionCode::IllegalDataAddress))
+        ));
+        assert!(matches!(
+            ModbusFrame::read_coils(1, 0, 1).unwrap().exception_code(),
+            Ok(None)
+        ));
+        assert!(matches!(
+            ModbusFrame::new(0x0A, 0x83, vec![0x07]).exception_code(),
+            Err(UberByteError::CodeViolation(0x07))
+        ));
+        assert!(matches!(
+            ModbusFrame::new(0x0A, 0x83, vec![]).exception_code(),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn lrc_known_value() {
+        assert_eq!(0x7E, lrc(&[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03]));
+    }
+}
@@ -0,0 +1,445 @@
+//! Reed-Solomon error correction over GF(256)
+//!
+//! The codec is systematic: the encoded codeword is the unchanged data followed
+//! by the parity symbols. With _n_ parity symbols any combination of _e_ errors
+//! and _f_ erasures (errors at known positions) can be corrected as long as
+//! `2 * e + f <= n`.
+//!
+//! ```rust
+//! use uberbyte::reed_solomon::ReedSolomon;
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let codec = ReedSolomon::new(4).unwrap();
+//! let mut codeword = codec.encode(&ByteArray::from(vec![1, 2, 3, 4])).unwrap();
+//!
+//! codeword[1] = UberByte::MAX;
+//! codeword[6] = UberByte::MAX;
+//!
+//! let data = codec.decode(&codeword).unwrap();
+//! assert_eq!(UberByte::from(2), data[1]);
+//! ```
+
+use crate::gf256::Gf256;
+use crate::{ByteArray, UberByte, UberByteError};
+
+/// The maximal length of a codeword including the parity symbols
+const MAX_CODEWORD_LENGTH: usize = 255;
+
+/// A Reed-Solomon codec with a fixed number of parity symbols
+#[derive(Debug, Clone)]
+pub struct ReedSolomon {
+    parity_symbols: usize,
+    generator: Vec<Gf256>,
+}
+
+impl ReedSolomon {
+    /// Creates a new codec that adds the given number of parity symbols
+    ///
+    /// # Returns
+    ///
+    /// An _InvalidLength_ error if the number of parity symbols is 0 or does not
+    /// leave room for at least one data symbol in a codeword
+    pub fn new(parity_symbols: usize) -> Result<Self, UberByteError> {
+        if parity_symbols == 0 || parity_symbols >= MAX_CODEWORD_LENGTH {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut generator = vec![Gf256::ONE];
+        for power in 0..parity_symbols {
+            generator = poly_mul(&generator, &[Gf256::ONE, Gf256::exp(power)]);
+        }
+
+        Ok(ReedSolomon {
+            parity_symbols,
+            generator,
+        })
+    }
+
+    /// Returns the number of parity symbols added to every codeword
+    pub fn parity_symbols(&self) -> usize {
+        self.parity_symbols
+    }
+
+    /// Encodes the given data into a codeword
+    ///
+    /// # Returns
+    ///
+    /// The data followed by the parity symbols
+    ///
+    /// An _InvalidLength_ error if the codeword would be longer than 255 bytes
+    pub fn encode(&self, data: &ByteArray) -> Result<ByteArray, UberByteError> {
+        if data.len() + self.parity_symbols > MAX_CODEWORD_LENGTH {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        let mut remainder: Vec<Gf256> = data
+            .clone()
+            .into_iter()
+            .map(Gf256::from)
+            .chain(std::iter::repeat_n(Gf256::ZERO, self.parity_symbols))
+            .collect();
+
+        // synthetic division by the monic generator polynomial
+        for index in 0..data.len() {
+            let coefficient = remainder[index];
+            if coefficient.is_zero() {
+                continue;
+            }
+            for (offset, factor) in self.generator.iter().enumerate().skip(1) {
+                remainder[index + offset] += *factor * coefficient;
+            }
+        }
+
+        let parity: Vec<UberByte> = remainder[data.len()..]
+            .iter()
+            .map(|symbol| UberByte::from(*symbol))
+            .collect();
+
+        Ok(data.add_range(parity))
+    }
+
+    /// Decodes the given codeword, correcting all errors if possible
+    ///
+    /// # Returns
+    ///
+    /// The corrected data without the parity symbols
+    ///
+    /// See [`ReedSolomon::decode_with_erasures`] for the possible errors
+    pub fn decode(&self, codeword: &ByteArray) -> Result<ByteArray, UberByteError> {
+        self.decode_with_erasures(codeword, &[])
+    }
+
+    /// Decodes the given codeword, treating the symbols at the given indexes as erased
+    ///
+    /// # Returns
+    ///
+    /// The corrected data without the parity symbols
+    ///
+    /// An _InvalidLength_ error if the codeword is longer than 255 bytes or does not
+    /// contain more symbols than parity symbols
+    ///
+    /// An _IndexOutOfRange_ error if a erasure index is outside of the codeword
+    ///
+    /// An _UncorrectableError_ error if the codeword contains more errors than can be corrected.
+    /// As a single codeword is decoded the index is always 0.
+    pub fn decode_with_erasures(
+        &self,
+        codeword: &ByteArray,
+        erasures: &[usize],
+    ) -> Result<ByteArray, UberByteError> {
+        let length = codeword.len();
+        if length > MAX_CODEWORD_LENGTH || length <= self.parity_symbols {
+            return Err(UberByteError::InvalidLength);
+        }
+        if erasures.iter().any(|position| *position >= length) {
+            return Err(UberByteError::IndexOutOfRange);
+        }
+        if erasures.len() > self.parity_symbols {
+            return Err(UberByteError::UncorrectableError(0));
+        }
+
+        let mut message: Vec<Gf256> = codeword.clone().into_iter().map(Gf256::from).collect();
+        for position in erasures {
+            message[*position] = Gf256::ZERO;
+        }
+
+        let syndromes = self.syndromes(&message);
+        if syndromes.iter().any(|s| !s.is_zero()) {
+            let forney = forney_syndromes(&syndromes, erasures, length);
+            let locator = self.error_locator(&forney, erasures.len())?;
+
+            let mut errata: Vec<usize> = erasures.to_vec();
+            errata.extend(find_errors(&locator, length)?);
+
+            correct_errata(&mut message, &syndromes, &errata)?;
+
+            if self.syndromes(&message).iter().any(|s| !s.is_zero()) {
+                return Err(UberByteError::UncorrectableError(0));
+            }
+        }
+
+        let data: Vec<UberByte> = message[..length - self.parity_symbols]
+            .iter()
+            .map(|symbol| UberByte::from(*symbol))
+            .collect();
+
+        Ok(ByteArray::from(&data[..]))
+    }
+
+    fn syndromes(&self, message: &[Gf256]) -> Vec<Gf256> {
+        (0..self.parity_symbols)
+            .map(|power| poly_eval(message, Gf256::exp(power)))
+            .collect()
+    }
+
+    /// Berlekamp-Massey search for the error locator polynomial
+    fn error_locator(
+        &self,
+        syndromes: &[Gf256],
+        erasure_count: usize,
+    ) -> Result<Vec<Gf256>, UberByteError> {
+        let mut locator = vec![Gf256::ONE];
+        let mut previous = vec![Gf256::ONE];
+
+        for index in 0..(self.parity_symbols - erasure_count) {
+            let mut delta = syndromes[index];
+            for offset in 1..locator.len() {
+                delta += locator[locator.len() - 1 - offset] * syndromes[index - offset];
+            }
+
+            previous.push(Gf256::ZERO);
+
+            if !delta.is_zero() {
+                if previous.len() > locator.len() {
+                    let scaled = poly_scale(&previous, delta);
+                    previous = poly_scale(&locator, Gf256::ONE / delta);
+                    locator = scaled;
+                }
+                locator = poly_add(&locator, &poly_scale(&previous, delta));
+            }
+        }
+
+        let leading_zeros = locator.iter().take_while(|c| c.is_zero()).count();
+        locator.drain(..leading_zeros);
+
+        let errors = locator.len().saturating_sub(1);
+        if errors * 2 + erasure_count > self.parity_symbols {
+            return Err(UberByteError::UncorrectableError(0));
+        }
+
+        Ok(locator)
+    }
+}
+
+/// Chien search for the positions of the errors described by the locator polynomial
+fn find_errors(locator: &[Gf256], length: usize) -> Result<Vec<usize>, UberByteError> {
+    let reversed: Vec<Gf256> = locator.iter().rev().copied().collect();
+    let positions: Vec<usize> = (0..length)
+        .filter(|power| poly_eval(&reversed, Gf256::exp(*power)).is_zero())
+        .map(|power| length - 1 - power)
+        .collect();
+
+    if positions.len() != locator.len() - 1 {
+        return Err(UberByteError::UncorrectableError(0));
+    }
+
+    Ok(positions)
+}
+
+/// Removes the influence of the erasures from the syndromes
+fn forney_syndromes(syndromes: &[Gf256], erasures: &[usize], length: usize) -> Vec<Gf256> {
+    let mut forney = syndromes.to_vec();
+
+    for position in erasures {
+        let x = Gf256::exp(length - 1 - position);
+        for index in 0..forney.len() - 1 {
+            forney[index] = forney[index] * x + forney[index + 1];
+        }
+    }
+
+    forney
+}
+
+/// Computes the error magnitudes using the Forney algorithm and applies them
+fn correct_errata(
+    message: &mut [Gf256],
+    syndromes: &[Gf256],
+    errata: &[usize],
+) -> Result<(), UberByteError> {
+    let coefficient_positions: Vec<usize> = errata.iter().map(|p| message.len() - 1 - p).collect();
+
+    let mut locator = vec![Gf256::ONE];
+    for position in &coefficient_positions {
+        locator = poly_mul(&locator, &[Gf256::exp(*position), Gf256::ONE]);
+    }
+
+    // error evaluator: syndromes * locator mod x^(errata + 1), with the syndromes in reversed order
+    let reversed_syndromes: Vec<Gf256> = std::iter::once(Gf256::ZERO)
+        .chain(syndromes.iter().copied())
+        .rev()
+        .collect();
+    let product = poly_mul(&reversed_syndromes, &locator);
+    let evaluator = &product[product.len().saturating_sub(locator.len())..];
+
+    let roots: Vec<Gf256> = coefficient_positions
+        .iter()
+        .map(|position| Gf256::exp(*position))
+        .collect();
+
+    for (index, root) in roots.iter().enumerate() {
+        let root_inverse = Gf256::ONE / *root;
+
+        let locator_derivative = roots
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .fold(Gf256::ONE, |acc, (_, other)| {
+                acc * (Gf256::ONE - root_inverse * *other)
+            });
+
+        if locator_derivative.is_zero() {
+            return Err(UberByteError::UncorrectableError(0));
+        }
+
+        let magnitude = *root * poly_eval(evaluator, root_inverse) / locator_derivative;
+        message[errata[index]] += magnitude;
+    }
+
+    Ok(())
+}
+
+fn poly_scale(polynomial: &[Gf256], factor: Gf256) -> Vec<Gf256> {
+    polynomial.iter().map(|c| *c * factor).collect()
+}
+
+fn poly_add(lhs: &[Gf256], rhs: &[Gf256]) -> Vec<Gf256> {
+    let length = lhs.len().max(rhs.len());
+    let mut sum = vec![Gf256::ZERO; length];
+
+    for (index, coefficient) in lhs.iter().enumerate() {
+        sum[index + length - lhs.len()] = *coefficient;
+    }
+    for (index, coefficient) in rhs.iter().enumerate() {
+        sum[index + length - rhs.len()] += *coefficient;
+    }
+
+    sum
+}
+
+fn poly_mul(lhs: &[Gf256], rhs: &[Gf256]) -> Vec<Gf256> {
+    let mut product = vec![Gf256::ZERO; lhs.len() + rhs.len() - 1];
+
+    for (j, r) in rhs.iter().enumerate() {
+        for (i, l) in lhs.iter().enumerate() {
+            product[i + j] += *l * *r;
+        }
+    }
+
+    product
+}
+
+/// Evaluates the polynomial, stored with the highest degree first, using Horner's method
+fn poly_eval(polynomial: &[Gf256], x: Gf256) -> Gf256 {
+    polynomial
+        .iter()
+        .fold(Gf256::ZERO, |acc, coefficient| acc * x + *coefficient)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MESSAGE: [u8; 16] = [
+        0x40, 0xD2, 0x75, 0x47, 0x76, 0x17, 0x32, 0x06, 0x27, 0x26, 0x96, 0xC6, 0xC6, 0x96, 0x70,
+        0xEC,
+    ];
+    const PARITY: [u8; 10] = [0xBC, 0x2A, 0x90, 0x13, 0x6B, 0xAF, 0xEF, 0xFD, 0x4B, 0xE0];
+
+    #[test]
+    fn encode_known_vector() {
+        let codec = ReedSolomon::new(10).unwrap();
+
+        let codeword = codec.encode(&ByteArray::from(MESSAGE.to_vec())).unwrap();
+
+        let mut expected = MESSAGE.to_vec();
+        expected.extend_from_slice(&PARITY);
+        assert_eq!(expected, codeword.to_u8_vec());
+    }
+
+    #[test]
+    fn decode_without_errors() {
+        let codec = ReedSolomon::new(10).unwrap();
+        let codeword = codec.encode(&ByteArray::from(MESSAGE.to_vec())).unwrap();
+
+        assert_eq!(
+            MESSAGE.to_vec(),
+            codec.decode(&codeword).unwrap().to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let codec = ReedSolomon::new(10).unwrap();
+        let mut codeword = codec.encode(&ByteArray::from(MESSAGE.to_vec())).unwrap();
+
+        for position in [0, 3, 10, 17, 25] {
+            codeword[position] = codeword[position].flip();
+        }
+
+        assert_eq!(
+            MESSAGE.to_vec(),
+            codec.decode(&codeword).unwrap().to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn decode_erasures() {
+        let codec = ReedSolomon::new(10).unwrap();
+        let mut codeword = codec.encode(&ByteArray::from(MESSAGE.to_vec())).unwrap();
+
+        let erasures: Vec<usize> = (0..10).collect();
+        for position in &erasures {
+            codeword[*position] = UberByte::MIN;
+        }
+
+        assert_eq!(
+            MESSAGE.to_vec(),
+            codec
+                .decode_with_erasures(&codeword, &erasures)
+                .unwrap()
+                .to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn decode_errors_and_erasures() {
+        let codec = ReedSolomon::new(10).unwrap();
+        let mut codeword = codec.encode(&ByteArray::from(MESSAGE.to_vec())).unwrap();
+
+        // 4 erasures and 3 errors use up all 10 parity symbols
+        let erasures = [1, 5, 9, 20];
+        for position in erasures.iter().chain([2, 12, 24].iter()) {
+            codeword[*position] = UberByte::from(0x55);
+        }
+
+        assert_eq!(
+            MESSAGE.to_vec(),
+            codec
+                .decode_with_erasures(&codeword, &erasures)
+                .unwrap()
+                .to_u8_vec()
+        );
+    }
+
+    #[test]
+    fn decode_too_many_errors() {
+        let codec = ReedSolomon::new(4).unwrap();
+        let mut codeword = codec.encode(&ByteArray::from(vec![1, 2, 3, 4])).unwrap();
+
+        for position in 0..3 {
+            codeword[position] = codeword[position].flip();
+        }
+
+        assert!(codec.decode(&codeword).is_err());
+    }
+
+    #[test]
+    fn invalid_lengths() {
+        assert!(ReedSolomon::new(0).is_err());
+        assert!(ReedSolomon::new(255).is_err());
+
+        let codec = ReedSolomon::new(10).unwrap();
+        assert!(matches!(
+            codec.encode(&ByteArray::from(vec![0; 246])),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            codec.decode(&ByteArray::from(vec![0; 10])),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            codec.decode_with_erasures(&ByteArray::from(vec![0; 20]), &[20]),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+    }
+}
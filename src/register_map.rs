@@ -0,0 +1,766 @@
+//! Description of address indexed register files and a simulated device enforcing them
+//!
+//! ```rust
+//! use uberbyte::register_map::{Access, Field, Register, RegisterMap, RegisterWidth, SimulatedDevice};
+//!
+//! let mut map = RegisterMap::default();
+//! map.add(
+//!     Register::new("STATUS", 0x04, RegisterWidth::Bits8)
+//!         .with_field(Field::new("READY", 0, 1, Access::ReadOnly))
+//!         .with_field(Field::new("ERROR", 7, 1, Access::WriteOneToClear)),
+//! )
+//! .unwrap();
+//!
+//! let mut device = SimulatedDevice::new(map);
+//! // the "hardware" raises the error flag
+//! device.poke(0x04, 0b_1000_0001).unwrap();
+//!
+//! // the driver acknowledges the error by writing a 1 into it
+//! device.write(0x04, 0b_1000_0000).unwrap();
+//! assert_eq!(0b_0000_0001, device.read(0x04).unwrap());
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::{ByteArray, Endianness, UberByte, UberByteError};
+
+/// The supported register widths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterWidth {
+    /// A single byte register
+    Bits8,
+    /// A two byte register
+    Bits16,
+    /// A four byte register
+    Bits32,
+}
+
+impl RegisterWidth {
+    /// Returns the number of bits in a register of this width
+    pub fn bits(&self) -> u32 {
+        match self {
+            RegisterWidth::Bits8 => 8,
+            RegisterWidth::Bits16 => 16,
+            RegisterWidth::Bits32 => 32,
+        }
+    }
+
+    /// Returns the number of bytes in a register of this width
+    pub fn bytes(&self) -> u32 {
+        self.bits() / 8
+    }
+
+    /// Returns the mask with all bits of a register of this width set
+    pub fn mask(&self) -> u32 {
+        u32::MAX >> (32 - self.bits())
+    }
+}
+
+/// Defines how the bits of a register react to reads and writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Access {
+    /// The bits can be read and written
+    #[default]
+    ReadWrite,
+    /// The bits can only be read, writes are ignored
+    ReadOnly,
+    /// The bits can only be written, reads return 0
+    WriteOnly,
+    /// Writing a 1 clears the bit, writing a 0 has no effect
+    WriteOneToClear,
+    /// Reading returns the bits and clears them afterwards, writes are ignored
+    ReadToClear,
+}
+
+/// A named range of bits inside a register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the field
+    pub name: String,
+    /// The index of the lowest bit of the field
+    pub offset: u32,
+    /// The number of bits in the field
+    pub width: u32,
+    /// How the bits of the field react to reads and writes
+    pub access: Access,
+}
+
+impl Field {
+    /// Creates a new field covering `width` bits starting at bit `offset`
+    pub fn new(name: &str, offset: u32, width: u32, access: Access) -> Self {
+        Field {
+            name: name.to_string(),
+            offset,
+            width,
+            access,
+        }
+    }
+
+    /// Returns the mask of all bits covered by the field
+    ///
+    /// # Remarks
+    ///
+    /// Bits past bit 31 are dropped, so a field with a width of 0 or an offset of 32
+    /// or more has an empty mask
+    pub fn mask(&self) -> u32 {
+        let bits = match self.width {
+            0 => 0,
+            width => u32::MAX >> 32u32.saturating_sub(width),
+        };
+        bits.checked_shl(self.offset).unwrap_or(0)
+    }
+}
+
+/// The description of a single register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Register {
+    /// The name of the register
+    pub name: String,
+    /// The address of the register
+    pub address: u32,
+    /// The width of the register
+    pub width: RegisterWidth,
+    /// The value of the register after a reset
+    pub reset_value: u32,
+    /// The access of all bits not covered by a field
+    pub access: Access,
+    /// The fields of the register
+    pub fields: Vec<Field>,
+}
+
+impl Register {
+    /// Creates a new read write register with a reset value of 0 and no fields
+    pub fn new(name: &str, address: u32, width: RegisterWidth) -> Self {
+        Register {
+            name: name.to_string(),
+            address,
+            width,
+            reset_value: 0,
+            access: Access::default(),
+            fields: vec![],
+        }
+    }
+
+    /// Returns the register with the given reset value
+    pub fn with_reset_value(mut self, reset_value: u32) -> Self {
+        self.reset_value = reset_value;
+        self
+    }
+
+    /// Returns the register with the given access for all bits not covered by a field
+    pub fn with_access(mut self, access: Access) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// Returns the register with the field added
+    pub fn with_field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Looks up the field with the given name
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns the mask of all bits with the given access
+    pub fn access_mask(&self, access: Access) -> u32 {
+        let covered = self
+            .fields
+            .iter()
+            .fold(0, |mask, field| mask | field.mask());
+        let mut mask = match self.access == access {
+            true => !covered & self.width.mask(),
+            false => 0,
+        };
+
+        for field in self.fields.iter().filter(|field| field.access == access) {
+            mask |= field.mask();
+        }
+        mask
+    }
+
+    fn validate(&self) -> Result<(), UberByteError> {
+        if self.reset_value & !self.width.mask() != 0 {
+            return Err(UberByteError::ValueOverflow);
+        }
+
+        let mut covered = 0;
+        for field in &self.fields {
+            if field.width == 0 || field.offset.saturating_add(field.width) > self.width.bits() {
+                return Err(UberByteError::ValueOverflow);
+            }
+            if covered & field.mask() != 0 {
+                return Err(UberByteError::AddressOverlap(self.address));
+            }
+            covered |= field.mask();
+        }
+
+        Ok(())
+    }
+}
+
+/// A collection of registers indexed by their address
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegisterMap {
+    registers: BTreeMap<u32, Register>,
+}
+
+impl RegisterMap {
+    /// Adds the register to the map
+    ///
+    /// # Returns
+    ///
+    /// An _AddressOverlap_ error with the first shared address if the bytes of the register
+    /// overlap an existing register, or if two fields of the register share a bit
+    ///
+    /// A _ValueOverflow_ error if the reset value or a field does not fit into the register,
+    /// or if the register does not fit into the 32 bit address space
+    pub fn add(&mut self, register: Register) -> Result<(), UberByteError> {
+        register.validate()?;
+
+        let end = end_address(&register);
+        if end > u32::MAX as u64 + 1 {
+            return Err(UberByteError::ValueOverflow);
+        }
+        let before = self
+            .registers
+            .range(..=register.address)
+            .next_back()
+            .filter(|(_, existing)| end_address(existing) > register.address as u64)
+            .map(|_| register.address);
+        let after = self
+            .registers
+            .range(register.address..)
+            .next()
+            .filter(|(address, _)| (**address as u64) < end)
+            .map(|(address, _)| *address);
+        if let Some(overlap) = before.or(after) {
+            return Err(UberByteError::AddressOverlap(overlap));
+        }
+
+        self.registers.insert(register.address, register);
+        Ok(())
+    }
+
+    /// Looks up the register at the given address
+    pub fn get(&self, address: u32) -> Option<&Register> {
+        self.registers.get(&address)
+    }
+
+    /// Looks up the register with the given name
+    pub fn by_name(&self, name: &str) -> Option<&Register> {
+        self.registers
+            .values()
+            .find(|register| register.name == name)
+    }
+
+    /// Returns all registers ordered by their address
+    pub fn registers(&self) -> impl Iterator<Item = &Register> {
+        self.registers.values()
+    }
+
+    /// Returns the number of registers in the map
+    pub fn len(&self) -> usize {
+        self.registers.len()
+    }
+
+    /// Determines if the map contains no registers
+    pub fn is_empty(&self) -> bool {
+        self.registers.is_empty()
+    }
+}
+
+/// Returns the address directly after the last byte of the register
+fn end_address(register: &Register) -> u64 {
+    register.address as u64 + register.width.bytes() as u64
+}
+
+/// A in memory device that enforces the access rules of a register map
+///
+/// Driver code uses [`SimulatedDevice::read`] and [`SimulatedDevice::write`] just like
+/// it would access the real hardware. Tests use [`SimulatedDevice::peek`] and
+/// [`SimulatedDevice::poke`] to inspect and change the raw register content
+/// without triggering any access side effects.
+///
+/// The content of each register is kept as UberBytes, least significant byte first,
+/// and the access rules are applied to every byte on its own.
+#[derive(Debug, Clone)]
+pub struct SimulatedDevice {
+    map: RegisterMap,
+    values: BTreeMap<u32, ByteArray>,
+}
+
+impl SimulatedDevice {
+    /// Creates a new device with all registers set to their reset value
+    pub fn new(map: RegisterMap) -> Self {
+        let mut device = SimulatedDevice {
+            map,
+            values: BTreeMap::new(),
+        };
+        device.reset();
+        device
+    }
+
+    /// Returns the register map of the device
+    pub fn map(&self) -> &RegisterMap {
+        &self.map
+    }
+
+    /// Sets all registers back to their reset value
+    pub fn reset(&mut self) {
+        self.values = self
+            .map
+            .registers()
+            .map(|register| {
+                let content = to_bytes(register.reset_value, register.width);
+                (register.address, content)
+            })
+            .collect();
+    }
+
+    /// Reads the register at the given address like the driver would
+    ///
+    /// # Returns
+    ///
+    /// The register value with all write only bits cleared. Read to clear bits are
+    /// returned and then cleared in the register.
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    pub fn read(&mut self, address: u32) -> Result<u32, UberByteError> {
+        Ok(to_value(&self.read_content(address)?))
+    }
+
+    /// Writes the register at the given address like the driver would
+    ///
+    /// # Returns
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    ///
+    /// A _ValueOverflow_ error if the value does not fit into the register
+    pub fn write(&mut self, address: u32, value: u32) -> Result<(), UberByteError> {
+        let width = self.register(address)?.width;
+        if value & !width.mask() != 0 {
+            return Err(UberByteError::ValueOverflow);
+        }
+
+        self.write_content(address, &to_bytes(value, width))
+    }
+
+    /// Reads the value of a single field like the driver would
+    ///
+    /// # Remarks
+    ///
+    /// The whole register is read, so read to clear bits of other fields are cleared as well
+    ///
+    /// # Returns
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    ///
+    /// An _IndexOutOfRange_ error if the register has no field with the given name
+    pub fn read_field(&mut self, address: u32, name: &str) -> Result<u32, UberByteError> {
+        let field = self.field(address, name)?;
+        let value = self.read(address)?;
+
+        Ok((value & field.mask()) >> field.offset)
+    }
+
+    /// Writes the value of a single field, leaving all other fields unchanged
+    ///
+    /// # Remarks
+    ///
+    /// Zeros are written into the write one to clear bits of all other fields
+    ///
+    /// # Returns
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    ///
+    /// An _IndexOutOfRange_ error if the register has no field with the given name
+    ///
+    /// A _ValueOverflow_ error if the value does not fit into the field
+    pub fn write_field(
+        &mut self,
+        address: u32,
+        name: &str,
+        value: u32,
+    ) -> Result<(), UberByteError> {
+        let field = self.field(address, name)?;
+        let shifted = value
+            .checked_shl(field.offset)
+            .filter(|shifted| shifted >> field.offset == value && shifted & !field.mask() == 0)
+            .ok_or(UberByteError::ValueOverflow)?;
+
+        let register = self.register(address)?;
+        let clear_on_one = register.access_mask(Access::WriteOneToClear);
+        let current = self.peek(address)? & !clear_on_one & !field.mask();
+
+        self.write(address, current | shifted)
+    }
+
+    /// Reads the register and returns it as bytes in the given order
+    ///
+    /// # Returns
+    ///
+    /// As many bytes as the register is wide, with the same side effects as [`SimulatedDevice::read`]
+    pub fn read_bytes(
+        &mut self,
+        address: u32,
+        endianness: Endianness,
+    ) -> Result<ByteArray, UberByteError> {
+        let content = self.read_content(address)?;
+
+        Ok(ordered(&content, endianness))
+    }
+
+    /// Writes the register from bytes in the given order like the driver would
+    ///
+    /// # Returns
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    ///
+    /// An _InvalidLength_ error if the number of bytes does not match the register width
+    pub fn write_bytes(
+        &mut self,
+        address: u32,
+        bytes: &ByteArray,
+        endianness: Endianness,
+    ) -> Result<(), UberByteError> {
+        if bytes.len() != self.register(address)?.width.bytes() as usize {
+            return Err(UberByteError::InvalidLength);
+        }
+
+        self.write_content(address, &ordered(bytes, endianness))
+    }
+
+    /// Returns the raw register content without any side effects
+    pub fn peek(&self, address: u32) -> Result<u32, UberByteError> {
+        self.values
+            .get(&address)
+            .map(to_value)
+            .ok_or(UberByteError::UnknownAddress(address))
+    }
+
+    /// Sets the raw register content without any access checks, simulating the hardware
+    ///
+    /// # Returns
+    ///
+    /// An _UnknownAddress_ error if no register exists at the address
+    ///
+    /// A _ValueOverflow_ error if the value does not fit into the register
+    pub fn poke(&mut self, address: u32, value: u32) -> Result<(), UberByteError> {
+        let width = self.register(address)?.width;
+        if value & !width.mask() != 0 {
+            return Err(UberByteError::ValueOverflow);
+        }
+
+        self.values.insert(address, to_bytes(value, width));
+        Ok(())
+    }
+
+    /// Reads the content least significant byte first and clears the read to clear bits
+    fn read_content(&mut self, address: u32) -> Result<ByteArray, UberByteError> {
+        let register = self.register(address)?;
+        let write_only = register.access_mask(Access::WriteOnly);
+        let read_to_clear = register.access_mask(Access::ReadToClear);
+
+        let content = self.content_mut(address)?;
+        let mut read = ByteArray::new();
+        for index in 0..content.len() {
+            read.add_mut(content[index].clear(mask_byte(write_only, index)));
+            content[index].clear_mut(mask_byte(read_to_clear, index));
+        }
+        Ok(read)
+    }
+
+    /// Writes bytes given least significant byte first into the writable bits
+    fn write_content(&mut self, address: u32, bytes: &ByteArray) -> Result<(), UberByteError> {
+        let register = self.register(address)?;
+        let writable =
+            register.access_mask(Access::ReadWrite) | register.access_mask(Access::WriteOnly);
+        let clear_on_one = register.access_mask(Access::WriteOneToClear);
+
+        let content = self.content_mut(address)?;
+        for index in 0..content.len() {
+            let written = bytes[index].into_u8();
+            content[index] = content[index]
+                .clear(mask_byte(writable, index))
+                .set(written & mask_byte(writable, index))
+                .clear(written & mask_byte(clear_on_one, index));
+        }
+        Ok(())
+    }
+
+    fn register(&self, address: u32) -> Result<&Register, UberByteError> {
+        self.map
+            .get(address)
+            .ok_or(UberByteError::UnknownAddress(address))
+    }
+
+    fn content_mut(&mut self, address: u32) -> Result<&mut ByteArray, UberByteError> {
+        self.values
+            .get_mut(&address)
+            .ok_or(UberByteError::UnknownAddress(address))
+    }
+
+    fn field(&self, address: u32, name: &str) -> Result<Field, UberByteError> {
+        self.register(address)?
+            .field(name)
+            .cloned()
+            .ok_or(UberByteError::IndexOutOfRange)
+    }
+}
+
+/// Splits the value into the bytes of a register, least significant byte first
+fn to_bytes(value: u32, width: RegisterWidth) -> ByteArray {
+    ByteArray::from(&value.to_le_bytes()[..width.bytes() as usize])
+}
+
+/// Joins the bytes of a register, least significant byte first, into a value
+fn to_value(content: &ByteArray) -> u32 {
+    content[..]
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | byte.into_u8() as u32)
+}
+
+/// Converts between the least significant byte first content and the given order
+fn ordered(bytes: &ByteArray, endianness: Endianness) -> ByteArray {
+    match endianness {
+        Endianness::Big => {
+            let reversed: Vec<UberByte> = bytes[..].iter().rev().copied().collect();
+            ByteArray::from(&reversed[..])
+        }
+        Endianness::Little => bytes.clone(),
+    }
+}
+
+/// Returns the part of the mask covering the register byte with the given index
+fn mask_byte(mask: u32, index: usize) -> u8 {
+    (mask >> (index * 8)) as u8
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn device() -> SimulatedDevice {
+        let mut map = RegisterMap::default();
+        map.add(
+            Register::new("CONTROL", 0x00, RegisterWidth::Bits16)
+                .with_reset_value(0x0100)
+                .with_field(Field::new("ENABLE", 0, 1, Access::ReadWrite))
+                .with_field(Field::new("MODE", 1, 3, Access::ReadWrite))
+                .with_field(Field::new("VERSION", 8, 4, Access::ReadOnly))
+                .with_field(Field::new("RESET", 15, 1, Access::WriteOnly)),
+        )
+        .unwrap();
+        map.add(
+            Register::new("IRQ", 0x04, RegisterWidth::Bits8)
+                .with_field(Field::new("RX", 0, 1, Access::WriteOneToClear))
+                .with_field(Field::new("TX", 1, 1, Access::WriteOneToClear))
+                .with_field(Field::new("EVENTS", 4, 4, Access::ReadToClear)),
+        )
+        .unwrap();
+        map.add(Register::new("DATA", 0x08, RegisterWidth::Bits32))
+            .unwrap();
+
+        SimulatedDevice::new(map)
+    }
+
+    #[test]
+    fn reset_values() {
+        let mut device = device();
+
+        assert_eq!(0x0100, device.read(0x00).unwrap());
+        device.write(0x08, 0xDEAD_BEEF).unwrap();
+        device.reset();
+        assert_eq!(0, device.read(0x08).unwrap());
+    }
+
+    #[test]
+    fn read_only_and_write_only() {
+        let mut device = device();
+
+        device.write(0x00, 0x8F0B).unwrap();
+
+        // VERSION keeps its reset value, RESET is stored but reads back as 0
+        assert_eq!(0x810B, device.peek(0x00).unwrap());
+        assert_eq!(0x010B, device.read(0x00).unwrap());
+    }
+
+    #[test]
+    fn write_one_to_clear() {
+        let mut device = device();
+        device.poke(0x04, 0b_0000_0011).unwrap();
+
+        device.write(0x04, 0b_0000_0001).unwrap();
+
+        assert_eq!(0b_0000_0010, device.read(0x04).unwrap());
+    }
+
+    #[test]
+    fn read_to_clear() {
+        let mut device = device();
+        device.poke(0x04, 0b_1010_0001).unwrap();
+
+        assert_eq!(0b_1010_0001, device.read(0x04).unwrap());
+        assert_eq!(0b_0000_0001, device.read(0x04).unwrap());
+
+        // writes do not touch read to clear bits
+        device.write(0x04, 0b_1111_0000).unwrap();
+        assert_eq!(0b_0000_0001, device.peek(0x04).unwrap());
+    }
+
+    #[test]
+    fn fields() {
+        let mut device = device();
+        device.poke(0x04, 0b_0000_0011).unwrap();
+
+        device.write_field(0x00, "MODE", 0b_101).unwrap();
+        device.write_field(0x04, "TX", 1).unwrap();
+
+        assert_eq!(0b_101, device.read_field(0x00, "MODE").unwrap());
+        assert_eq!(1, device.read_field(0x00, "VERSION").unwrap());
+        // writing TX must not clear RX
+        assert_eq!(1, device.read_field(0x04, "RX").unwrap());
+        assert_eq!(0, device.read_field(0x04, "TX").unwrap());
+
+        assert!(matches!(
+            device.write_field(0x00, "MODE", 0b_1000),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert!(matches!(
+            device.read_field(0x00, "MISSING"),
+            Err(UberByteError::IndexOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn read_bytes() {
+        let mut device = device();
+        device.write(0x08, 0x1234_5678).unwrap();
+
+        let big = device.read_bytes(0x08, Endianness::Big).unwrap();
+        let little = device.read_bytes(0x00, Endianness::Little).unwrap();
+
+        assert_eq!(4, big.len());
+        assert_eq!(0x12, big[0].into_u8());
+        assert_eq!(2, little.len());
+        assert_eq!(0x01, little[1].into_u8());
+    }
+
+    #[test]
+    fn write_bytes() {
+        let mut device = device();
+
+        device
+            .write_bytes(
+                0x08,
+                &ByteArray::from(vec![0x12, 0x34, 0x56, 0x78]),
+                Endianness::Big,
+            )
+            .unwrap();
+        // VERSION is read only and keeps its reset value
+        device
+            .write_bytes(0x00, &ByteArray::from(vec![0x0B, 0x0F]), Endianness::Little)
+            .unwrap();
+
+        assert_eq!(0x1234_5678, device.peek(0x08).unwrap());
+        assert_eq!(0x010B, device.peek(0x00).unwrap());
+        assert!(matches!(
+            device.write_bytes(0x04, &ByteArray::from(vec![0, 0]), Endianness::Big),
+            Err(UberByteError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn unknown_address_and_overflow() {
+        let mut device = device();
+
+        assert!(matches!(
+            device.read(0x02),
+            Err(UberByteError::UnknownAddress(0x02))
+        ));
+        assert!(matches!(
+            device.write(0x04, 0x100),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert!(matches!(
+            device.poke(0x00, 0x1_0000),
+            Err(UberByteError::ValueOverflow)
+        ));
+    }
+
+    #[test]
+    fn field_mask() {
+        assert_eq!(0b_1110, Field::new("X", 1, 3, Access::ReadWrite).mask());
+        assert_eq!(0, Field::new("X", 4, 0, Access::ReadWrite).mask());
+        assert_eq!(0, Field::new("X", 32, 1, Access::ReadWrite).mask());
+        assert_eq!(
+            0x8000_0000,
+            Field::new("X", 31, 4, Access::ReadWrite).mask()
+        );
+        assert_eq!(u32::MAX, Field::new("X", 0, 40, Access::ReadWrite).mask());
+    }
+
+    #[test]
+    fn invalid_map() {
+        let mut map = RegisterMap::default();
+        map.add(Register::new("A", 0x00, RegisterWidth::Bits8))
+            .unwrap();
+
+        assert!(matches!(
+            map.add(Register::new("B", 0x00, RegisterWidth::Bits8)),
+            Err(UberByteError::AddressOverlap(0x00))
+        ));
+        assert!(matches!(
+            map.add(
+                Register::new("C", 0x01, RegisterWidth::Bits8)
+                    .with_field(Field::new("X", 0, 4, Access::ReadWrite))
+                    .with_field(Field::new("Y", 3, 2, Access::ReadWrite))
+            ),
+            Err(UberByteError::AddressOverlap(0x01))
+        ));
+        assert!(matches!(
+            map.add(
+                Register::new("D", 0x02, RegisterWidth::Bits8).with_field(Field::new(
+                    "X",
+                    6,
+                    4,
+                    Access::ReadWrite
+                ))
+            ),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert_eq!(1, map.len());
+        assert_eq!(Some(0x00), map.by_name("A").map(|r| r.address));
+    }
+
+    #[test]
+    fn overlapping_registers() {
+        let mut map = RegisterMap::default();
+        map.add(Register::new("WIDE", 0x00, RegisterWidth::Bits16))
+            .unwrap();
+        map.add(Register::new("LATER", 0x04, RegisterWidth::Bits8))
+            .unwrap();
+
+        assert!(matches!(
+            map.add(Register::new("INSIDE", 0x01, RegisterWidth::Bits8)),
+            Err(UberByteError::AddressOverlap(0x01))
+        ));
+        assert!(matches!(
+            map.add(Register::new("ACROSS", 0x02, RegisterWidth::Bits32)),
+            Err(UberByteError::AddressOverlap(0x04))
+        ));
+        assert!(matches!(
+            map.add(Register::new("END", 0xFFFF_FFFE, RegisterWidth::Bits32)),
+            Err(UberByteError::ValueOverflow)
+        ));
+        map.add(Register::new("BETWEEN", 0x02, RegisterWidth::Bits16))
+            .unwrap();
+        map.add(Register::new("LAST", 0xFFFF_FFFC, RegisterWidth::Bits32))
+            .unwrap();
+        assert_eq!(4, map.len());
+    }
+}
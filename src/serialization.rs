@@ -0,0 +1,271 @@
+//! Serde support for [`UberByte`] and [`ByteArray`], enabled by the `serde` feature
+//!
+//! Human readable formats like JSON or TOML store an [`UberByte`] as a `"0b..."` string and
+//! a [`ByteArray`] as a hex string. Binary formats store them as an integer and as raw bytes.
+//! Deserializing accepts both representations, so fixtures may also use plain numbers.
+//!
+//! The modules in here can be used with `#[serde(with = "...")]` to always use one
+//! representation, regardless of the format.
+//!
+//! ```rust
+//! use uberbyte::{ByteArray, UberByte};
+//!
+//! let byte: UberByte = serde_json::from_str("\"0xA5\"").unwrap();
+//! let frame = ByteArray::from(vec![0x7E, 0x01]);
+//!
+//! assert_eq!("\"0b10100101\"", serde_json::to_string(&byte).unwrap());
+//! assert_eq!("\"7E01\"", serde_json::to_string(&frame).unwrap());
+//! ```
+
+use std::fmt;
+
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ByteArray, UberByte};
+
+impl Serialize for UberByte {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => as_binary_string::serialize(self, serializer),
+            false => as_integer::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UberByte {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_any(UberByteVisitor),
+            false => deserializer.deserialize_u8(UberByteVisitor),
+        }
+    }
+}
+
+impl Serialize for ByteArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => as_hex::serialize(self, serializer),
+            false => as_bytes::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_any(ByteArrayVisitor),
+            false => deserializer.deserialize_byte_buf(ByteArrayVisitor),
+        }
+    }
+}
+
+struct UberByteVisitor;
+
+impl<'de> Visitor<'de> for UberByteVisitor {
+    type Value = UberByte;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer between 0 and 255 or a string like \"0b1010_0101\"")
+    }
+
+    fn visit_u64<E: Error>(self, value: u64) -> Result<UberByte, E> {
+        u8::try_from(value)
+            .map(UberByte::from)
+            .map_err(|_| E::invalid_value(Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_i64<E: Error>(self, value: i64) -> Result<UberByte, E> {
+        u8::try_from(value)
+            .map(UberByte::from)
+            .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<UberByte, E> {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+struct ByteArrayVisitor;
+
+impl<'de> Visitor<'de> for ByteArrayVisitor {
+    type Value = ByteArray;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string, bytes or a sequence of bytes")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<ByteArray, E> {
+        let digits: String = value.split_whitespace().collect();
+        ByteArray::from_base16(&digits).map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<ByteArray, E> {
+        Ok(ByteArray::from(value))
+    }
+
+    fn visit_byte_buf<E: Error>(self, value: Vec<u8>) -> Result<ByteArray, E> {
+        Ok(ByteArray::from(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteArray, A::Error> {
+        let mut bytes = ByteArray::default();
+        while let Some(byte) = seq.next_element::<UberByte>()? {
+            bytes.add_mut(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+/// Stores an [`UberByte`] as an integer
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+/// use uberbyte::UberByte;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Status {
+///     #[serde(with = "uberbyte::serialization::as_integer")]
+///     flags: UberByte,
+/// }
+///
+/// let status = Status { flags: UberByte::from(0x81) };
+///
+/// assert_eq!("{\"flags\":129}", serde_json::to_string(&status).unwrap());
+/// ```
+pub mod as_integer {
+    use super::*;
+
+    /// Serializes the byte as an integer
+    pub fn serialize<S: Serializer>(byte: &UberByte, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(byte.into_u8())
+    }
+
+    /// Deserializes the byte from an integer
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UberByte, D::Error> {
+        u8::deserialize(deserializer).map(UberByte::from)
+    }
+}
+
+/// Stores an [`UberByte`] as a `"0b..."` string
+pub mod as_binary_string {
+    use super::*;
+
+    /// Serializes the byte as a string of 8 binary digits prefixed with `0b`
+    pub fn serialize<S: Serializer>(byte: &UberByte, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:#010b}", byte))
+    }
+
+    /// Deserializes the byte from any string [`UberByte`] can be parsed from
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UberByte, D::Error> {
+        deserializer.deserialize_str(UberByteVisitor)
+    }
+}
+
+/// Stores a [`ByteArray`] as an upper case hex string
+///
+/// # Remarks
+///
+/// Whitespace between the hex digits is ignored when deserializing
+pub mod as_hex {
+    use super::*;
+
+    /// Serializes the bytes as a hex string
+    pub fn serialize<S: Serializer>(bytes: &ByteArray, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&bytes.to_base16())
+    }
+
+    /// Deserializes the bytes from a hex string
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ByteArray, D::Error> {
+        deserializer.deserialize_str(ByteArrayVisitor)
+    }
+}
+
+/// Stores a [`ByteArray`] as raw bytes
+///
+/// # Remarks
+///
+/// Formats without a byte type, like JSON, store the bytes as a sequence of integers
+pub mod as_bytes {
+    use super::*;
+
+    /// Serializes the bytes as raw bytes
+    pub fn serialize<S: Serializer>(bytes: &ByteArray, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw = bytes.to_u8_vec();
+        serializer.serialize_bytes(&raw)
+    }
+
+    /// Deserializes the bytes from raw bytes or a sequence of integers
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ByteArray, D::Error> {
+        deserializer.deserialize_byte_buf(ByteArrayVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
+
+    #[test]
+    fn uber_byte_tokens() {
+        let byte = UberByte::from(0xA5);
+
+        assert_tokens(&byte.readable(), &[Token::Str("0b10100101")]);
+        assert_tokens(&byte.compact(), &[Token::U8(0xA5)]);
+        assert_de_tokens(&byte.readable(), &[Token::U64(165)]);
+        assert_de_tokens(&byte.readable(), &[Token::Str("0xA5")]);
+    }
+
+    #[test]
+    fn byte_array_tokens() {
+        let bytes = ByteArray::from(vec![0xDE, 0xAD]);
+
+        serde_test::assert_ser_tokens(&bytes.clone().readable(), &[Token::Str("DEAD")]);
+        serde_test::assert_ser_tokens(&bytes.clone().compact(), &[Token::Bytes(&[0xDE, 0xAD])]);
+
+        let json: ByteArray = serde_json::from_str("\"de ad\"").unwrap();
+        assert_eq!(bytes[..], json[..]);
+        let json: ByteArray = serde_json::from_str("[222, 173]").unwrap();
+        assert_eq!(bytes[..], json[..]);
+    }
+
+    #[test]
+    fn attributes() {
+        #[derive(Serialize, Deserialize)]
+        struct Fixture {
+            #[serde(with = "as_integer")]
+            flags: UberByte,
+            #[serde(with = "as_bytes")]
+            frame: ByteArray,
+            register: UberByte,
+        }
+
+        let fixture = Fixture {
+            flags: UberByte::from(3),
+            frame: ByteArray::from(vec![1, 2]),
+            register: UberByte::from(0x80),
+        };
+
+        let json = serde_json::to_string(&fixture).unwrap();
+        assert_eq!(
+            "{\"flags\":3,\"frame\":[1,2],\"register\":\"0b10000000\"}",
+            json
+        );
+
+        let parsed: Fixture = serde_json::from_str(&json).unwrap();
+        assert_eq!(fixture.flags, parsed.flags);
+        assert_eq!(fixture.frame[..], parsed.frame[..]);
+        assert_eq!(fixture.register, parsed.register);
+    }
+
+    #[test]
+    fn invalid_values() {
+        assert!(serde_json::from_str::<UberByte>("256").is_err());
+        assert!(serde_json::from_str::<UberByte>("-1").is_err());
+        assert!(serde_json::from_str::<UberByte>("\"0b2\"").is_err());
+        assert!(serde_json::from_str::<ByteArray>("\"ABC\"").is_err());
+        assert!(serde_json::from_str::<ByteArray>("[256]").is_err());
+    }
+}
@@ -0,0 +1,614 @@
+//! Generators and laws for property based tests of bit operations
+//!
+//! Enabled with the `testing` feature. The generators are deterministic, so a failing
+//! case can always be reproduced from the seed printed in the panic message.
+//!
+//! ```rust
+//! use uberbyte::testing::{for_all, laws, Mask};
+//! use uberbyte::UberByte;
+//!
+//! for_all(|(byte, mask): &(UberByte, Mask)| laws::set_is_idempotent(*byte, mask.0));
+//! ```
+
+use std::fmt::Debug;
+
+use crate::compression::lzss::LzssConfig;
+use crate::compression::rle::RleVariant;
+use crate::ecc::EccScheme;
+use crate::encoding::ascii85::Ascii85Variant;
+use crate::encoding::base32::Base32Alphabet;
+use crate::encoding::base64::Base64Config;
+use crate::encoding::{cobs, slip};
+use crate::formats::{ihex, srec};
+use crate::line_coding::{self, Codec8b10b, ManchesterConvention};
+use crate::memory_image::MemoryImage;
+use crate::tlv::{Tlv, TlvBuilder, TlvConfig};
+use crate::{BitArray, BitOrder, ByteArray, UberByte, UberByteError};
+
+/// The number of cases checked by [`for_all`]
+pub const DEFAULT_CASES: usize = 256;
+
+/// The seed used by [`for_all`]
+pub const DEFAULT_SEED: u64 = 0x5EED_B175;
+
+/// The bytes generators favour because they tend to expose edge cases
+const INTERESTING_BYTES: [u8; 8] = [0x00, 0x01, 0x7E, 0x7F, 0x80, 0x81, 0xC0, 0xFF];
+
+/// A deterministic source of random values
+#[derive(Debug, Clone)]
+pub struct Gen {
+    state: u64,
+    size: usize,
+}
+
+impl Gen {
+    /// Creates a new generator with the given seed
+    pub fn new(seed: u64) -> Self {
+        Gen {
+            state: seed.max(1),
+            size: 64,
+        }
+    }
+
+    /// Limits the length of generated collections
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// The maximum length of generated collections
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the next random value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Returns a random value from 0 up to, but not including, the bound
+    ///
+    /// # Remarks
+    ///
+    /// A bound of 0 always returns 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        match bound {
+            0 => 0,
+            bound => (self.next_u64() % bound as u64) as usize,
+        }
+    }
+
+    /// Returns TRUE one time out of the given number
+    pub fn one_in(&mut self, times: usize) -> bool {
+        self.below(times) == 0
+    }
+}
+
+/// Types that can be generated at random
+pub trait Arbitrary: Sized + Debug {
+    /// Creates a random value
+    fn arbitrary(gen: &mut Gen) -> Self;
+}
+
+/// A bit mask, with single bit masks generated more often than others
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mask(pub u8);
+
+/// A range of bit indices inside a byte
+///
+/// # Remarks
+///
+/// The start is inclusive and the end exclusive, so `0..8` covers the whole byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitRange {
+    /// The index of the lowest bit in the range
+    pub start: u32,
+    /// The index after the highest bit in the range
+    pub end: u32,
+}
+
+impl BitRange {
+    /// The number of bits in the range
+    pub fn len(&self) -> u32 {
+        self.end - self.start
+    }
+
+    /// Determines if the range holds no bits
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The mask with all bits of the range set
+    pub fn mask(&self) -> u8 {
+        (((1u16 << self.len()) - 1) << self.start) as u8
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        gen.one_in(2)
+    }
+}
+
+impl Arbitrary for u8 {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        match gen.one_in(4) {
+            true => INTERESTING_BYTES[gen.below(INTERESTING_BYTES.len())],
+            false => gen.next_u64() as u8,
+        }
+    }
+}
+
+impl Arbitrary for UberByte {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        UberByte::from(u8::arbitrary(gen))
+    }
+}
+
+impl Arbitrary for Mask {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        match gen.one_in(2) {
+            true => Mask(1 << gen.below(8)),
+            false => Mask(u8::arbitrary(gen)),
+        }
+    }
+}
+
+impl Arbitrary for BitRange {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        let start = gen.below(9) as u32;
+        let end = start + gen.below(9 - start as usize) as u32;
+        BitRange { start, end }
+    }
+}
+
+/// Generates up to [`Gen::size`] bytes, mixing random bytes with runs of a single byte
+impl Arbitrary for ByteArray {
+    fn arbitrary(gen: &mut Gen) -> Self {
+        let length = gen.below(gen.size() + 1);
+        let mut bytes = ByteArray::default();
+
+        while bytes.len() < length {
+            let byte = UberByte::arbitrary(gen);
+            let count = match gen.one_in(4) {
+                true => gen.below(length - bytes.len()) + 1,
+                false => 1,
+            };
+            for _ in 0..count {
+                bytes.add_mut(byte);
+            }
+        }
+
+        bytes
+    }
+}
+
+macro_rules! arbitrary_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Arbitrary),+> Arbitrary for ($($name,)+) {
+            fn arbitrary(gen: &mut Gen) -> Self {
+                ($($name::arbitrary(gen),)+)
+            }
+        }
+    };
+}
+
+arbitrary_tuple!(A, B);
+arbitrary_tuple!(A, B, C);
+
+/// Checks the property against [`DEFAULT_CASES`] generated values
+///
+/// # Remarks
+///
+/// Panics with the first value that does not hold the property
+pub fn for_all<T: Arbitrary>(property: impl Fn(&T) -> bool) {
+    for_all_seeded(DEFAULT_SEED, DEFAULT_CASES, property)
+}
+
+/// Checks the property against the given number of values generated from the seed
+///
+/// # Remarks
+///
+/// Panics with the first value that does not hold the property
+pub fn for_all_seeded<T: Arbitrary>(seed: u64, cases: usize, property: impl Fn(&T) -> bool) {
+    let mut gen = Gen::new(seed);
+
+    for case in 0..cases {
+        let value = T::arbitrary(&mut gen);
+        if !property(&value) {
+            panic!(
+                "property failed for {:?} (seed {:#X}, case {})",
+                value, seed, case
+            );
+        }
+    }
+}
+
+/// Checks the property against every possible byte
+///
+/// # Remarks
+///
+/// Panics with the first byte that does not hold the property
+pub fn exhaustive(property: impl Fn(UberByte) -> bool) {
+    for value in u8::MIN..=u8::MAX {
+        let byte = UberByte::from(value);
+        if !property(byte) {
+            panic!("property failed for {:#04X}", value);
+        }
+    }
+}
+
+/// The algebraic laws every [`UberByte`] has to obey
+pub mod laws {
+    use crate::UberByte;
+
+    /// Setting the same bits twice changes nothing the second time
+    pub fn set_is_idempotent(byte: UberByte, mask: u8) -> bool {
+        let once = byte.set(mask);
+        once.set(mask) == once && once.into_u8() & mask == mask
+    }
+
+    /// Clearing the same bits twice changes nothing the second time
+    pub fn clear_is_idempotent(byte: UberByte, mask: u8) -> bool {
+        let once = byte.clear(mask);
+        once.clear(mask) == once && once.into_u8() & mask == 0
+    }
+
+    /// Flipping all bits twice restores the byte
+    pub fn flip_is_involution(byte: UberByte) -> bool {
+        byte.flip().flip() == byte && byte.flip() == !byte
+    }
+
+    /// Setting and clearing only touch the bits of the mask
+    pub fn set_and_clear_are_local(byte: UberByte, mask: u8) -> bool {
+        let untouched = |other: UberByte| (other ^ byte).into_u8() & !mask == 0;
+        untouched(byte.set(mask)) && untouched(byte.clear(mask))
+    }
+
+    /// The complement of a disjunction is the conjunction of the complements and vice versa
+    pub fn de_morgan(left: UberByte, right: UberByte) -> bool {
+        !(left | right) == (!left & !right) && !(left & right) == (!left | !right)
+    }
+}
+
+/// A codec whose decoder has to restore everything its encoder produced
+#[derive(Debug, Clone, Copy)]
+pub struct Codec {
+    /// The name shown when the round trip fails
+    pub name: &'static str,
+    /// The length of the input has to be a multiple of this number of bytes
+    pub block_size: usize,
+    /// Encodes and decodes the data again
+    pub round_trip: fn(&ByteArray) -> Result<ByteArray, UberByteError>,
+}
+
+/// Every codec of the crate that works on arbitrary bytes
+pub const CODECS: &[Codec] = &[
+    Codec {
+        name: "cobs",
+        block_size: 1,
+        round_trip: |data| cobs::decode(&cobs::encode(data)),
+    },
+    Codec {
+        name: "slip",
+        block_size: 1,
+        round_trip: |data| slip::decode(&slip::encode(data)),
+    },
+    Codec {
+        name: "base64",
+        block_size: 1,
+        round_trip: |data| {
+            ByteArray::from_base64(
+                &data.to_base64(Base64Config::STANDARD),
+                Base64Config::STANDARD,
+            )
+        },
+    },
+    Codec {
+        name: "base64 url safe without padding",
+        block_size: 1,
+        round_trip: |data| {
+            let config = Base64Config::URL_SAFE_NO_PAD;
+            ByteArray::from_base64(&data.to_base64(config), config)
+        },
+    },
+    Codec {
+        name: "base32",
+        block_size: 1,
+        round_trip: |data| {
+            let alphabet = Base32Alphabet::Rfc4648;
+            ByteArray::from_base32(&data.to_base32(alphabet), alphabet)
+        },
+    },
+    Codec {
+        name: "base32 crockford",
+        block_size: 1,
+        round_trip: |data| {
+            let alphabet = Base32Alphabet::Crockford;
+            ByteArray::from_base32(&data.to_base32(alphabet), alphabet)
+        },
+    },
+    Codec {
+        name: "base16",
+        block_size: 1,
+        round_trip: |data| ByteArray::from_base16(&data.to_base16()),
+    },
+    Codec {
+        name: "ascii85",
+        block_size: 1,
+        round_trip: |data| {
+            let variant = Ascii85Variant::Ascii85;
+            ByteArray::from_ascii85(&data.to_ascii85(variant)?, variant)
+        },
+    },
+    Codec {
+        name: "z85",
+        block_size: 4,
+        round_trip: |data| {
+            let variant = Ascii85Variant::Z85;
+            ByteArray::from_ascii85(&data.to_ascii85(variant)?, variant)
+        },
+    },
+    Codec {
+        name: "packbits",
+        block_size: 1,
+        round_trip: |data| {
+            let variant = RleVariant::PackBits;
+            data.compress_rle(variant).decompress_rle(variant)
+        },
+    },
+    Codec {
+        name: "escape rle",
+        block_size: 1,
+        round_trip: |data| {
+            let variant = RleVariant::Escape(0x1B);
+            data.compress_rle(variant).decompress_rle(variant)
+        },
+    },
+    Codec {
+        name: "lzss",
+        block_size: 1,
+        round_trip: |data| data.compress_lzss(LzssConfig::default()).decompress_lzss(),
+    },
+    Codec {
+        name: "lzss with a small window",
+        block_size: 1,
+        round_trip: |data| data.compress_lzss(LzssConfig::new(16)).decompress_lzss(),
+    },
+    Codec {
+        name: "bit array",
+        block_size: 1,
+        round_trip: |data| {
+            BitArray::from_byte_array(data, BitOrder::LsbFirst).to_byte_array(BitOrder::LsbFirst)
+        },
+    },
+    Codec {
+        name: "manchester",
+        block_size: 1,
+        round_trip: |data| {
+            let (convention, order) = (ManchesterConvention::Ieee, BitOrder::LsbFirst);
+            let line = line_coding::encode_manchester(data, convention, order);
+            line_coding::decode_manchester(&line, convention, order)
+        },
+    },
+    Codec {
+        name: "differential manchester",
+        block_size: 1,
+        round_trip: |data| {
+            let line = line_coding::encode_differential_manchester(data, true, BitOrder::MsbFirst);
+            line_coding::decode_differential_manchester(&line, true, BitOrder::MsbFirst)
+        },
+    },
+    Codec {
+        name: "nrzi",
+        block_size: 1,
+        round_trip: |data| {
+            let line = line_coding::encode_nrzi(data, false, BitOrder::LsbFirst);
+            line_coding::decode_nrzi(&line, false, BitOrder::LsbFirst)
+        },
+    },
+    Codec {
+        name: "4b5b",
+        block_size: 1,
+        round_trip: |data| {
+            let line = line_coding::encode_4b5b(data, BitOrder::MsbFirst);
+            line_coding::decode_4b5b(&line, BitOrder::MsbFirst)
+        },
+    },
+    Codec {
+        name: "8b10b",
+        block_size: 1,
+        round_trip: |data| Codec8b10b::default().decode(&Codec8b10b::default().encode(data)),
+    },
+    Codec {
+        name: "hamming(7,4)",
+        block_size: 1,
+        round_trip: |data| {
+            let scheme = EccScheme::Hamming74;
+            Ok(scheme.decode(&scheme.encode(data)?)?.data)
+        },
+    },
+    Codec {
+        name: "hamming(8,4)",
+        block_size: 1,
+        round_trip: |data| {
+            let scheme = EccScheme::Hamming84;
+            Ok(scheme.decode(&scheme.encode(data)?)?.data)
+        },
+    },
+    Codec {
+        name: "secded(72,64)",
+        block_size: 8,
+        round_trip: |data| {
+            let scheme = EccScheme::Secded7264;
+            Ok(scheme.decode(&scheme.encode(data)?)?.data)
+        },
+    },
+    Codec {
+        name: "tlv",
+        block_size: 1,
+        round_trip: |data| {
+            let mut builder = TlvBuilder::new(TlvConfig::BER);
+            builder.add(0x9F02, &data[..])?;
+            let encoded = builder.build();
+            match Tlv::new(&encoded, TlvConfig::BER).next() {
+                Some(entry) => Ok(ByteArray::from(entry?.value)),
+                None => Err(UberByteError::InvalidLength),
+            }
+        },
+    },
+    Codec {
+        name: "intel hex",
+        block_size: 1,
+        round_trip: |data| {
+            let mut image = MemoryImage::new();
+            image.write(0x0800_FFF0, data)?;
+            ihex::parse(&ihex::write(&image, 16)?)?.read(0x0800_FFF0, data.len())
+        },
+    },
+    Codec {
+        name: "motorola s-record",
+        block_size: 1,
+        round_trip: |data| {
+            let mut image = MemoryImage::new();
+            image.write(0x0800_FFF0, data)?;
+            srec::parse(&srec::write(&image, 16)?)?.read(0x0800_FFF0, data.len())
+        },
+    },
+];
+
+/// Sends the data through every codec in [`CODECS`] that accepts its length
+///
+/// # Returns
+///
+/// The name of the first codec that failed or did not restore the data,
+/// together with a description of the failure
+pub fn check_round_trips(data: &ByteArray) -> Result<(), String> {
+    for codec in CODECS
+        .iter()
+        .filter(|codec| data.len().is_multiple_of(codec.block_size))
+    {
+        match (codec.round_trip)(data) {
+            Ok(decoded) if decoded[..] == data[..] => {}
+            Ok(decoded) => {
+                return Err(format!(
+                    "{} decoded {:02X?} instead of {:02X?}",
+                    codec.name,
+                    decoded[..]
+                        .iter()
+                        .map(UberByte::into_u8)
+                        .collect::<Vec<u8>>(),
+                    data[..].iter().map(UberByte::into_u8).collect::<Vec<u8>>()
+                ))
+            }
+            Err(error) => return Err(format!("{} failed with {:?}", codec.name, error)),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parity::Parity;
+
+    #[test]
+    fn generators_are_deterministic() {
+        let first: Vec<u64> = (0..4).map(|_| Gen::new(7).next_u64()).collect();
+        let mut gen = Gen::new(7);
+        let second: Vec<u64> = (0..4).map(|_| gen.next_u64()).collect();
+
+        assert!(first.iter().all(|value| *value == first[0]));
+        assert_eq!(first[0], second[0]);
+        assert_ne!(second[0], second[1]);
+        for_all(|range: &BitRange| range.end <= 8 && range.mask().count_ones() == range.len());
+        for_all_seeded(1, 64, |bytes: &ByteArray| bytes.len() <= 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed for 0x80")]
+    fn exhaustive_reports_value() {
+        exhaustive(|byte| byte.into_u8() < 0x80);
+    }
+
+    #[test]
+    fn exhaustive_unary_operations() {
+        exhaustive(|byte| {
+            let value = byte.into_u8();
+            (!byte).into_u8() == !value
+                && byte.flip().into_u8() == !value
+                && byte.count_set_bits() as u32 == value.count_ones()
+                && byte.leading_zeros() as u32 == value.leading_zeros()
+                && byte.trailing_zeros() as u32 == value.trailing_zeros()
+                && byte.leading_ones() as u32 == value.leading_ones()
+                && byte.trailing_ones() as u32 == value.trailing_ones()
+        });
+        exhaustive(|byte| {
+            let value = byte.into_u8();
+            let set: Vec<usize> = (0..8).filter(|bit| value >> bit & 1 == 1).collect();
+            byte.iter_set_indices().collect::<Vec<usize>>() == set
+                && byte.lowest_set_bit() == set.first().copied()
+                && byte.highest_set_bit() == set.last().copied()
+                && (0..8).all(|bit| byte.is_bit_set(bit) == (value >> bit & 1 == 1))
+                && byte.bits(BitOrder::LsbFirst).collect::<Vec<bool>>()
+                    == (0..8)
+                        .map(|bit| value >> bit & 1 == 1)
+                        .collect::<Vec<bool>>()
+                && UberByte::from(<[bool; 8]>::from(byte)) == byte
+        });
+        exhaustive(|byte| {
+            (0..8).all(|amount| {
+                byte.checked_shl(amount).map(|b| b.into_u8()) == Some(byte.into_u8() << amount)
+                    && byte.checked_shr(amount).map(|b| b.into_u8())
+                        == Some(byte.into_u8() >> amount)
+            }) && byte.checked_shl(8).is_none()
+        });
+        exhaustive(|byte| {
+            [Parity::Even, Parity::Odd].iter().all(|parity| {
+                byte.clear(0x80)
+                    .with_parity_bit(*parity)
+                    .has_valid_parity_bit(*parity)
+            })
+        });
+    }
+
+    #[test]
+    fn exhaustive_text_round_trips() {
+        exhaustive(|byte| {
+            [
+                byte.to_string(),
+                format!("{:#b}", byte),
+                format!("{:#o}", byte),
+                format!("{:#X}", byte),
+                format!("0b{}", byte.to_nibble_binary()),
+            ]
+            .iter()
+            .all(|text| text.parse::<UberByte>().ok() == Some(byte))
+        });
+    }
+
+    #[test]
+    fn bit_laws() {
+        for_all(|(byte, range): &(UberByte, BitRange)| {
+            laws::set_is_idempotent(*byte, range.mask())
+                && byte.set(range.mask()).clear(range.mask()) == byte.clear(range.mask())
+        });
+        exhaustive(|left| exhaustive_pair(left, laws::de_morgan));
+    }
+
+    fn exhaustive_pair(left: UberByte, law: fn(UberByte, UberByte) -> bool) -> bool {
+        (u8::MIN..=u8::MAX).all(|right| law(left, UberByte::from(right)))
+    }
+
+    #[test]
+    fn codec_round_trips() {
+        for_all(|data: &ByteArray| check_round_trips(data).is_ok());
+        for_all(|data: &ByteArray| {
+            check_round_trips(&ByteArray::from(&data[..data.len() / 8 * 8])).is_ok()
+        });
+    }
+}
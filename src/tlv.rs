@@ -0,0 +1,581 @@
+//! Parsing and building of TLV (type-length-value) encoded data
+//!
+//! ```rust
+//! use uberbyte::tlv::{Tlv, TlvBuilder, TlvConfig};
+//! use uberbyte::ByteArray;
+//!
+//! let mut builder = TlvBuilder::new(TlvConfig::default());
+//! builder.add_bytes(0x01, &[0xCA, 0xFE]).unwrap();
+//! let encoded: ByteArray = builder.build();
+//!
+//! for entry in Tlv::new(&encoded, TlvConfig::default()) {
+//!     let entry = entry.unwrap();
+//!     println!("tag {:02X} has {} bytes", entry.tag, entry.value.len());
+//! }
+//! ```
+
+use crate::{ByteArray, Endianness, UberByte, UberByteError};
+
+/// Defines how the tag of a TLV entry is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagFormat {
+    /// The tag is a single byte
+    #[default]
+    U8,
+    /// The tag is made out of two bytes in the given order
+    U16(Endianness),
+    /// The tag is encoded according to the BER rules
+    ///
+    /// If the lower 5 bits of the first byte are all set then the tag continues
+    /// with every following byte that has bit 7 set. The raw tag bytes are
+    /// combined into the tag value, so the EMV tag `9F 02` becomes `0x9F02`.
+    Ber,
+}
+
+/// Defines how the length of a TLV entry is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LengthFormat {
+    /// The length is a single byte
+    #[default]
+    U8,
+    /// The length is made out of two bytes in the given order
+    U16(Endianness),
+    /// The length is made out of four bytes in the given order
+    U32(Endianness),
+    /// The length is encoded according to the BER definite length rules
+    ///
+    /// Lengths up to 127 use a single byte, longer lengths are prefixed
+    /// with `0x80 | n` followed by _n_ big endian length bytes.
+    Ber,
+}
+
+/// Describes the layout of TLV encoded data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TlvConfig {
+    /// How the tag of each entry is encoded
+    pub tag: TagFormat,
+    /// How the length of each entry is encoded
+    pub length: LengthFormat,
+}
+
+impl TlvConfig {
+    /// The configuration used by BER and EMV style encodings
+    pub const BER: TlvConfig = TlvConfig {
+        tag: TagFormat::Ber,
+        length: LengthFormat::Ber,
+    };
+
+    /// Creates a new configuration out of the given tag and length formats
+    pub fn new(tag: TagFormat, length: LengthFormat) -> Self {
+        TlvConfig { tag, length }
+    }
+}
+
+/// A single entry read from TLV encoded data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlvEntry<'a> {
+    /// The tag of the entry
+    pub tag: u32,
+    /// The value bytes of the entry
+    pub value: &'a [UberByte],
+    config: TlvConfig,
+}
+
+impl<'a> TlvEntry<'a> {
+    /// Determines if the entry is a constructed entry containing further TLV entries
+    ///
+    /// # Returns
+    ///
+    /// TRUE if the BER constructed bit (bit 5 of the first tag byte) is set
+    /// FALSE in all other cases
+    ///
+    /// # Remarks
+    ///
+    /// Only BER encoded tags carry this information. For other tag formats
+    /// FALSE is always returned, but the entry can still be read with [`TlvEntry::children`].
+    pub fn is_constructed(&self) -> bool {
+        if self.config.tag != TagFormat::Ber {
+            return false;
+        }
+
+        let mut first_byte = self.tag;
+        while first_byte > 0xFF {
+            first_byte >>= 8;
+        }
+
+        first_byte & 0b_0010_0000 != 0
+    }
+
+    /// Reads the value of this entry as nested TLV entries using the same configuration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::tlv::{Tlv, TlvConfig};
+    /// use uberbyte::ByteArray;
+    ///
+    /// let data = ByteArray::from(vec![0x70, 0x03, 0x5A, 0x01, 0x42]);
+    /// let entry = Tlv::new(&data, TlvConfig::BER).next().unwrap().unwrap();
+    ///
+    /// assert!(entry.is_constructed());
+    /// let child = entry.children().next().unwrap().unwrap();
+    /// assert_eq!(0x5A, child.tag);
+    /// ```
+    pub fn children(&self) -> Tlv<'a> {
+        Tlv::from_slice(self.value, self.config)
+    }
+}
+
+/// Iterator over the TLV entries stored in a byte slice
+///
+/// Every item is either a decoded [`TlvEntry`] or the error describing why the
+/// entry could not be decoded. After the first error the iterator is exhausted.
+#[derive(Debug, Clone)]
+pub struct Tlv<'a> {
+    data: &'a [UberByte],
+    position: usize,
+    config: TlvConfig,
+}
+
+impl<'a> Tlv<'a> {
+    /// Creates a iterator over the TLV entries in the given byte array
+    pub fn new(data: &'a ByteArray, config: TlvConfig) -> Self {
+        Tlv::from_slice(&data[..], config)
+    }
+
+    /// Creates a iterator over the TLV entries in the given slice
+    pub fn from_slice(data: &'a [UberByte], config: TlvConfig) -> Self {
+        Tlv {
+            data,
+            position: 0,
+            config,
+        }
+    }
+
+    fn read_fixed(&mut self, width: usize, endianness: Endianness) -> Option<u32> {
+        let bytes = self.data.get(self.position..self.position + width)?;
+        self.position += width;
+
+        let fold = |acc: u32, byte: &UberByte| (acc << 8) | byte.into_u8() as u32;
+        Some(match endianness {
+            Endianness::Big => bytes.iter().fold(0, fold),
+            Endianness::Little => bytes.iter().rev().fold(0, fold),
+        })
+    }
+
+    fn read_tag(&mut self) -> Option<u32> {
+        match self.config.tag {
+            TagFormat::U8 => self.read_fixed(1, Endianness::Big),
+            TagFormat::U16(endianness) => self.read_fixed(2, endianness),
+            TagFormat::Ber => {
+                let first = self.data.get(self.position)?.into_u8();
+                self.position += 1;
+                let mut tag = first as u32;
+
+                if first & 0b_0001_1111 != 0b_0001_1111 {
+                    return Some(tag);
+                }
+
+                for _ in 1..4 {
+                    let next = self.data.get(self.position)?.into_u8();
+                    self.position += 1;
+                    tag = (tag << 8) | next as u32;
+
+                    if next & 0b_1000_0000 == 0 {
+                        return Some(tag);
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    fn read_length(&mut self) -> Option<usize> {
+        let length = match self.config.length {
+            LengthFormat::U8 => self.read_fixed(1, Endianness::Big)?,
+            LengthFormat::U16(endianness) => self.read_fixed(2, endianness)?,
+            LengthFormat::U32(endianness) => self.read_fixed(4, endianness)?,
+            LengthFormat::Ber => {
+                let first = self.data.get(self.position)?.into_u8();
+                self.position += 1;
+
+                if first & 0b_1000_0000 == 0 {
+                    first as u32
+                } else {
+                    // 0x80 (indefinite length) and more than 4 length bytes are not supported
+                    let width = (first & 0b_0111_1111) as usize;
+                    if width == 0 || width > 4 {
+                        return None;
+                    }
+                    self.read_fixed(width, Endianness::Big)?
+                }
+            }
+        };
+
+        usize::try_from(length).ok()
+    }
+}
+
+impl<'a> Iterator for Tlv<'a> {
+    type Item = Result<TlvEntry<'a>, UberByteError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.data.len() {
+            return None;
+        }
+
+        let entry_start = self.position;
+
+        let tag = match self.read_tag() {
+            Some(tag) => tag,
+            None => {
+                self.position = self.data.len();
+                return Some(Err(UberByteError::MalformedTag(entry_start)));
+            }
+        };
+
+        let length_start = self.position;
+        let value = self.read_length().and_then(|length| {
+            let end = self.position.checked_add(length)?;
+            self.data.get(self.position..end)
+        });
+
+        match value {
+            Some(value) => {
+                self.position += value.len();
+                Some(Ok(TlvEntry {
+                    tag,
+                    value,
+                    config: self.config,
+                }))
+            }
+            None => {
+                self.position = self.data.len();
+                Some(Err(UberByteError::MalformedLength(length_start)))
+            }
+        }
+    }
+}
+
+/// Composes TLV encoded data while computing the length of each entry
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::tlv::{TlvBuilder, TlvConfig};
+///
+/// let mut application = TlvBuilder::new(TlvConfig::BER);
+/// application.add_bytes(0x5A, &[0x12, 0x34]).unwrap();
+///
+/// let mut template = TlvBuilder::new(TlvConfig::BER);
+/// template.add_nested(0x70, application).unwrap();
+///
+/// assert_eq!(6, template.build().len());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TlvBuilder {
+    config: TlvConfig,
+    data: ByteArray,
+}
+
+impl TlvBuilder {
+    /// Creates a new empty builder using the given configuration
+    pub fn new(config: TlvConfig) -> Self {
+        TlvBuilder {
+            config,
+            data: ByteArray::default(),
+        }
+    }
+
+    /// Appends a entry with the given tag and value
+    ///
+    /// # Returns
+    ///
+    /// A _ValueOverflow_ error if the tag or the length of the value can not be
+    /// represented with the configured formats. In that case nothing is appended.
+    pub fn add(&mut self, tag: u32, value: &[UberByte]) -> Result<(), UberByteError> {
+        let mut entry = ByteArray::default();
+        encode_tag(&mut entry, tag, self.config.tag)?;
+        encode_length(&mut entry, value.len(), self.config.length)?;
+        entry.add_range_mut(value.to_vec());
+
+        self.data += entry;
+        Ok(())
+    }
+
+    /// Appends a entry with the given tag and raw byte value
+    ///
+    /// # Returns
+    ///
+    /// The same result as [`TlvBuilder::add`]
+    pub fn add_bytes(&mut self, tag: u32, value: &[u8]) -> Result<(), UberByteError> {
+        self.add(tag, &ByteArray::from(value)[..])
+    }
+
+    /// Appends a constructed entry whose value is the content of the given builder
+    ///
+    /// # Returns
+    ///
+    /// The same result as [`TlvBuilder::add`]
+    pub fn add_nested(&mut self, tag: u32, nested: TlvBuilder) -> Result<(), UberByteError> {
+        self.add(tag, &nested.build()[..])
+    }
+
+    /// Returns the encoded entries
+    pub fn build(self) -> ByteArray {
+        self.data
+    }
+}
+
+fn encode_fixed(target: &mut ByteArray, value: u32, width: usize, endianness: Endianness) {
+    let bytes = value.to_be_bytes();
+    let mut bytes = bytes[4 - width..].to_vec();
+    if endianness == Endianness::Little {
+        bytes.reverse();
+    }
+    *target += ByteArray::from(bytes);
+}
+
+fn encode_tag(target: &mut ByteArray, tag: u32, format: TagFormat) -> Result<(), UberByteError> {
+    match format {
+        TagFormat::U8 => {
+            let tag = u8::try_from(tag).map_err(|_| UberByteError::ValueOverflow)?;
+            target.add_mut(UberByte::from(tag));
+        }
+        TagFormat::U16(endianness) => {
+            if tag > u16::MAX as u32 {
+                return Err(UberByteError::ValueOverflow);
+            }
+            encode_fixed(target, tag, 2, endianness);
+        }
+        TagFormat::Ber => {
+            let width = (4 - tag.leading_zeros() as usize / 8).max(1);
+            let bytes = &tag.to_be_bytes()[4 - width..];
+            if !is_ber_tag(bytes) {
+                return Err(UberByteError::ValueOverflow);
+            }
+            encode_fixed(target, tag, width, Endianness::Big);
+        }
+    }
+    Ok(())
+}
+
+/// Determines if the bytes form a single BER tag that reads back unchanged
+fn is_ber_tag(bytes: &[u8]) -> bool {
+    let continues = |byte: &u8| byte & 0b_1000_0000 != 0;
+
+    match bytes {
+        [first] => first & 0b_0001_1111 != 0b_0001_1111,
+        [first, middle @ .., last] => {
+            first & 0b_0001_1111 == 0b_0001_1111 && middle.iter().all(continues) && !continues(last)
+        }
+        [] => false,
+    }
+}
+
+fn encode_length(
+    target: &mut ByteArray,
+    length: usize,
+    format: LengthFormat,
+) -> Result<(), UberByteError> {
+    let overflow = |max: u32| {
+        u32::try_from(length)
+            .ok()
+            .filter(|length| *length <= max)
+            .ok_or(UberByteError::ValueOverflow)
+    };
+
+    match format {
+        LengthFormat::U8 => encode_fixed(target, overflow(u8::MAX as u32)?, 1, Endianness::Big),
+        LengthFormat::U16(endianness) => {
+            encode_fixed(target, overflow(u16::MAX as u32)?, 2, endianness)
+        }
+        LengthFormat::U32(endianness) => encode_fixed(target, overflow(u32::MAX)?, 4, endianness),
+        LengthFormat::Ber => {
+            let length = overflow(u32::MAX)?;
+            if length < 0x80 {
+                target.add_mut(UberByte::from(length as u8));
+            } else {
+                let width = 4 - length.leading_zeros() as usize / 8;
+                target.add_mut(UberByte::from(0x80 | width as u8));
+                encode_fixed(target, length, width, Endianness::Big);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_simple() {
+        let data = ByteArray::from(vec![0x01, 0x02, 0xAA, 0xBB, 0x02, 0x00, 0x03, 0x01, 0xCC]);
+
+        let entries: Vec<TlvEntry> = Tlv::new(&data, TlvConfig::default())
+            .map(|e| e.unwrap())
+            .collect();
+
+        assert_eq!(3, entries.len());
+        assert_eq!(0x01, entries[0].tag);
+        assert_eq!(
+            vec![0xAA, 0xBB],
+            ByteArray::from(entries[0].value).to_u8_vec()
+        );
+        assert_eq!(0x02, entries[1].tag);
+        assert!(entries[1].value.is_empty());
+        assert_eq!(vec![0xCC], ByteArray::from(entries[2].value).to_u8_vec());
+    }
+
+    #[test]
+    fn parse_wide_fields() {
+        let config = TlvConfig::new(
+            TagFormat::U16(Endianness::Little),
+            LengthFormat::U32(Endianness::Big),
+        );
+        let data = ByteArray::from(vec![0x34, 0x12, 0x00, 0x00, 0x00, 0x01, 0xFF]);
+
+        let entry = Tlv::new(&data, config).next().unwrap().unwrap();
+
+        assert_eq!(0x1234, entry.tag);
+        assert_eq!(vec![0xFF], ByteArray::from(entry.value).to_u8_vec());
+    }
+
+    #[test]
+    fn parse_length_exceeding_data() {
+        let data = ByteArray::from(vec![0x01, 0x01, 0xAA, 0x02, 0x05, 0xBB]);
+        let mut tlv = Tlv::new(&data, TlvConfig::default());
+
+        assert!(tlv.next().unwrap().is_ok());
+        assert!(matches!(
+            tlv.next(),
+            Some(Err(UberByteError::MalformedLength(4)))
+        ));
+        assert!(tlv.next().is_none());
+    }
+
+    #[test]
+    fn parse_truncated_tag() {
+        let data = ByteArray::from(vec![0x9F]);
+
+        assert!(matches!(
+            Tlv::new(&data, TlvConfig::BER).next(),
+            Some(Err(UberByteError::MalformedTag(0)))
+        ));
+    }
+
+    #[test]
+    fn parse_ber() {
+        // 9F02 with a long form length of 0x81 0x02
+        let data = ByteArray::from(vec![0x9F, 0x02, 0x81, 0x02, 0x00, 0x01]);
+
+        let entry = Tlv::new(&data, TlvConfig::BER).next().unwrap().unwrap();
+
+        assert_eq!(0x9F02, entry.tag);
+        assert!(!entry.is_constructed());
+        assert_eq!(vec![0x00, 0x01], ByteArray::from(entry.value).to_u8_vec());
+    }
+
+    #[test]
+    fn parse_ber_indefinite_length() {
+        let data = ByteArray::from(vec![0x30, 0x80, 0x00, 0x00]);
+
+        assert!(matches!(
+            Tlv::new(&data, TlvConfig::BER).next(),
+            Some(Err(UberByteError::MalformedLength(1)))
+        ));
+    }
+
+    #[test]
+    fn parse_nested() {
+        let data = ByteArray::from(vec![0x70, 0x07, 0x5A, 0x01, 0x42, 0x9F, 0x02, 0x01, 0x43]);
+
+        let template = Tlv::new(&data, TlvConfig::BER).next().unwrap().unwrap();
+        assert!(template.is_constructed());
+
+        let children: Vec<TlvEntry> = template.children().map(|e| e.unwrap()).collect();
+        assert_eq!(2, children.len());
+        assert_eq!(0x5A, children[0].tag);
+        assert_eq!(0x9F02, children[1].tag);
+        assert_eq!(vec![0x43], ByteArray::from(children[1].value).to_u8_vec());
+    }
+
+    #[test]
+    fn build_simple() {
+        let mut builder = TlvBuilder::new(TlvConfig::default());
+        builder.add_bytes(0x01, &[0xAA, 0xBB]).unwrap();
+        builder.add_bytes(0x02, &[]).unwrap();
+
+        let result = builder.build();
+
+        let expected: Vec<u8> = vec![0x01, 0x02, 0xAA, 0xBB, 0x02, 0x00];
+        let actual = result.to_u8_vec();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn build_overflow() {
+        let mut builder = TlvBuilder::new(TlvConfig::default());
+
+        assert!(builder.add_bytes(0x100, &[]).is_err());
+        assert!(builder.add_bytes(0x01, &[0; 256]).is_err());
+        assert_eq!(0, builder.build().len());
+    }
+
+    #[test]
+    fn build_invalid_ber_tag() {
+        let mut builder = TlvBuilder::new(TlvConfig::BER);
+
+        for tag in [0x1F, 0x0100, 0x9F82, 0x1F_0102, 0x9F_8180] {
+            assert!(matches!(
+                builder.add_bytes(tag, &[0x01]),
+                Err(UberByteError::ValueOverflow)
+            ));
+        }
+        assert_eq!(0, builder.build().len());
+
+        let mut builder = TlvBuilder::new(TlvConfig::BER);
+        for tag in [0x00, 0x5F20, 0xBF_8102] {
+            builder.add_bytes(tag, &[0x01]).unwrap();
+        }
+        let encoded = builder.build();
+        let tags: Vec<u32> = Tlv::new(&encoded, TlvConfig::BER)
+            .map(|entry| entry.unwrap().tag)
+            .collect();
+        assert_eq!(vec![0x00, 0x5F20, 0xBF_8102], tags);
+    }
+
+    #[test]
+    fn build_ber_long_length() {
+        let mut builder = TlvBuilder::new(TlvConfig::BER);
+        builder.add_bytes(0x9F02, &[0; 200]).unwrap();
+
+        let result = builder.build();
+
+        assert_eq!(204, result.len());
+        assert_eq!(UberByte::from(0x81), result[2]);
+        assert_eq!(UberByte::from(200), result[3]);
+    }
+
+    #[test]
+    fn build_and_parse_round_trip() {
+        let config = TlvConfig::new(
+            TagFormat::U16(Endianness::Big),
+            LengthFormat::U16(Endianness::Little),
+        );
+        let mut inner = TlvBuilder::new(config);
+        inner.add_bytes(0x0102, &[0x01]).unwrap();
+        let mut outer = TlvBuilder::new(config);
+        outer.add_nested(0xABCD, inner).unwrap();
+        let encoded = outer.build();
+
+        let entry = Tlv::new(&encoded, config).next().unwrap().unwrap();
+        assert_eq!(0xABCD, entry.tag);
+
+        let child = entry.children().next().unwrap().unwrap();
+        assert_eq!(0x0102, child.tag);
+        assert_eq!(vec![0x01], ByteArray::from(child.value).to_u8_vec());
+    }
+}
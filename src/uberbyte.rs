@@ -10,8 +10,11 @@ use crate::{
     ALL_BIT_MASK, FIFTH_BIT_MASK, FIRST_BIT_MASK, FOURTH_BIT_MASK, NONE_BIT_MASK, SECOND_BIT_MASK,
     SEVENTH_BIT_MASK, SIXTH_BIT_MASK, THIRD_BIT_MASK, ZERO_BIT_MASK,
 };
+use crate::BitOrder;
 
 pub mod formatters;
+pub mod from_str;
+pub mod operators;
 pub mod try_from;
 
 /// Implements a simple wrapper over a __u8__ that allows you simple bit manipulation
@@ -204,7 +207,7 @@ impl UberByte {
     ///
     /// let my_byte = UberByte::from(42);
     ///
-    /// for index in 0..7 {
+    /// for index in 0..8 {
     ///     println!("Bit on index {} is {}", index, my_byte.is_bit_set(index))
     /// }
     /// ```
@@ -359,6 +362,90 @@ impl UberByte {
         count
     }
 
+    /// Iterates over all 8 bits in the given order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::{BitOrder, UberByte};
+    ///
+    /// let bits: Vec<bool> = UberByte::from(0b_0000_0011).bits(BitOrder::LsbFirst).collect();
+    ///
+    /// assert_eq!(vec![true, true, false, false, false, false, false, false], bits);
+    /// ```
+    pub fn bits(&self, order: BitOrder) -> impl Iterator<Item = bool> {
+        let byte = *self;
+        (0..8).map(move |index| match order {
+            BitOrder::MsbFirst => byte.is_bit_set(7 - index),
+            BitOrder::LsbFirst => byte.is_bit_set(index),
+        })
+    }
+
+    /// Iterates over the indices of all set bits, starting with bit 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::UberByte;
+    ///
+    /// let indices: Vec<usize> = UberByte::from(0b_1000_0101).iter_set_indices().collect();
+    ///
+    /// assert_eq!(vec![0, 2, 7], indices);
+    /// ```
+    pub fn iter_set_indices(&self) -> impl Iterator<Item = usize> {
+        let byte = *self;
+        (0..8).filter(move |index| byte.is_bit_set(*index))
+    }
+
+    /// Determines the number of unset bits before the most significant set bit
+    pub fn leading_zeros(&self) -> u8 {
+        self.value.leading_zeros() as u8
+    }
+
+    /// Determines the number of unset bits after the least significant set bit
+    pub fn trailing_zeros(&self) -> u8 {
+        self.value.trailing_zeros() as u8
+    }
+
+    /// Determines the number of set bits before the most significant unset bit
+    pub fn leading_ones(&self) -> u8 {
+        self.value.leading_ones() as u8
+    }
+
+    /// Determines the number of set bits after the least significant unset bit
+    pub fn trailing_ones(&self) -> u8 {
+        self.value.trailing_ones() as u8
+    }
+
+    /// Determines the index of the most significant set bit
+    ///
+    /// # Returns
+    ///
+    /// The bit index, None if no bit is set
+    pub fn highest_set_bit(&self) -> Option<usize> {
+        match self.value {
+            0 => None,
+            value => Some(7 - value.leading_zeros() as usize),
+        }
+    }
+
+    /// Determines the index of the least significant set bit
+    ///
+    /// # Returns
+    ///
+    /// The bit index, None if no bit is set
+    pub fn lowest_set_bit(&self) -> Option<usize> {
+        match self.value {
+            0 => None,
+            value => Some(value.trailing_zeros() as usize),
+        }
+    }
+
+    /// Creates a UberByte out of the given value, usable in constant expressions
+    pub(crate) const fn from_u8(value: u8) -> UberByte {
+        UberByte { value }
+    }
+
     /// Gets the internal value as a u8
     /// 
     /// # Returns
@@ -442,14 +529,13 @@ impl Shl for UberByte {
     type Output = Self;
 
     fn shl(self, rhs: Self) -> Self::Output {
-        let shift_left = self.value << rhs.value;
-        UberByte::from(shift_left)
+        self << rhs.value as u32
     }
 }
 
 impl ShlAssign for UberByte {
     fn shl_assign(&mut self, rhs: Self) {
-        self.value = self.value << rhs.value;
+        *self = *self << rhs;
     }
 }
 
@@ -457,20 +543,20 @@ impl Shr for UberByte {
     type Output = Self;
 
     fn shr(self, rhs: Self) -> Self::Output {
-        let shift_right = self.value >> rhs.value;
-        UberByte::from(shift_right)
+        self >> rhs.value as u32
     }
 }
 
 impl ShrAssign for UberByte {
     fn shr_assign(&mut self, rhs: Self) {
-        self.value = self.value >> rhs.value;
+        *self = *self >> rhs;
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::testing::{exhaustive, for_all, laws, Mask};
 
     #[test]
     fn individual_bits_set() {
@@ -688,4 +774,71 @@ mod test {
         assert_eq!(8, UberByte::MAX.count_set_bits());
         assert_eq!(3, UberByte::from(0b_0001_0110).count_set_bits());
     }
+
+    #[test]
+    fn bits() {
+        let byte = UberByte::from(0b_1000_0010);
+
+        let msb: Vec<bool> = byte.bits(BitOrder::MsbFirst).collect();
+        let lsb: Vec<bool> = byte.bits(BitOrder::LsbFirst).collect();
+
+        assert_eq!(8, msb.len());
+        assert!(msb[0] && msb[6]);
+        assert!(lsb[1] && lsb[7]);
+        assert_eq!(2, msb.iter().filter(|bit| **bit).count());
+    }
+
+    #[test]
+    fn iter_set_indices() {
+        assert_eq!(0, UberByte::MIN.iter_set_indices().count());
+        assert_eq!(
+            vec![1, 4],
+            UberByte::from(0b_0001_0010)
+                .iter_set_indices()
+                .collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn zeros_and_ones() {
+        let byte = UberByte::from(0b_1100_1000);
+
+        assert_eq!(0, byte.leading_zeros());
+        assert_eq!(3, byte.trailing_zeros());
+        assert_eq!(2, byte.leading_ones());
+        assert_eq!(0, byte.trailing_ones());
+        assert_eq!(8, UberByte::MIN.leading_zeros());
+        assert_eq!(8, UberByte::MAX.trailing_ones());
+    }
+
+    #[test]
+    fn highest_and_lowest_set_bit() {
+        let byte = UberByte::from(0b_0010_0100);
+
+        assert_eq!(Some(5), byte.highest_set_bit());
+        assert_eq!(Some(2), byte.lowest_set_bit());
+        assert_eq!(None, UberByte::MIN.highest_set_bit());
+        assert_eq!(None, UberByte::MIN.lowest_set_bit());
+    }
+
+    #[test]
+    fn set_clear_flip_laws() {
+        for_all(|(byte, mask): &(UberByte, Mask)| {
+            laws::set_is_idempotent(*byte, mask.0)
+                && laws::clear_is_idempotent(*byte, mask.0)
+                && laws::set_and_clear_are_local(*byte, mask.0)
+        });
+        exhaustive(laws::flip_is_involution);
+    }
+
+    #[test]
+    fn mutating_operations_match_copies() {
+        for_all(|(byte, mask): &(UberByte, Mask)| {
+            let (mut set, mut clear, mut flip) = (*byte, *byte, *byte);
+            set.set_mut(mask.0);
+            clear.clear_mut(mask.0);
+            flip.flip_mut();
+            set == byte.set(mask.0) && clear == byte.clear(mask.0) && flip == byte.flip()
+        });
+    }
 }
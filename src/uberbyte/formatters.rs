@@ -4,31 +4,68 @@ use crate::UberByte;
 
 impl Binary for UberByte {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:b}", self.value))
+        Binary::fmt(&self.value, f)
     }
 }
 
 impl LowerHex for UberByte {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:x}", self.value))
+        LowerHex::fmt(&self.value, f)
     }
 }
 
 impl UpperHex for UberByte {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:X}", self.value))
+        UpperHex::fmt(&self.value, f)
     }
 }
 
 impl Octal for UberByte {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{:o}", self.value))
+        Octal::fmt(&self.value, f)
     }
 }
 
 impl Display for UberByte {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}", self.value))
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl UberByte {
+    /// Formats the byte as binary with the nibbles separated by an underscore
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::UberByte;
+    ///
+    /// assert_eq!("1010_0101", UberByte::from(0xA5).to_nibble_binary());
+    /// ```
+    pub fn to_nibble_binary(&self) -> String {
+        format!("{:04b}_{:04b}", self.value >> 4, self.value & 0x0F)
+    }
+
+    /// Formats the byte as a table listing every bit under its position
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use uberbyte::UberByte;
+    ///
+    /// assert_eq!(
+    ///     "7 6 5 4 3 2 1 0\n1 0 1 0 0 1 0 1",
+    ///     UberByte::from(0xA5).to_bit_table()
+    /// );
+    /// ```
+    pub fn to_bit_table(&self) -> String {
+        let positions: Vec<String> = (0..8).rev().map(|index| index.to_string()).collect();
+        let bits: Vec<&str> = (0..8)
+            .rev()
+            .map(|index| if self.is_bit_set(index) { "1" } else { "0" })
+            .collect();
+
+        format!("{}\n{}", positions.join(" "), bits.join(" "))
     }
 }
 
@@ -63,4 +100,29 @@ mod test {
 
         assert_eq!(String::from("231"), format!("{:o}", my_bit));
     }
+
+    #[test]
+    fn formatter_flags() {
+        let my_bit = UberByte::from(0b_0000_0101);
+
+        assert_eq!(String::from("00000101"), format!("{:08b}", my_bit));
+        assert_eq!(String::from("0b101"), format!("{:#b}", my_bit));
+        assert_eq!(String::from("0x05"), format!("{:#04x}", my_bit));
+        assert_eq!(String::from("**5"), format!("{:*>3}", my_bit));
+        assert_eq!(String::from("5  "), format!("{:<3o}", my_bit));
+    }
+
+    #[test]
+    fn nibble_binary() {
+        assert_eq!("0000_0000", UberByte::MIN.to_nibble_binary());
+        assert_eq!("0001_1110", UberByte::from(0x1E).to_nibble_binary());
+    }
+
+    #[test]
+    fn bit_table() {
+        assert_eq!(
+            "7 6 5 4 3 2 1 0\n0 0 0 0 0 0 0 1",
+            UberByte::from(1).to_bit_table()
+        );
+    }
 }
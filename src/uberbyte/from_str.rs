@@ -0,0 +1,125 @@
+use std::str::FromStr;
+
+use crate::{UberByte, UberByteError};
+
+/// Parses a byte out of its textual representation
+///
+/// # Remarks
+///
+/// The prefixes `0b`, `0o` and `0x` select binary, octal and hexadecimal. Text without
+/// a prefix is read as decimal, unless it contains whitespace in which case it is read
+/// as grouped binary (`1010 0101`). Underscores and whitespace between digits are ignored.
+///
+/// # Returns
+///
+/// An _InvalidLength_ error if no digits are given
+///
+/// An _InvalidCharacter_ error with the position of the first character that is not a digit
+///
+/// A _ValueOverflow_ error if the value does not fit into a byte
+///
+/// # Example
+///
+/// ```rust
+/// use uberbyte::UberByte;
+///
+/// assert_eq!(UberByte::from(0xA5), "0b1010_0101".parse::<UberByte>().unwrap());
+/// assert_eq!(UberByte::from(0xA5), "0xA5".parse::<UberByte>().unwrap());
+/// assert_eq!(UberByte::from(0xA5), "1010 0101".parse::<UberByte>().unwrap());
+/// ```
+impl FromStr for UberByte {
+    type Err = UberByteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim_start();
+        let mut offset = s.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+
+        let prefix = trimmed.get(..2).map(|prefix| prefix.to_ascii_lowercase());
+        let (radix, digits) = match prefix.as_deref() {
+            Some("0b") => (2, &trimmed[2..]),
+            Some("0o") => (8, &trimmed[2..]),
+            Some("0x") => (16, &trimmed[2..]),
+            _ if trimmed.contains(char::is_whitespace) => (2, trimmed),
+            _ => (10, trimmed),
+        };
+        offset += trimmed.len() - digits.len();
+
+        let mut value: u32 = 0;
+        let mut digit_count = 0;
+        for (position, character) in digits.char_indices() {
+            if character == '_' || character.is_whitespace() {
+                continue;
+            }
+            let digit = character
+                .to_digit(radix)
+                .ok_or(UberByteError::InvalidCharacter(offset + position))?;
+
+            value = value * radix + digit;
+            if value > u8::MAX as u32 {
+                return Err(UberByteError::ValueOverflow);
+            }
+            digit_count += 1;
+        }
+
+        match digit_count {
+            0 => Err(UberByteError::InvalidLength),
+            _ => Ok(UberByte::from(value as u8)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let expected = UberByte::from(0xA5);
+
+        for text in [
+            "0b1010_0101",
+            "0B10100101",
+            "0xA5",
+            "0xa5",
+            "0o245",
+            "165",
+            "1010 0101",
+            " 165 ",
+        ] {
+            assert_eq!(expected, text.parse::<UberByte>().unwrap(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn from_str_errors() {
+        assert!(matches!(
+            "".parse::<UberByte>(),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            "0x".parse::<UberByte>(),
+            Err(UberByteError::InvalidLength)
+        ));
+        assert!(matches!(
+            "256".parse::<UberByte>(),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert!(matches!(
+            "0b1_0000_0000".parse::<UberByte>(),
+            Err(UberByteError::ValueOverflow)
+        ));
+        assert!(matches!(
+            "0b102".parse::<UberByte>(),
+            Err(UberByteError::InvalidCharacter(4))
+        ));
+        assert!(matches!(
+            "1010 0201".parse::<UberByte>(),
+            Err(UberByteError::InvalidCharacter(6))
+        ));
+        assert!(matches!(
+            "-1".parse::<UberByte>(),
+            Err(UberByteError::InvalidCharacter(0))
+        ));
+    }
+}
@@ -0,0 +1,334 @@
+//! Operators mixing UberByte with plain integers and references
+//!
+//! ```rust
+//! use uberbyte::{UberByte, FIFTH_BIT_MASK};
+//!
+//! let my_byte = UberByte::from(0b_0000_0001);
+//!
+//! assert_eq!(0b_0010_0001, my_byte | FIFTH_BIT_MASK);
+//! assert_eq!(UberByte::from(0b_0000_0100), my_byte << 2);
+//! assert_eq!(UberByte::MIN, my_byte << 8);
+//! assert_eq!(0b_1111_1110, !my_byte);
+//! ```
+
+use std::{
+    cmp::Ordering,
+    ops::{
+        Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl,
+        ShlAssign, Shr, ShrAssign,
+    },
+};
+
+use crate::UberByte;
+
+impl UberByte {
+    /// Shifts the byte to the left
+    ///
+    /// # Returns
+    ///
+    /// The shifted byte, None if the shift amount is 8 or bigger
+    pub fn checked_shl(&self, rhs: u32) -> Option<UberByte> {
+        self.into_u8().checked_shl(rhs).map(UberByte::from)
+    }
+
+    /// Shifts the byte to the right
+    ///
+    /// # Returns
+    ///
+    /// The shifted byte, None if the shift amount is 8 or bigger
+    pub fn checked_shr(&self, rhs: u32) -> Option<UberByte> {
+        self.into_u8().checked_shr(rhs).map(UberByte::from)
+    }
+}
+
+impl Not for UberByte {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        UberByte::from(!self.into_u8())
+    }
+}
+
+impl Not for &UberByte {
+    type Output = UberByte;
+
+    fn not(self) -> Self::Output {
+        !*self
+    }
+}
+
+/// Shifting all bits out of the byte results in 0 instead of a panic
+impl Shl<u32> for UberByte {
+    type Output = Self;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        self.checked_shl(rhs).unwrap_or(UberByte::MIN)
+    }
+}
+
+/// Shifting all bits out of the byte results in 0 instead of a panic
+impl Shr<u32> for UberByte {
+    type Output = Self;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        self.checked_shr(rhs).unwrap_or(UberByte::MIN)
+    }
+}
+
+impl Shl<u8> for UberByte {
+    type Output = Self;
+
+    fn shl(self, rhs: u8) -> Self::Output {
+        self << rhs as u32
+    }
+}
+
+impl Shr<u8> for UberByte {
+    type Output = Self;
+
+    fn shr(self, rhs: u8) -> Self::Output {
+        self >> rhs as u32
+    }
+}
+
+/// Allows shifting by plain integer literals
+///
+/// # Panics
+///
+/// Like the integer operators, if the shift amount is negative
+impl Shl<i32> for UberByte {
+    type Output = Self;
+
+    fn shl(self, rhs: i32) -> Self::Output {
+        match u32::try_from(rhs) {
+            Ok(rhs) => self << rhs,
+            Err(_) => panic!("attempt to shift left by a negative amount"),
+        }
+    }
+}
+
+/// Allows shifting by plain integer literals
+///
+/// # Panics
+///
+/// Like the integer operators, if the shift amount is negative
+impl Shr<i32> for UberByte {
+    type Output = Self;
+
+    fn shr(self, rhs: i32) -> Self::Output {
+        match u32::try_from(rhs) {
+            Ok(rhs) => self >> rhs,
+            Err(_) => panic!("attempt to shift right by a negative amount"),
+        }
+    }
+}
+
+impl Add<u8> for UberByte {
+    type Output = Self;
+
+    fn add(self, rhs: u8) -> Self::Output {
+        self + UberByte::from(rhs)
+    }
+}
+
+macro_rules! bitwise_u8 {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait<u8> for UberByte {
+            type Output = Self;
+
+            fn $method(self, rhs: u8) -> Self::Output {
+                UberByte::from(self.into_u8() $op rhs)
+            }
+        }
+
+        impl $trait<UberByte> for u8 {
+            type Output = UberByte;
+
+            fn $method(self, rhs: UberByte) -> Self::Output {
+                UberByte::from(self $op rhs.into_u8())
+            }
+        }
+    };
+}
+
+bitwise_u8!(BitOr, bitor, |);
+bitwise_u8!(BitAnd, bitand, &);
+bitwise_u8!(BitXor, bitxor, ^);
+
+macro_rules! assign_op {
+    ($trait:ident, $method:ident, $op_trait:ident, $op_method:ident, $rhs:ty) => {
+        impl $trait<$rhs> for UberByte {
+            fn $method(&mut self, rhs: $rhs) {
+                *self = $op_trait::$op_method(*self, rhs);
+            }
+        }
+    };
+}
+
+assign_op!(AddAssign, add_assign, Add, add, u8);
+assign_op!(BitOrAssign, bitor_assign, BitOr, bitor, u8);
+assign_op!(BitAndAssign, bitand_assign, BitAnd, bitand, u8);
+assign_op!(BitXorAssign, bitxor_assign, BitXor, bitxor, u8);
+assign_op!(ShlAssign, shl_assign, Shl, shl, u8);
+assign_op!(ShlAssign, shl_assign, Shl, shl, u32);
+assign_op!(ShrAssign, shr_assign, Shr, shr, u8);
+assign_op!(ShrAssign, shr_assign, Shr, shr, u32);
+assign_op!(ShlAssign, shl_assign, Shl, shl, i32);
+assign_op!(ShrAssign, shr_assign, Shr, shr, i32);
+assign_op!(AddAssign, add_assign, Add, add, &UberByte);
+assign_op!(BitOrAssign, bitor_assign, BitOr, bitor, &UberByte);
+assign_op!(BitAndAssign, bitand_assign, BitAnd, bitand, &UberByte);
+assign_op!(BitXorAssign, bitxor_assign, BitXor, bitxor, &UberByte);
+
+macro_rules! ref_op {
+    ($trait:ident, $method:ident, $rhs:ty) => {
+        impl $trait<$rhs> for &UberByte {
+            type Output = UberByte;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $trait::$method(*self, rhs)
+            }
+        }
+
+        impl $trait<&$rhs> for UberByte {
+            type Output = UberByte;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $trait::$method(self, *rhs)
+            }
+        }
+
+        impl $trait<&$rhs> for &UberByte {
+            type Output = UberByte;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $trait::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+ref_op!(Add, add, UberByte);
+ref_op!(Add, add, u8);
+ref_op!(BitOr, bitor, UberByte);
+ref_op!(BitOr, bitor, u8);
+ref_op!(BitAnd, bitand, UberByte);
+ref_op!(BitAnd, bitand, u8);
+ref_op!(BitXor, bitxor, UberByte);
+ref_op!(BitXor, bitxor, u8);
+ref_op!(Shl, shl, UberByte);
+ref_op!(Shl, shl, u8);
+ref_op!(Shl, shl, u32);
+ref_op!(Shr, shr, UberByte);
+ref_op!(Shr, shr, u8);
+ref_op!(Shr, shr, u32);
+ref_op!(Shl, shl, i32);
+ref_op!(Shr, shr, i32);
+
+impl PartialEq<u8> for UberByte {
+    fn eq(&self, other: &u8) -> bool {
+        self.into_u8() == *other
+    }
+}
+
+impl PartialEq<UberByte> for u8 {
+    fn eq(&self, other: &UberByte) -> bool {
+        *self == other.into_u8()
+    }
+}
+
+impl PartialOrd<u8> for UberByte {
+    fn partial_cmp(&self, other: &u8) -> Option<Ordering> {
+        self.into_u8().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<UberByte> for u8 {
+    fn partial_cmp(&self, other: &UberByte) -> Option<Ordering> {
+        self.partial_cmp(&other.into_u8())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FIFTH_BIT_MASK, ZERO_BIT_MASK};
+
+    #[test]
+    fn bitwise_with_u8() {
+        let my_byte = UberByte::from(0b_0011_0000);
+
+        assert_eq!(0b_0011_0001, my_byte | ZERO_BIT_MASK);
+        assert_eq!(0b_0010_0000, my_byte & FIFTH_BIT_MASK);
+        assert_eq!(0b_0001_0000, my_byte ^ FIFTH_BIT_MASK);
+        assert_eq!(0b_0011_0001, ZERO_BIT_MASK | my_byte);
+    }
+
+    #[test]
+    fn assign_with_u8() {
+        let mut my_byte = UberByte::from(0b_0000_0001);
+
+        my_byte |= 0b_0000_0010;
+        my_byte <<= 2u32;
+        my_byte += 1;
+
+        assert_eq!(0b_0000_1101, my_byte);
+
+        my_byte >>= 2;
+        assert_eq!(0b_0000_0011, my_byte);
+    }
+
+    #[test]
+    fn shift_out_of_range() {
+        let my_byte = UberByte::MAX;
+
+        assert_eq!(0b_1000_0000, my_byte << 7u8);
+        assert_eq!(0, my_byte << 8u8);
+        assert_eq!(0, my_byte >> 100u32);
+        assert_eq!(0, my_byte << UberByte::from(9));
+        assert_eq!(UberByte::MIN, my_byte >> 8);
+        assert_eq!(None, my_byte.checked_shl(8));
+        assert_eq!(Some(UberByte::from(0b_0111_1111)), my_byte.checked_shr(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_shl() {
+        let _byte = UberByte::MAX << -1;
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_shr() {
+        let mut my_byte = UberByte::MAX;
+        my_byte >>= -1;
+    }
+
+    #[test]
+    fn not() {
+        assert_eq!(UberByte::MAX, !UberByte::MIN);
+        assert_eq!(0b_0101_0101, !&UberByte::from(0b_1010_1010));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn references() {
+        let left = UberByte::from(0b_0000_0011);
+        let right = UberByte::from(0b_0000_0110);
+
+        assert_eq!(0b_0000_0010, &left & &right);
+        assert_eq!(0b_0000_0111, left | &right);
+        assert_eq!(0b_0000_0101, &left ^ right);
+        assert_eq!(0b_0000_1100, &left << &2u8);
+    }
+
+    #[test]
+    fn compare_with_u8() {
+        let my_byte = UberByte::from(42);
+
+        assert!(my_byte == 42);
+        assert!(42 == my_byte);
+        assert!(my_byte > 41);
+        assert!(43 > my_byte);
+    }
+}
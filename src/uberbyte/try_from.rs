@@ -14,6 +14,28 @@ impl From<&u8> for UberByte {
     }
 }
 
+impl From<[bool; 8]> for UberByte {
+    /// Creates a byte where the bool at index n defines bit n
+    fn from(value: [bool; 8]) -> Self {
+        let value = value
+            .iter()
+            .enumerate()
+            .fold(0, |byte, (index, bit)| byte | ((*bit as u8) << index));
+        UberByte { value }
+    }
+}
+
+impl From<UberByte> for [bool; 8] {
+    /// Returns the bits of the byte where index n holds bit n
+    fn from(value: UberByte) -> Self {
+        let mut bits = [false; 8];
+        for (index, bit) in bits.iter_mut().enumerate() {
+            *bit = value.is_bit_set(index);
+        }
+        bits
+    }
+}
+
 macro_rules! try_from_unsigned {
     ($source:ty) => {
         impl TryFrom<$source> for UberByte {
@@ -153,4 +175,15 @@ mod test {
     test_signed!(i64, i64);
     test_signed!(i128, i128);
     test_signed!(isize, isize);
+
+    #[test]
+    fn bool_array_round_trip() {
+        let bits = [true, false, true, false, false, false, false, true];
+
+        let byte = UberByte::from(bits);
+        let back: [bool; 8] = byte.into();
+
+        assert_eq!(0b_1000_0101, byte.into_u8());
+        assert_eq!(bits, back);
+    }
 }